@@ -0,0 +1,593 @@
+//! Computes the errors of all implemented functions and prints them as tables.
+//!
+//! The testing follows the methodology described in the documentation of
+//! `nikisas_test`: the approximation error is measured in the primary range of
+//! each function and the additional error caused by the argument reduction on
+//! its entire domain. The latter table corresponds to the one in the
+//! documentation of nikisas, its maximum errors are measured for all single
+//! precision numbers in the domains, which takes a while.
+//!
+//! ```plain
+//! cargo run --release --example quality_report [-- --csv]
+//! ```
+
+use nikisas_test::error::ErrorSnapshot;
+use nikisas_test::float::FloatExt;
+use nikisas_test::prelude::*;
+use nikisas_test::utils::{avoid, avoid_mults, avoid_odd_mults, shift_left, shift_right};
+use nikisas_test::Report;
+
+use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use core::fmt;
+
+fn primary() -> Report<f32> {
+    let mut report = Report::new();
+
+    report.add(
+        "exp",
+        &UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+            .error(|x| (nikisas::exp(x), x.exp())),
+    );
+
+    report.add(
+        "exp_neg",
+        &UniformSample::with_count(0.0, 2.0f32.ln() / 2.0, 100000)
+            .error(|x| (nikisas::exp_neg(x), (-x).exp())),
+    );
+
+    report.add(
+        "expm1",
+        &UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+            .error(|x| (nikisas::expm1(x), x.exp_m1())),
+    );
+
+    report.add(
+        "ln",
+        &UniformSample::with_count(1.0 / 2.0f32.sqrt(), 2.0f32.sqrt(), 100000)
+            .error(|x| (nikisas::ln(x), x.ln())),
+    );
+
+    report.add(
+        "log2",
+        &UniformSample::with_count(1.0 / 2.0f32.sqrt(), 2.0f32.sqrt(), 100000)
+            .error(|x| (nikisas::log2(x), x.log2())),
+    );
+
+    report.add(
+        "log10",
+        &UniformSample::with_count(1.0 / 2.0f32.sqrt(), 2.0f32.sqrt(), 100000)
+            .error(|x| (nikisas::log10(x), x.log10())),
+    );
+
+    report.add(
+        "pow2",
+        &UniformSample::with_count(-0.5, 0.5, 100000).error(|x| (nikisas::pow2(x), x.exp2())),
+    );
+
+    report.add(
+        "pow10",
+        &UniformSample::with_count(-0.5, 0.5, 100000)
+            .error(|x| (nikisas::pow10(x), 10.0f32.powf(x))),
+    );
+
+    // The primary range of sqrt is small enough to be tested exhaustively.
+    report.add(
+        "sqrt",
+        &Exhaustive::bounded(1.0, 4.0).error(|x| (nikisas::sqrt(x), x.sqrt())),
+    );
+
+    report.add(
+        "sqrt_signed",
+        &UniformSample::with_count(-4.0, 4.0, 100000)
+            .error(|x| (nikisas::sqrt_signed(x), x.signum() * x.abs().sqrt())),
+    );
+
+    report.add(
+        "recip_sqrt",
+        &Exhaustive::bounded(1.0, 4.0).error(|x| (nikisas::recip_sqrt(x), 1.0 / x.sqrt())),
+    );
+
+    report.add(
+        "sin",
+        &UniformSample::with_count(-FRAC_PI_4, FRAC_PI_4, 100000)
+            .error(|x| (nikisas::sin(x), x.sin())),
+    );
+
+    report.add(
+        "cos",
+        &UniformSample::with_count(-FRAC_PI_4, FRAC_PI_4, 100000)
+            .error(|x| (nikisas::cos(x), x.cos())),
+    );
+
+    report.add(
+        "sincos",
+        &UniformSample::with_count(-FRAC_PI_4, FRAC_PI_4, 100000).fold(Error::new(), sincos),
+    );
+
+    report.add(
+        "tan",
+        &UniformSample::with_count(shift_right(-FRAC_PI_2), shift_left(FRAC_PI_2), 100000)
+            .error(|x| (nikisas::tan(x), x.tan())),
+    );
+
+    report.add(
+        "cot",
+        &UniformSample::with_count(shift_right(0.0), shift_left(PI), 100000)
+            .error(|x| (nikisas::cot(x), 1.0 / x.tan())),
+    );
+
+    report.add(
+        "sinpi",
+        &UniformSample::with_count(-0.25, 0.25, 100000).error(|x| (nikisas::sinpi(x), sinpi(x))),
+    );
+
+    report.add(
+        "cospi",
+        &UniformSample::with_count(-0.25, 0.25, 100000).error(|x| (nikisas::cospi(x), cospi(x))),
+    );
+
+    report.add(
+        "tanpi",
+        &UniformSample::with_count(-0.25, 0.25, 100000).error(|x| (nikisas::tanpi(x), tanpi(x))),
+    );
+
+    report.add(
+        "atan",
+        &UniformSample::with_count(-1.0, 1.0, 100000).error(|x| (nikisas::atan(x), x.atan())),
+    );
+
+    report
+}
+
+// The real values of the functions of π * x. Both the reduction by the period
+// and the product with π are exact in double precision, so these are precise
+// on the whole domain. The zeros and poles are handled separately, because the
+// double precision multiples of π are not exact.
+fn sinpi(x: f32) -> f32 {
+    let r = x % 2.0;
+
+    if r % 1.0 == 0.0 {
+        0.0
+    } else {
+        (core::f64::consts::PI * r as f64).sin() as f32
+    }
+}
+
+fn cospi(x: f32) -> f32 {
+    let r = x % 2.0;
+
+    if r.abs() % 1.0 == 0.5 {
+        0.0
+    } else {
+        (core::f64::consts::PI * r as f64).cos() as f32
+    }
+}
+
+fn tanpi(x: f32) -> f32 {
+    let r = x % 1.0;
+
+    if r == 0.0 {
+        0.0
+    } else if r.abs() == 0.5 {
+        f32::INFINITY
+    } else {
+        (core::f64::consts::PI * r as f64).tan() as f32
+    }
+}
+
+// Both results of sincos contribute to the same errors.
+fn sincos(mut error: Error<f32, f32>, x: f32) -> Error<f32, f32> {
+    let (sin, cos) = nikisas::sincos(x);
+    error.calculate(x, sin, x.sin());
+    error.calculate(x, cos, x.cos());
+    error
+}
+
+// Adapts a function returning the computed and the real value for `measure`.
+// The inputs for which the real value overflows, for example at the poles of
+// tangent, are skipped.
+fn values<T>(compute: T) -> impl Fn(Error<f32, f32>, f32) -> Error<f32, f32>
+where
+    T: Fn(f32) -> (f32, f32),
+{
+    move |mut error, x| {
+        let (computed, real) = compute(x);
+
+        if real.is_finite() {
+            error.calculate(x, computed, real);
+        }
+
+        error
+    }
+}
+
+// The maximum errors are measured for all single precision numbers in the
+// domain, the root mean square error on a random sample. Measured exhaustively,
+// the latter would be dominated by tiny inputs, which are the vast majority of
+// all numbers.
+fn measure<F, D, T>(low: f32, high: f32, sample: D, calculate: T) -> ErrorSnapshot<F>
+where
+    F: FloatExt,
+    D: Iterator<Item = f32>,
+    T: Fn(Error<F, f32>, f32) -> Error<F, f32>,
+{
+    let exhaustive = Exhaustive::bounded(low, high).fold(Error::new(), &calculate);
+    let sampled = sample.fold(Error::new(), &calculate);
+
+    ErrorSnapshot {
+        rms: sampled.rms(),
+        ..exhaustive.snapshot()
+    }
+}
+
+// The domain of logarithms, including subnormal numbers, sampled uniformly in
+// the logarithmic scale.
+fn log_domain() -> UniformSample<f32> {
+    UniformSample::builder(1e-45, 3.4e+38)
+        .count(100000)
+        .log_scale()
+        .build()
+}
+
+fn full() -> Report<f32> {
+    let mut report = Report::new();
+
+    report.add_snapshot(
+        "exp",
+        measure(
+            -87.3,
+            88.7,
+            UniformSample::with_count(-87.3, 88.7, 10000),
+            values(|x| (nikisas::exp(x), x.exp())),
+        ),
+    );
+
+    #[cfg(not(feature = "bare-metal"))]
+    report.add_snapshot(
+        "exp_mixed",
+        measure(
+            -87.3,
+            88.7,
+            UniformSample::with_count(-87.3, 88.7, 10000),
+            values(|x| (nikisas::exp_mixed(x as f64), x.exp())),
+        ),
+    );
+
+    // The results above 87.3 are subnormal, their relative error is large
+    // even when they are correctly rounded.
+    report.add_snapshot(
+        "exp_neg",
+        measure(
+            0.0,
+            87.3,
+            UniformSample::with_count(0.0, 87.3, 10000),
+            values(|x| (nikisas::exp_neg(x), (-x).exp())),
+        ),
+    );
+
+    report.add_snapshot(
+        "expm1",
+        measure(
+            -87.3,
+            88.7,
+            UniformSample::with_count(-87.3, 88.7, 10000),
+            values(|x| (nikisas::expm1(x), x.exp_m1())),
+        ),
+    );
+
+    // Uniform sampling of such a wide interval would almost never hit the
+    // primary range, where the error is the largest.
+    report.add_snapshot(
+        "ln",
+        measure(
+            1e-45,
+            f32::MAX,
+            log_domain(),
+            values(|x| (nikisas::ln(x), x.ln())),
+        ),
+    );
+
+    report.add_snapshot(
+        "log2",
+        measure(
+            1e-45,
+            f32::MAX,
+            log_domain(),
+            values(|x| (nikisas::log2(x), x.log2())),
+        ),
+    );
+
+    report.add_snapshot(
+        "log10",
+        measure(
+            1e-45,
+            f32::MAX,
+            log_domain(),
+            values(|x| (nikisas::log10(x), x.log10())),
+        ),
+    );
+
+    report.add_snapshot(
+        "pow2",
+        measure(
+            -126.0,
+            127.9,
+            UniformSample::with_count(-126.0, 127.9, 10000),
+            values(|x| (nikisas::pow2(x), x.exp2())),
+        ),
+    );
+
+    report.add_snapshot(
+        "pow10",
+        measure(
+            -37.9,
+            38.5,
+            UniformSample::with_count(-37.9, 38.5, 10000),
+            values(|x| (nikisas::pow10(x), 10.0f32.powf(x))),
+        ),
+    );
+
+    report.add_snapshot(
+        "sqrt",
+        measure(
+            0.0,
+            f32::MAX,
+            UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000),
+            values(|x| (nikisas::sqrt(x), x.sqrt())),
+        ),
+    );
+
+    report.add_snapshot(
+        "sqrt_signed",
+        measure(
+            -f32::MAX,
+            f32::MAX,
+            UniformSample::with_count(-3.4e+38, 3.4e+38, 10000),
+            values(|x| (nikisas::sqrt_signed(x), x.signum() * x.abs().sqrt())),
+        ),
+    );
+
+    report.add_snapshot(
+        "recip_sqrt",
+        measure(
+            1e-45,
+            f32::MAX,
+            UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000),
+            values(|x| (nikisas::recip_sqrt(x), 1.0 / x.sqrt())),
+        ),
+    );
+
+    report.add_snapshot(
+        "sin",
+        measure(
+            -2.1e+9,
+            2.1e+9,
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).filter(avoid_mults(PI)),
+            values(|x| (nikisas::sin(x), x.sin())),
+        ),
+    );
+
+    report.add_snapshot(
+        "cos",
+        measure(
+            -2.1e+9,
+            2.1e+9,
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).filter(avoid_odd_mults(FRAC_PI_2)),
+            values(|x| (nikisas::cos(x), x.cos())),
+        ),
+    );
+
+    report.add_snapshot(
+        "sincos",
+        measure(
+            -2.1e+9,
+            2.1e+9,
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).filter(avoid_mults(FRAC_PI_2)),
+            sincos,
+        ),
+    );
+
+    report.add_snapshot(
+        "tan",
+        measure(
+            -2.1e+9,
+            2.1e+9,
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).filter(avoid_mults(FRAC_PI_2)),
+            values(|x| (nikisas::tan(x), x.tan())),
+        ),
+    );
+
+    report.add_snapshot(
+        "cot",
+        measure(
+            -2.1e+9,
+            2.1e+9,
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).filter(avoid_mults(FRAC_PI_2)),
+            values(|x| (nikisas::cot(x), 1.0 / x.tan())),
+        ),
+    );
+
+    // Large numbers are integers or have only a few fractional bits, for which
+    // the results are trivial, so the sample is limited to the same range as
+    // in the tests.
+    report.add_snapshot(
+        "sinpi",
+        measure(
+            -f32::MAX,
+            f32::MAX,
+            UniformSample::with_count(-1e6, 1e6, 10000),
+            values(|x| (nikisas::sinpi(x), sinpi(x))),
+        ),
+    );
+
+    report.add_snapshot(
+        "cospi",
+        measure(
+            -f32::MAX,
+            f32::MAX,
+            UniformSample::with_count(-1e6, 1e6, 10000),
+            values(|x| (nikisas::cospi(x), cospi(x))),
+        ),
+    );
+
+    report.add_snapshot(
+        "tanpi",
+        measure(
+            -f32::MAX,
+            f32::MAX,
+            UniformSample::with_count(-1e6, 1e6, 10000).filter(avoid_odd_mults(0.5)),
+            values(|x| (nikisas::tanpi(x), tanpi(x))),
+        ),
+    );
+
+    // Uniform sampling of such a wide interval would give almost only the
+    // inputs for which the result is ±π/2.
+    report.add_snapshot(
+        "atan",
+        measure(
+            -f32::MAX,
+            f32::MAX,
+            BitUniformSample::with_count(-3.4e+38, 3.4e+38, 10000),
+            values(|x| (nikisas::atan(x), x.atan())),
+        ),
+    );
+
+    // Functions of two arguments have too many inputs to be tested
+    // exhaustively, they are tested on a grid of sampled values.
+    report.add(
+        "pow",
+        &UniformSample::with_count(shift_right(0.0f32), 32.0, 500).fold(
+            Error::new(),
+            |error, x| {
+                UniformSample::with_count(-10.0, 10.0, 500)
+                    .filter(avoid(0.0))
+                    .fold(error, |mut error, p| {
+                        if x.powf(p).is_finite() {
+                            error.calculate((x, p), nikisas::pow(x, p), x.powf(p));
+                        }
+                        error
+                    })
+            },
+        ),
+    );
+
+    report.add(
+        "powu",
+        &UniformSample::with_count(-32.0f32, 32.0, 500).fold(Error::new(), |error, x| {
+            (0..=25u32).fold(error, |mut error, n| {
+                let real = (x as f64).powi(n as i32) as f32;
+                if real.is_finite() {
+                    error.calculate((x, n), nikisas::powu(x, n), real);
+                }
+                error
+            })
+        }),
+    );
+
+    report.add(
+        "hypot",
+        &UniformSample::with_count(-100.0f32, 100.0, 500).fold(Error::new(), |error, x| {
+            UniformSample::with_count(-100.0, 100.0, 500).fold(error, |mut error, y| {
+                error.calculate((x, y), nikisas::hypot(x, y), x.hypot(y));
+                error
+            })
+        }),
+    );
+
+    report.add(
+        "atan2",
+        &UniformSample::with_count(-100.0f32, 100.0, 500).fold(Error::new(), |error, y| {
+            UniformSample::with_count(-100.0, 100.0, 500).fold(error, |mut error, x| {
+                error.calculate((y, x), nikisas::atan2(y, x), y.atan2(x));
+                error
+            })
+        }),
+    );
+
+    // The quotient has at most 20 bits and the divisor 24 bits, so the real
+    // value is exact in double precision.
+    report.add(
+        "remainder",
+        &UniformSample::with_count(-1e+6f32, 1e+6, 500).fold(Error::new(), |error, x| {
+            UniformSample::with_count(-100.0, 100.0, 500)
+                .filter(|y: &f32| y.abs() >= 1.0)
+                .fold(error, |mut error, y| {
+                    let real =
+                        (x as f64 - (x as f64 / y as f64).round_ties_even() * y as f64) as f32;
+                    error.calculate((x, y), nikisas::remainder(x, y), real);
+                    error
+                })
+        }),
+    );
+
+    report
+}
+
+// Double precision results, also the pairs of single precision numbers, are
+// compared with double precision real values.
+fn double() -> Report<f64> {
+    let mut report = Report::new();
+
+    let pair = |compute: fn(f32) -> (f32, f32), real: fn(f64) -> f64| {
+        move |mut error: Error<f64, f32>, x: f32| {
+            error.calculate_pair(x, compute(x), real(x as f64));
+            error
+        }
+    };
+
+    report.add_snapshot(
+        "exp_dd",
+        measure(
+            -87.3,
+            88.7,
+            UniformSample::with_count(-87.3, 88.7, 10000),
+            pair(nikisas::exp_dd, f64::exp),
+        ),
+    );
+
+    report.add_snapshot(
+        "ln_dd",
+        measure(1e-45, f32::MAX, log_domain(), pair(nikisas::ln_dd, f64::ln)),
+    );
+
+    // There are too many double precision numbers to test them exhaustively.
+    // The real values are computed by the standard library in double
+    // precision too, so the errors are bounded by its accuracy.
+    #[cfg(not(feature = "bare-metal"))]
+    {
+        report.add(
+            "exp_f64",
+            &UniformSample::with_count(-708.3, 709.7, 100000)
+                .error(|x| (nikisas::exp_f64(x), x.exp())),
+        );
+
+        report.add(
+            "ln_f64",
+            &UniformSample::builder(5e-324, 1.7e+308)
+                .count(100000)
+                .log_scale()
+                .build()
+                .error(|x| (nikisas::ln_f64(x), x.ln())),
+        );
+    }
+
+    report
+}
+
+fn print<F: FloatExt + fmt::LowerExp>(title: &str, report: &Report<F>, csv: bool) {
+    if csv {
+        report.print_csv();
+    } else {
+        println!("## {}\n", title);
+        report.print_markdown();
+        println!();
+    }
+}
+
+fn main() {
+    let csv = std::env::args().any(|arg| arg == "--csv");
+
+    print("Primary range", &primary(), csv);
+    print("Entire domain", &full(), csv);
+    print("Double precision", &double(), csv);
+}
@@ -0,0 +1,166 @@
+//! Exporting per-sample errors as Arrow record batches (requires `arrow`
+//! feature).
+//!
+//! Writing millions of samples line by line is slow, and the error
+//! distributions are often analyzed by data-science tools anyway. A
+//! [`SampleBatch`] collects the samples of a sweep into columns and converts
+//! them into an Arrow [`RecordBatch`], which can then be written to Parquet or
+//! passed to other tools without any copying.
+//!
+//! # Schema
+//!
+//! The schema is fixed (see [`SampleBatch::schema`]). All columns are 64-bit
+//! floating point numbers, the values of single precision types are converted
+//! exactly.
+//!
+//! | column     | nullable | meaning                                      |
+//! | ---------- | -------- | -------------------------------------------- |
+//! | `arg`      | no       | input argument                               |
+//! | `computed` | no       | computed value                               |
+//! | `real`     | no       | real value                                   |
+//! | `rel`      | yes      | relative error, null if the real value is 0  |
+//! | `abs`      | no       | absolute error                               |
+//!
+//! # Examples
+//!
+//! ```
+//! use nikisas_test::prelude::*;
+//! use nikisas_test::arrow::SampleBatch;
+//!
+//! # fn exp(x: f32) -> f32 { x.exp() }
+//! let batch = UniformSample::with_count(-87.3, 88.7, 10000)
+//!     .fold(SampleBatch::new(), |mut batch, x| {
+//!         batch.calculate(x, exp(x), x.exp());
+//!         batch
+//!     })
+//!     .finish();
+//!
+//! assert_eq!(batch.num_rows(), 10000);
+//! ```
+//!
+//! [`SampleBatch`]: struct.SampleBatch.html
+//! [`SampleBatch::schema`]: struct.SampleBatch.html#method.schema
+//! [`RecordBatch`]: https://docs.rs/arrow-array/latest/arrow_array/struct.RecordBatch.html
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::error::relative;
+use crate::float::FloatExt;
+
+/// Collector of per-sample values and errors which are converted into an Arrow
+/// record batch with a fixed schema (see [module documentation]).
+///
+/// [module documentation]: index.html
+pub struct SampleBatch<F> {
+    arg: Vec<f64>,
+    computed: Vec<f64>,
+    real: Vec<f64>,
+    rel: Vec<Option<f64>>,
+    abs: Vec<f64>,
+    _float: PhantomData<F>,
+}
+
+impl<F: FloatExt> SampleBatch<F> {
+    /// Initializes an empty batch.
+    pub fn new() -> Self {
+        SampleBatch {
+            arg: Vec::new(),
+            computed: Vec::new(),
+            real: Vec::new(),
+            rel: Vec::new(),
+            abs: Vec::new(),
+            _float: PhantomData,
+        }
+    }
+
+    /// Returns the schema of the record batch.
+    pub fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("arg", DataType::Float64, false),
+            Field::new("computed", DataType::Float64, false),
+            Field::new("real", DataType::Float64, false),
+            Field::new("rel", DataType::Float64, true),
+            Field::new("abs", DataType::Float64, false),
+        ])
+    }
+
+    /// Calculates the errors between computed value and real value and
+    /// appends them as a new row.
+    pub fn calculate(&mut self, arg: F, computed: F, real: F) {
+        let abs = (computed - real).abs();
+
+        self.arg.push(arg.to_f64());
+        self.computed.push(computed.to_f64());
+        self.real.push(real.to_f64());
+        self.abs.push(abs.to_f64());
+
+        if real != F::zero() {
+            self.rel.push(Some(relative(abs, real).to_f64()));
+        } else {
+            self.rel.push(None);
+        }
+    }
+
+    /// Returns the number of collected samples.
+    pub fn len(&self) -> usize {
+        self.arg.len()
+    }
+
+    /// Determines whether there are no samples collected.
+    pub fn is_empty(&self) -> bool {
+        self.arg.is_empty()
+    }
+
+    /// Converts the collected samples into a record batch.
+    pub fn finish(self) -> RecordBatch {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(self.arg)),
+            Arc::new(Float64Array::from(self.computed)),
+            Arc::new(Float64Array::from(self.real)),
+            Arc::new(Float64Array::from(self.rel)),
+            Arc::new(Float64Array::from(self.abs)),
+        ];
+
+        // The columns match the schema by construction.
+        RecordBatch::try_new(Arc::new(Self::schema()), columns).unwrap()
+    }
+}
+
+impl<F: FloatExt> Default for SampleBatch<F> {
+    fn default() -> Self {
+        SampleBatch::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use arrow_array::Array;
+
+    #[test]
+    fn record_batch() {
+        let sweep = || UniformSample::with_count(-8.0f32, 8.0, 1000).chain(std::iter::once(0.0));
+
+        let batch = sweep().fold(SampleBatch::new(), |mut batch, x| {
+            batch.calculate(x, x.sin(), (x as f64).sin() as f32);
+            batch
+        });
+
+        let count = sweep().count();
+        assert_eq!(batch.len(), count);
+
+        let batch = batch.finish();
+        assert_eq!(batch.num_columns(), 5);
+        assert_eq!(batch.num_rows(), count);
+        assert_eq!(batch.schema().as_ref(), &SampleBatch::<f32>::schema());
+
+        // The relative error is undefined at zero.
+        let rel = batch.column_by_name("rel").unwrap();
+        assert_eq!(rel.null_count(), 1);
+    }
+}
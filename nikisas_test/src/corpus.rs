@@ -0,0 +1,162 @@
+//! Regression corpus of inputs that once failed.
+//!
+//! When a test finds inputs for which the implementation exceeds the error
+//! bounds, they can be collected using [`Domain::failures`] and saved to a
+//! file. After the bug is fixed, the saved inputs are replayed in every test
+//! run, so the bug stays fixed.
+//!
+//! The corpus is a plain text file with one input per line. Each line contains
+//! the ordinal of the number (see [`FloatExt::to_ordinal`]), which represents
+//! the number exactly, followed by its human-readable value. Empty lines and
+//! lines starting with `#` are ignored.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use nikisas_test::prelude::*;
+//! use nikisas_test::corpus::{replay_corpus, save_corpus};
+//!
+//! # fn exp(x: f32) -> f32 { x.exp() }
+//! let bounds = || ErrorBounds::new().rel(0.001);
+//!
+//! // Collect the failures and save them.
+//! let failures = UniformSample::with_count(-87.3, 88.7, 10000).failures(&bounds(), |x| (exp(x), x.exp()));
+//! save_corpus("tests/corpus/exp.txt", &failures).unwrap();
+//!
+//! // Later, replay them.
+//! replay_corpus("tests/corpus/exp.txt", bounds(), |x| (exp(x), x.exp()));
+//! ```
+//!
+//! [`Domain::failures`]: ../domain/trait.Domain.html#tymethod.failures
+//! [`FloatExt::to_ordinal`]: ../float/trait.FloatExt.html#tymethod.to_ordinal
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::domain::Domain;
+use crate::error::ErrorBounds;
+use crate::float::FloatExt;
+
+/// Writes the inputs to the file in the corpus format. If the file exists, it
+/// is overwritten.
+pub fn save_corpus<F: FloatExt, P: AsRef<Path>>(path: P, inputs: &[F]) -> io::Result<()> {
+    let mut file = BufWriter::new(fs::File::create(path)?);
+
+    for x in inputs {
+        writeln!(file, "{} {:?}", x.to_ordinal(), x)?;
+    }
+
+    file.flush()
+}
+
+/// Reads the inputs from the file in the corpus format.
+pub fn load_corpus<F: FloatExt, P: AsRef<Path>>(path: P) -> io::Result<Vec<F>> {
+    let file = BufReader::new(fs::File::open(path)?);
+    let mut inputs = Vec::new();
+
+    for line in file.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let ordinal = line
+            .split_whitespace()
+            .next()
+            .and_then(|ordinal| ordinal.parse::<i64>().ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid corpus line: {}", line),
+                )
+            })?;
+
+        inputs.push(F::from_ordinal(ordinal));
+    }
+
+    Ok(inputs)
+}
+
+/// Asserts the errors for the inputs saved in the corpus file to have given
+/// bounds. Panics if the file cannot be read.
+pub fn replay_corpus<F, P, T>(path: P, bounds: ErrorBounds<F>, compute: T)
+where
+    F: FloatExt,
+    P: AsRef<Path>,
+    T: Fn(F) -> (F, F),
+{
+    let path = path.as_ref();
+    let inputs = load_corpus::<F, _>(path)
+        .unwrap_or_else(|err| panic!("cannot read corpus {}: {}", path.display(), err));
+
+    inputs.into_iter().assert(bounds, compute);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::UniformSample;
+
+    // Implementation which is wrong for inputs greater than 1.5.
+    fn buggy(x: f32) -> (f32, f32) {
+        if x > 1.5 {
+            (x * 1.1, x)
+        } else {
+            (x, x)
+        }
+    }
+
+    fn corpus_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("nikisas_test_{}_{}.txt", name, std::process::id()))
+    }
+
+    #[test]
+    fn roundtrip() {
+        let path = corpus_path("roundtrip");
+        let inputs = [0.0f32, -1.5, 1e-45, 3.4e+38, 0.1];
+
+        save_corpus(&path, &inputs).unwrap();
+        let loaded = load_corpus::<f32, _>(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), inputs.len());
+        for (x, y) in inputs.iter().zip(loaded.iter()) {
+            assert_eq!(x.to_bits(), y.to_bits());
+        }
+    }
+
+    #[test]
+    fn replay() {
+        let path = corpus_path("replay");
+        let bounds = || ErrorBounds::new().rel(0.001);
+
+        let failures = UniformSample::with_count(1.0, 2.0, 100).failures(&bounds(), buggy);
+        assert!(!failures.is_empty());
+        assert!(failures.iter().all(|x| *x > 1.5));
+
+        save_corpus(&path, &failures).unwrap();
+
+        // The bug is still there.
+        let result = std::panic::catch_unwind(|| replay_corpus(&path, bounds(), buggy));
+        assert!(result.is_err());
+
+        // The bug is fixed.
+        replay_corpus(&path, bounds(), |x| (x, x));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn invalid() {
+        let path = corpus_path("invalid");
+        fs::write(&path, "# comment\n\n1065353216 1.0\nnot a number\n").unwrap();
+
+        let result = load_corpus::<f32, _>(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,149 @@
+//! Counting how many inputs hit individual branches of an implementation.
+//!
+//! The implementations usually contain special cases (e.g., exp(0) = 1), which
+//! are handled without any approximation. A random sample may easily miss
+//! them, so that only the general path is actually tested. [`BranchCounter`]
+//! makes it possible to check that all branches were exercised.
+//!
+//! # Examples
+//!
+//! ```
+//! use nikisas_test::prelude::*;
+//!
+//! let counter = Exhaustive::near(1.0f32, 1e-6).branch_coverage(|x| {
+//!     if (x - 1.0).abs() <= f32::EPSILON {
+//!         "one"
+//!     } else {
+//!         "general"
+//!     }
+//! });
+//!
+//! counter.assert_covered(&["one", "general"]);
+//! ```
+//!
+//! [`BranchCounter`]: struct.BranchCounter.html
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Counter of hits of named branches. The counting takes only shared
+/// reference, so the counter can be used inside `compute` closures passed to
+/// [`Domain`] methods.
+///
+/// [`Domain`]: ../domain/trait.Domain.html
+#[derive(Debug, Default)]
+pub struct BranchCounter {
+    hits: RefCell<BTreeMap<&'static str, u64>>,
+}
+
+impl BranchCounter {
+    /// Creates a counter with no hits.
+    pub fn new() -> Self {
+        BranchCounter::default()
+    }
+
+    /// Records a hit of given branch.
+    pub fn hit(&self, branch: &'static str) {
+        *self.hits.borrow_mut().entry(branch).or_insert(0) += 1;
+    }
+
+    /// Returns the number of hits of given branch.
+    pub fn hits(&self, branch: &str) -> u64 {
+        self.hits.borrow().get(branch).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of hits of all branches.
+    pub fn total(&self) -> u64 {
+        self.hits.borrow().values().sum()
+    }
+
+    /// Returns all branches that were hit at least once together with the
+    /// number of hits, sorted by the name.
+    pub fn branches(&self) -> Vec<(&'static str, u64)> {
+        self.hits
+            .borrow()
+            .iter()
+            .map(|(branch, hits)| (*branch, *hits))
+            .collect()
+    }
+
+    /// Asserts that all given branches were hit at least once.
+    pub fn assert_covered(&self, branches: &[&str]) {
+        let missed = branches
+            .iter()
+            .filter(|branch| self.hits(branch) == 0)
+            .collect::<Vec<_>>();
+
+        if !missed.is_empty() {
+            panic!(
+                "branches {:?} were not hit, hits: {:?}",
+                missed,
+                self.branches()
+            );
+        }
+    }
+
+    /// Prints the number of hits of individual branches in a plain,
+    /// human-readable form.
+    pub fn print_plain(&self, name: &str) {
+        let total = self.total();
+
+        for (branch, hits) in self.branches() {
+            println!(
+                "{}:\t{} = {} ({:.2}%)",
+                name,
+                branch,
+                hits,
+                100.0 * hits as f64 / total as f64
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Domain, UniformSample};
+
+    #[test]
+    fn counter() {
+        let counter = BranchCounter::new();
+
+        UniformSample::with_count(-1.0f32, 1.0, 1000)
+            .error(|x| {
+                if x < 0.0 {
+                    counter.hit("negative");
+                } else {
+                    counter.hit("positive");
+                }
+                (x, x)
+            })
+            .assert();
+
+        assert_eq!(counter.total(), 1000);
+        assert_eq!(counter.hits("negative") + counter.hits("positive"), 1000);
+        assert_eq!(counter.hits("zero"), 0);
+        assert_eq!(counter.branches().len(), 2);
+
+        counter.assert_covered(&["negative", "positive"]);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            counter.assert_covered(&["zero"])
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn branch_coverage() {
+        let counter = UniformSample::with_count(0.0f32, 10.0, 1000).branch_coverage(|x| {
+            if x < 1.0 {
+                "small"
+            } else {
+                "large"
+            }
+        });
+
+        assert_eq!(counter.total(), 1000);
+        assert!(counter.hits("small") > 50 && counter.hits("small") < 150);
+    }
+}
@@ -2,36 +2,50 @@
 //! that domain.
 //
 // Use [`UniformSample`] for random sampling in given interval. Use
-// [`Exhaustive`] to iterate over all machine numbers around an extreme of
-// interest.
+// [`BitUniformSample`] for random sampling where each machine number has the
+// same probability. Use [`SubnormalSample`] for random sampling of subnormal
+// numbers. Use [`Exhaustive`] to iterate over all machine numbers around an
+// extreme of interest.
 //
 // [`UniformSample`]: struct.UniformSample.html
+// [`BitUniformSample`]: struct.BitUniformSample.html
+// [`SubnormalSample`]: struct.SubnormalSample.html
 // [`Exhaustive`]: struct.Exhaustive.html
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+
 use rand::distributions::{DistIter, Uniform};
 use rand::prelude::*;
 use rand::rngs::SmallRng;
 
-use crate::error::{Error, ErrorBounds};
+use crate::coverage::BranchCounter;
+use crate::error::{Error, ErrorBounds, ErrorSplit, ExactError};
 use crate::float::FloatExt;
 
 /// Uniformly samples values in given interval. This should be primarily used
 /// for determining errors on the domain.
+///
+/// For more options, use [`builder`].
+///
+/// [`builder`]: struct.UniformSample.html#method.builder
 pub struct UniformSample<F: FloatExt> {
     count: usize,
-    iter: DistIter<Uniform<F>, SmallRng, F>,
+    seed: u64,
+    rng: SmallRng,
+    distr: Uniform<F>,
+    low: F,
+    high: F,
+    endpoints: Vec<F>,
+    log_scale: Option<bool>,
 }
 
 impl<F: FloatExt> UniformSample<F> {
     /// Creates new iterator. The number of sampled values is fixed to given
     /// count.
     pub fn with_count(low: F, high: F, count: usize) -> Self {
-        assert!(low < high);
-        let distr = Uniform::new_inclusive(low, high);
-        let rng = SmallRng::seed_from_u64(3);
-        let iter = rng.sample_iter(distr);
-
-        UniformSample { count, iter }
+        UniformSample::builder(low, high).count(count).build()
     }
 
     /// Creates new iterator. The number of samples is determined by the total
@@ -40,39 +54,372 @@ impl<F: FloatExt> UniformSample<F> {
     /// zeros, and the number of sampled values might become infeasible in such
     /// case. The `fraction` argument must be a number between zero and one.
     pub fn with_fraction(low: F, high: F, fraction: f32) -> Self {
-        assert!(low < high);
-        assert!(fraction > 0.0 && fraction <= 1.0);
-        let count = (low.floats_between(high) as f64 * fraction as f64).round() as usize;
+        UniformSample::builder(low, high).fraction(fraction).build()
+    }
 
+    /// Creates new iterator. The number of sampled values is given as a
+    /// density, that is, the number of samples per unit interval, so the count
+    /// scales with the width of the interval. This gives consistent coverage
+    /// when sweeping intervals of different sizes.
+    pub fn with_density(low: F, high: F, per_unit: F) -> Self {
+        let count = ((high - low) * per_unit).round().to_f64() as usize;
         UniformSample::with_count(low, high, count)
     }
+
+    /// Creates a builder for configuring the iterator. By default, the number
+    /// of sampled values is 10000.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// let sample = UniformSample::builder(1.0f32, 1e+30)
+    ///     .count(1000)
+    ///     .seed(42)
+    ///     .with_endpoints()
+    ///     .log_scale()
+    ///     .build();
+    ///
+    /// assert_eq!(sample.count(), 1000);
+    /// ```
+    pub fn builder(low: F, high: F) -> UniformSampleBuilder<F> {
+        UniformSampleBuilder {
+            low,
+            high,
+            count: SampleCount::Count(10000),
+            seed: 3,
+            endpoints: false,
+            log_scale: false,
+        }
+    }
+
+    /// Returns the width of the local interval represented by a sampled value,
+    /// up to a constant factor. It is constant for uniform sampling and |x|
+    /// for sampling in logarithmic scale. It is meant to be passed to
+    /// [`Error::weighted`].
+    ///
+    /// [`Error::weighted`]: ../error/struct.Error.html#method.weighted
+    pub fn weight(&self) -> impl Fn(&F) -> f64 + 'static {
+        let log_scale = self.log_scale.is_some();
+        move |x| {
+            if log_scale {
+                x.to_f64().abs()
+            } else {
+                1.0
+            }
+        }
+    }
+
+    /// Returns the seed of the random number generator. The sampled values are
+    /// fully determined by the configuration and the seed, so the value with
+    /// given index (for example [`Error::max_rel_index`]) can be regenerated by
+    /// building the same sample and taking its `nth` value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// let sample = UniformSample::builder(0.1f32, 1.0).seed(7).build();
+    /// let seed = sample.seed();
+    /// let error = sample.error(|x| (x.sin(), x));
+    ///
+    /// let worst = UniformSample::builder(0.1f32, 1.0)
+    ///     .seed(seed)
+    ///     .build()
+    ///     .nth(error.max_rel_index() as usize);
+    ///
+    /// assert_eq!(worst, Some(error.max_rel_arg()));
+    /// ```
+    ///
+    /// [`Error::max_rel_index`]: ../error/struct.Error.html#method.max_rel_index
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
 }
 
 impl<F: FloatExt> Iterator for UniformSample<F> {
     type Item = F;
 
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+
+        self.count -= 1;
+
+        if let Some(x) = self.endpoints.pop() {
+            return Some(x);
+        }
+
+        let x = self.distr.sample(&mut self.rng);
+
+        let x = match self.log_scale {
+            // Sampled value is the logarithm of the magnitude.
+            Some(negative) => {
+                let x = if negative {
+                    F::zero() - x.exp()
+                } else {
+                    x.exp()
+                };
+
+                // Guard against rounding errors of logarithm and exponential.
+                if x < self.low {
+                    self.low
+                } else if x > self.high {
+                    self.high
+                } else {
+                    x
+                }
+            }
+            None => x,
+        };
+
+        Some(x)
+    }
+}
+
+enum SampleCount {
+    Count(usize),
+    Fraction(f32),
+}
+
+/// Builder for [`UniformSample`] iterator. See [`UniformSample::builder`].
+///
+/// [`UniformSample`]: struct.UniformSample.html
+/// [`UniformSample::builder`]: struct.UniformSample.html#method.builder
+pub struct UniformSampleBuilder<F: FloatExt> {
+    low: F,
+    high: F,
+    count: SampleCount,
+    seed: u64,
+    endpoints: bool,
+    log_scale: bool,
+}
+
+impl<F: FloatExt> UniformSampleBuilder<F> {
+    /// Fixes the number of sampled values to given count.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = SampleCount::Count(count);
+        self
+    }
+
+    /// Determines the number of samples as given fraction of the total number
+    /// of machine numbers within the interval (see
+    /// [`UniformSample::with_fraction`]). The `fraction` argument must be a
+    /// number between zero and one.
+    ///
+    /// [`UniformSample::with_fraction`]: struct.UniformSample.html#method.with_fraction
+    pub fn fraction(mut self, fraction: f32) -> Self {
+        self.count = SampleCount::Fraction(fraction);
+        self
+    }
+
+    /// Sets the seed of the random number generator.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Includes the endpoints of the interval as the first two sampled values.
+    /// They are included in the total number of sampled values.
+    pub fn with_endpoints(mut self) -> Self {
+        self.endpoints = true;
+        self
+    }
+
+    /// Samples the values uniformly in logarithmic scale, that is, the
+    /// logarithms of the magnitudes are distributed uniformly. This more
+    /// simulates the distribution of numbers encountered in real-world for
+    /// large intervals. The interval must not contain zero.
+    pub fn log_scale(mut self) -> Self {
+        self.log_scale = true;
+        self
+    }
+
+    /// Creates the configured iterator.
+    pub fn build(self) -> UniformSample<F> {
+        let UniformSampleBuilder { low, high, .. } = self;
+        assert!(low < high);
+
+        let count = match self.count {
+            SampleCount::Count(count) => count,
+            SampleCount::Fraction(fraction) => {
+                assert!(fraction > 0.0 && fraction <= 1.0);
+                (low.floats_between(high) as f64 * fraction as f64).round() as usize
+            }
+        };
+
+        let (distr, log_scale) = if self.log_scale {
+            let positive = low > F::zero();
+            assert!(positive || high < F::zero());
+
+            if positive {
+                (Uniform::new_inclusive(low.ln(), high.ln()), Some(false))
+            } else {
+                let (low, high) = (F::zero() - high, F::zero() - low);
+                (Uniform::new_inclusive(low.ln(), high.ln()), Some(true))
+            }
+        } else {
+            (Uniform::new_inclusive(low, high), None)
+        };
+
+        // The values are popped from the end.
+        let endpoints = if self.endpoints {
+            vec![high, low]
+        } else {
+            Vec::new()
+        };
+
+        UniformSample {
+            count,
+            seed: self.seed,
+            rng: SmallRng::seed_from_u64(self.seed),
+            distr,
+            low,
+            high,
+            endpoints,
+            log_scale,
+        }
+    }
+}
+
+/// Uniformly samples machine numbers in given interval, that is, each
+/// representable value has the same probability. Since machine numbers are
+/// dense near zero and sparse for large magnitudes, this sampling matches the
+/// distribution of floating point numbers, unlike [`UniformSample`] which
+/// samples uniformly in the value space.
+///
+/// [`UniformSample`]: struct.UniformSample.html
+pub struct BitUniformSample<F: FloatExt> {
+    count: usize,
+    iter: DistIter<Uniform<i64>, SmallRng, i64>,
+    ty: PhantomData<F>,
+}
+
+impl<F: FloatExt> BitUniformSample<F> {
+    /// Creates new iterator. The number of sampled values is fixed to given
+    /// count.
+    pub fn with_count(low: F, high: F, count: usize) -> Self {
+        assert!(low < high);
+        let distr = Uniform::new_inclusive(low.to_ordinal(), high.to_ordinal());
+        let rng = SmallRng::seed_from_u64(3);
+        let iter = rng.sample_iter(distr);
+
+        BitUniformSample {
+            count,
+            iter,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<F: FloatExt> Iterator for BitUniformSample<F> {
+    type Item = F;
+
     fn next(&mut self) -> Option<Self::Item> {
         if self.count == 0 {
             None
         } else {
             self.count -= 1;
-            self.iter.next()
+            self.iter.next().map(F::from_ordinal)
+        }
+    }
+}
+
+/// Uniformly samples subnormal numbers in given interval. Subnormal numbers
+/// have special representation and so they often need special handling in the
+/// implementations, which this sampling exercises directly.
+pub struct SubnormalSample<F: FloatExt> {
+    inner: BitUniformSample<F>,
+}
+
+impl<F: FloatExt> SubnormalSample<F> {
+    /// Creates new iterator. Both ends of the interval must be subnormal
+    /// numbers of the same sign. The number of sampled values is fixed to given
+    /// count.
+    pub fn with_count(low: F, high: F, count: usize) -> Self {
+        assert!(low.is_subnormal() && high.is_subnormal());
+        assert!((low > F::zero()) == (high > F::zero()));
+
+        SubnormalSample {
+            inner: BitUniformSample::with_count(low, high, count),
         }
     }
 }
 
+impl<F: FloatExt> Iterator for SubnormalSample<F> {
+    type Item = F;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
 /// Iterates over *all* machine numbers in given interval. This might be useful
 /// to test values near certain extremas.
 pub struct Exhaustive<F: FloatExt> {
     low: F,
     high: F,
+    capped: Option<Capped>,
+}
+
+// Evenly spaced ordinals of machine numbers used when the interval contains
+// more numbers than allowed.
+struct Capped {
+    start: i64,
+    span: u64,
+    count: u64,
+    index: u64,
 }
 
 impl<F: FloatExt> Exhaustive<F> {
-    /// Creates new iterator. The range is specified exactly by the user.
+    /// Creates new iterator. The range is specified exactly by the user. If
+    /// low equals high, exactly that one value is yielded.
     pub fn bounded(low: F, high: F) -> Self {
-        assert!(low < high);
-        Exhaustive { low, high }
+        assert!(low <= high);
+        Exhaustive {
+            low,
+            high,
+            capped: None,
+        }
+    }
+
+    /// Creates new iterator like [`bounded`], but yielding at most `max_count`
+    /// values. If there are more machine numbers in the interval, a warning is
+    /// printed to standard error and the values are evenly spaced machine
+    /// numbers spanning the whole interval, including both ends. This prevents
+    /// accidentally iterating over billions of values.
+    ///
+    /// [`bounded`]: struct.Exhaustive.html#method.bounded
+    pub fn bounded_capped(low: F, high: F, max_count: usize) -> Self {
+        assert!(low <= high);
+        assert!(max_count >= 2);
+
+        let start = low.to_ordinal();
+        let span = (high.to_ordinal() - start) as u64;
+
+        let capped = if span >= max_count as u64 {
+            eprintln!(
+                "warning: interval [{:?}, {:?}] contains {} machine numbers, only {} of them are used",
+                low,
+                high,
+                span + 1,
+                max_count
+            );
+
+            Some(Capped {
+                start,
+                span,
+                count: max_count as u64,
+                index: 0,
+            })
+        } else {
+            None
+        };
+
+        Exhaustive { low, high, capped }
     }
 
     /// Creates new iterator. The range determined by the middle point and an
@@ -82,7 +429,11 @@ impl<F: FloatExt> Exhaustive<F> {
         assert!(eps > F::zero());
         let low = value - eps;
         let high = value + eps;
-        Exhaustive { low, high }
+        Exhaustive {
+            low,
+            high,
+            capped: None,
+        }
     }
 }
 
@@ -90,7 +441,15 @@ impl<F: FloatExt> Iterator for Exhaustive<F> {
     type Item = F;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.low > self.high {
+        if let Some(capped) = self.capped.as_mut() {
+            if capped.index == capped.count {
+                return None;
+            }
+
+            let offset = capped.index as u128 * capped.span as u128 / (capped.count - 1) as u128;
+            capped.index += 1;
+            Some(F::from_ordinal(capped.start + offset as i64))
+        } else if self.low > self.high {
             None
         } else {
             let current = self.low;
@@ -100,20 +459,241 @@ impl<F: FloatExt> Iterator for Exhaustive<F> {
     }
 }
 
+/// Iterator which yields a fixed set of inputs followed by the values of
+/// another iterator (see [`Domain::with_fixed`]).
+///
+/// [`Domain::with_fixed`]: trait.Domain.html#tymethod.with_fixed
+pub struct WithFixed<F, I> {
+    fixed: Vec<F>,
+    index: usize,
+    inner: I,
+}
+
+impl<F, I> WithFixed<F, I> {
+    /// Returns the number of fixed inputs. They are yielded first, so a sample
+    /// with index (see [`Error::max_rel_index`]) less than this number is one
+    /// of the fixed inputs.
+    ///
+    /// [`Error::max_rel_index`]: ../error/struct.Error.html#method.max_rel_index
+    pub fn fixed_count(&self) -> usize {
+        self.fixed.len()
+    }
+}
+
+impl<F: FloatExt, I: Iterator<Item = F>> Iterator for WithFixed<F, I> {
+    type Item = F;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.fixed.get(self.index) {
+            Some(&x) => {
+                self.index += 1;
+                Some(x)
+            }
+            None => self.inner.next(),
+        }
+    }
+}
+
+/// Known parity of a function (see [`Domain::assert_symmetric`]).
+///
+/// [`Domain::assert_symmetric`]: trait.Domain.html#tymethod.assert_symmetric
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Even function, that is, f(-x) = f(x).
+    Even,
+    /// Odd function, that is, f(-x) = -f(x).
+    Odd,
+}
+
+impl Symmetry {
+    /// Returns the value of f(-x) given the value of f(x).
+    pub fn mirror<F: FloatExt>(self, value: F) -> F {
+        match self {
+            Symmetry::Even => value,
+            Symmetry::Odd => F::zero() - value,
+        }
+    }
+}
+
 /// Trait for interval iterators for computing (or asserting) errors.
 pub trait Domain<F: FloatExt> {
+    /// Includes given inputs in addition to the values of the interval. Random
+    /// sampling may never hit known-hard inputs, like exact powers of two,
+    /// multiples of π/2 or the endpoints of the domain, so these are always
+    /// processed regardless of the random draw. The fixed inputs come first,
+    /// so they are attributable in the worst-case reporting by the index of the
+    /// sample (see [`WithFixed::fixed_count`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// let error = UniformSample::with_count(0.0f32, 2.0, 1000)
+    ///     .with_fixed(&[0.0, 1.0, 2.0])
+    ///     .error(|x| (x.sqrt(), x.sqrt()));
+    ///
+    /// assert_eq!(error.max_rel(), 0.0);
+    /// ```
+    ///
+    /// [`WithFixed::fixed_count`]: struct.WithFixed.html#method.fixed_count
+    fn with_fixed(self, extra: &[F]) -> WithFixed<F, Self>
+    where
+        Self: Sized;
+
     /// Computes the errors encountered on the interval.
     fn error<T>(self, compute: T) -> Error<F, F>
     where
         T: Fn(F) -> (F, F);
 
+    /// Computes the errors encountered on the interval in a transformed output
+    /// space. The `transform` is applied to both computed and real value
+    /// before the errors are calculated. For example, the absolute error of
+    /// logarithms of the values is approximately the relative error, or for
+    /// probabilities, the error may be more meaningful in logit space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// # fn exp(x: f32) -> f32 { x.exp() }
+    /// let error = UniformSample::with_count(-87.3, 88.7, 10000)
+    ///     .error_transformed(|x| (exp(x), x.exp()), f32::ln);
+    ///
+    /// assert!(error.max_abs() < 1e-5);
+    /// ```
+    fn error_transformed<T, R>(self, compute: T, transform: R) -> Error<F, F>
+    where
+        T: Fn(F) -> (F, F),
+        R: Fn(F) -> F;
+
+    /// Computes the errors on the interval, which is the primary range of the
+    /// implementation, and on the `full` interval, which is the entire domain.
+    /// The first measures the approximation error, the increase on the entire
+    /// domain is attributed to the argument reduction (see [crate
+    /// documentation]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// # fn exp(x: f32) -> f32 { x.exp() }
+    /// let split = UniformSample::with_count(-0.35, 0.35, 10000).error_split(
+    ///     UniformSample::with_count(-87.3, 88.7, 10000),
+    ///     |x| (exp(x), x.exp()),
+    /// );
+    ///
+    /// println!("approximation: {:e}", split.primary_error.max_rel());
+    /// println!("reduction: {:e}", split.reduction_error());
+    /// ```
+    ///
+    /// [crate documentation]: ../index.html#domain
+    fn error_split<D, T>(self, full: D, compute: T) -> ErrorSplit<F, F>
+    where
+        D: Domain<F>,
+        T: Fn(F) -> (F, F);
+
+    /// Computes the errors encountered on the interval for an implementation
+    /// which returns its result as an unevaluated sum of two numbers, the value
+    /// and a low order correction. The `compute` closure returns this pair and
+    /// the real value in double precision (see [`Error::calculate_pair`]). The
+    /// errors measure the effective precision that the pair delivers.
+    ///
+    /// [`Error::calculate_pair`]: ../error/struct.Error.html#method.calculate_pair
+    fn error_pair<T>(self, compute: T) -> Error<f64, F>
+    where
+        T: Fn(F) -> ((F, F), f64);
+
+    /// Computes the errors of two implementations at once. Each input is drawn
+    /// only once and fed to both closures, so both implementations are
+    /// evaluated on exactly the same inputs even if the domain is random. This
+    /// makes the comparison of two candidates fair.
+    fn error_tee<A, B>(self, compute_a: A, compute_b: B) -> (Error<F, F>, Error<F, F>)
+    where
+        A: Fn(F) -> (F, F),
+        B: Fn(F) -> (F, F);
+
+    /// Computes the maximum relative error separately for each decade of the
+    /// inputs, that is, for the inputs with the same integral part of
+    /// log10(|x|). The result is sorted by the decade and contains only the
+    /// decades in which there was at least one input with nonzero real value.
+    /// It reveals the magnitudes of the inputs at which the implementation
+    /// performs the worst.
+    fn error_by_decade<T>(self, compute: T) -> Vec<(i32, F)>
+    where
+        T: Fn(F) -> (F, F);
+
+    /// Counts the inputs on the interval for which the computed value exactly
+    /// matches the real value. This is meant for functions with discrete
+    /// output, like integers.
+    fn error_exact<T, O>(self, compute: T) -> ExactError<O, F>
+    where
+        T: Fn(F) -> (O, O),
+        O: PartialEq + fmt::Debug + Copy;
+
+    /// Counts how many inputs on the interval hit individual branches of an
+    /// implementation. The `classify` closure returns the name of the branch
+    /// for given input.
+    fn branch_coverage<T>(self, classify: T) -> BranchCounter
+    where
+        T: Fn(F) -> &'static str;
+
     /// Asserts the errors encountered on the interval to have given bounds.
     fn assert<T>(self, bounds: ErrorBounds<F>, compute: T)
     where
         T: Fn(F) -> (F, F);
+
+    /// Asserts that at least one of given bounds is violated on the interval.
+    /// This documents known deficiencies of an implementation. When the
+    /// implementation gets fixed and the bounds are unexpectedly met, it
+    /// panics, so that the test can be turned into regular [`assert`].
+    ///
+    /// [`assert`]: trait.Domain.html#tymethod.assert
+    fn assert_expected_fail<T>(self, bounds: ErrorBounds<F>, compute: T)
+    where
+        T: Fn(F) -> (F, F);
+
+    /// Asserts the errors encountered on the interval to have given bounds,
+    /// like [`assert`], and additionally evaluates the function also at the
+    /// mirrored input -x for every sampled x. The errors are computed for both
+    /// inputs and the computed values must satisfy given symmetry exactly.
+    ///
+    /// [`assert`]: trait.Domain.html#tymethod.assert
+    fn assert_symmetric<T>(self, bounds: ErrorBounds<F>, symmetry: Symmetry, compute: T)
+    where
+        T: Fn(F) -> (F, F);
+
+    /// Asserts that sine and cosine implementations satisfy the Pythagorean
+    /// identity sin²(x) + cos²(x) = 1 within absolute tolerance `tol` on the
+    /// interval. No external reference is needed, so it catches an argument
+    /// reduction which is consistent with itself, but not with the other
+    /// function.
+    fn assert_pythagorean<S, C>(self, sin: S, cos: C, tol: F)
+    where
+        S: Fn(F) -> F,
+        C: Fn(F) -> F;
+
+    /// Collects all inputs on the interval for which the errors exceed given
+    /// bounds, instead of panicking on the first one. The failures can be then
+    /// saved to a regression corpus (see [`corpus`] module).
+    ///
+    /// [`corpus`]: ../corpus/index.html
+    fn failures<T>(self, bounds: &ErrorBounds<F>, compute: T) -> Vec<F>
+    where
+        T: Fn(F) -> (F, F);
 }
 
 impl<F: FloatExt, I: Iterator<Item = F>> Domain<F> for I {
+    fn with_fixed(self, extra: &[F]) -> WithFixed<F, Self> {
+        WithFixed {
+            fixed: extra.to_vec(),
+            index: 0,
+            inner: self,
+        }
+    }
+
     fn error<T>(self, compute: T) -> Error<F, F>
     where
         T: Fn(F) -> (F, F),
@@ -128,6 +708,114 @@ impl<F: FloatExt, I: Iterator<Item = F>> Domain<F> for I {
         error
     }
 
+    fn error_transformed<T, R>(self, compute: T, transform: R) -> Error<F, F>
+    where
+        T: Fn(F) -> (F, F),
+        R: Fn(F) -> F,
+    {
+        self.error(|x| {
+            let (computed, real) = compute(x);
+            (transform(computed), transform(real))
+        })
+    }
+
+    fn error_split<D, T>(self, full: D, compute: T) -> ErrorSplit<F, F>
+    where
+        D: Domain<F>,
+        T: Fn(F) -> (F, F),
+    {
+        ErrorSplit {
+            primary_error: self.error(&compute),
+            full_error: full.error(&compute),
+        }
+    }
+
+    fn error_pair<T>(self, compute: T) -> Error<f64, F>
+    where
+        T: Fn(F) -> ((F, F), f64),
+    {
+        let mut error = Error::new();
+
+        for x in self {
+            let (computed, real) = compute(x);
+            error.calculate_pair(x, computed, real);
+        }
+
+        error
+    }
+
+    fn error_tee<A, B>(self, compute_a: A, compute_b: B) -> (Error<F, F>, Error<F, F>)
+    where
+        A: Fn(F) -> (F, F),
+        B: Fn(F) -> (F, F),
+    {
+        let mut error_a = Error::new();
+        let mut error_b = Error::new();
+
+        for x in self {
+            let (computed, real) = compute_a(x);
+            error_a.calculate(x, computed, real);
+
+            let (computed, real) = compute_b(x);
+            error_b.calculate(x, computed, real);
+        }
+
+        (error_a, error_b)
+    }
+
+    fn error_by_decade<T>(self, compute: T) -> Vec<(i32, F)>
+    where
+        T: Fn(F) -> (F, F),
+    {
+        let mut decades = BTreeMap::new();
+
+        for x in self {
+            let (computed, real) = compute(x);
+
+            if x == F::zero() || real == F::zero() {
+                continue;
+            }
+
+            let decade = x.abs().to_f64().log10().floor() as i32;
+            let rel = ((computed - real) / real).abs();
+            let max_rel = decades.entry(decade).or_insert_with(F::zero);
+
+            if rel > *max_rel {
+                *max_rel = rel;
+            }
+        }
+
+        decades.into_iter().collect()
+    }
+
+    fn error_exact<T, O>(self, compute: T) -> ExactError<O, F>
+    where
+        T: Fn(F) -> (O, O),
+        O: PartialEq + fmt::Debug + Copy,
+    {
+        let mut error = ExactError::new();
+
+        for x in self {
+            let (computed, real) = compute(x);
+            error.calculate(x, computed, real);
+        }
+
+        error
+    }
+
+    fn branch_coverage<T>(self, classify: T) -> BranchCounter
+    where
+        T: Fn(F) -> &'static str,
+    {
+        let counter = BranchCounter::new();
+
+        for x in self {
+            counter.hit(classify(x));
+        }
+
+        counter
+    }
+
     fn assert<T>(self, bounds: ErrorBounds<F>, compute: T)
     where
         T: Fn(F) -> (F, F),
@@ -141,12 +829,88 @@ impl<F: FloatExt, I: Iterator<Item = F>> Domain<F> for I {
 
         error.assert();
     }
+
+    fn assert_expected_fail<T>(self, bounds: ErrorBounds<F>, compute: T)
+    where
+        T: Fn(F) -> (F, F),
+    {
+        let mut error = Error::new();
+        let mut violated = false;
+
+        for x in self {
+            let (computed, real) = compute(x);
+            error.calculate(x, computed, real);
+            violated = violated || !bounds.check(x, computed, real);
+        }
+
+        let violated =
+            violated || !bounds.check_rms(error.rms()) || !bounds.check_rms_abs(error.rms_abs());
+
+        if !violated {
+            panic!(
+                "expected failure, but the bounds were met (relative = {:?}, absolute = {:?}, root-mean-square = {:?})",
+                error.max_rel(),
+                error.max_abs(),
+                error.rms()
+            );
+        }
+    }
+
+    fn assert_symmetric<T>(self, bounds: ErrorBounds<F>, symmetry: Symmetry, compute: T)
+    where
+        T: Fn(F) -> (F, F),
+    {
+        let mut error = Error::with_bounds(bounds);
+
+        for x in self {
+            let (computed, real) = compute(x);
+            error.calculate(x, computed, real);
+
+            let mirrored = F::zero() - x;
+            let (computed_mirrored, real) = compute(mirrored);
+            error.calculate(mirrored, computed_mirrored, real);
+
+            let expected = symmetry.mirror(computed);
+            if computed_mirrored != expected && !(computed.is_nan() && computed_mirrored.is_nan()) {
+                panic!(
+                    "{:?} symmetry violated at {:?} (f(x) = {:?}, f(-x) = {:?})",
+                    symmetry, x, computed, computed_mirrored
+                );
+            }
+        }
+
+        error.assert();
+    }
+
+    fn assert_pythagorean<S, C>(self, sin: S, cos: C, tol: F)
+    where
+        S: Fn(F) -> F,
+        C: Fn(F) -> F,
+    {
+        self.assert(ErrorBounds::new().abs(tol), |x| {
+            let s = sin(x);
+            let c = cos(x);
+            (s * s + c * c, F::one())
+        });
+    }
+
+    fn failures<T>(self, bounds: &ErrorBounds<F>, compute: T) -> Vec<F>
+    where
+        T: Fn(F) -> (F, F),
+    {
+        self.filter(|x| {
+            let (computed, real) = compute(*x);
+            !bounds.check(*x, computed, real)
+        })
+        .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
+    use std::cell::RefCell;
     use std::collections::HashSet;
 
     #[test]
@@ -172,6 +936,330 @@ mod tests {
         assert!(uniqueness > 0.99);
     }
 
+    #[test]
+    fn uniform_sample_builder() {
+        let low = 1.0f32;
+        let high = 1e+6f32;
+
+        let default = UniformSample::builder(low, high)
+            .build()
+            .collect::<Vec<_>>();
+        assert_eq!(default.len(), 10000);
+
+        let values = UniformSample::builder(low, high)
+            .count(1000)
+            .seed(42)
+            .with_endpoints()
+            .log_scale()
+            .build()
+            .collect::<Vec<_>>();
+
+        // Count.
+        assert_eq!(values.len(), 1000);
+        assert!(values.iter().all(|&x| x >= low && x <= high));
+
+        // Endpoints.
+        assert_eq!(values[0], low);
+        assert_eq!(values[1], high);
+
+        // Seed.
+        let other = UniformSample::builder(low, high)
+            .count(1000)
+            .with_endpoints()
+            .log_scale()
+            .build()
+            .collect::<Vec<_>>();
+        assert_ne!(values, other);
+
+        // Logarithmic scale: roughly a half of the values should be below
+        // sqrt(high), whereas in linear scale, it is only a tiny fraction.
+        let below = |values: &[f32]| values.iter().filter(|&&x| x < 1000.0).count();
+        assert!(below(&values) > 400 && below(&values) < 600);
+        assert!(below(&default) < 100);
+
+        // Logarithmic scale on negative interval.
+        assert!(UniformSample::builder(-high, -low)
+            .log_scale()
+            .build()
+            .all(|x| x >= -high && x <= -low));
+
+        // Fraction.
+        assert_eq!(
+            UniformSample::builder(1.0f32, 2.0)
+                .fraction(0.001)
+                .build()
+                .count(),
+            UniformSample::with_fraction(1.0f32, 2.0, 0.001).count()
+        );
+    }
+
+    #[test]
+    fn with_density() {
+        assert_eq!(
+            UniformSample::with_density(0.0f32, 1.0, 1000.0).count(),
+            1000
+        );
+
+        // The count is proportional to the width of the interval.
+        assert_eq!(
+            UniformSample::with_density(-5.0f32, 5.0, 1000.0).count(),
+            10000
+        );
+        assert_eq!(UniformSample::with_density(1.0f32, 1.25, 10.0).count(), 3);
+    }
+
+    #[test]
+    fn bit_uniform_sample() {
+        let count = 100000;
+        let low = 0.0f32;
+        let high = 2.0f32;
+
+        let below_one = BitUniformSample::with_count(low, high, count)
+            .inspect(|&x| assert!(x >= low && x <= high))
+            .filter(|&x| x < 1.0)
+            .count();
+
+        // The fraction of sampled values below one should match the fraction
+        // of machine numbers below one, which is almost all of them.
+        let expected = low.floats_between(1.0.nextdown()) as f64 / low.floats_between(high) as f64;
+        let fraction = below_one as f64 / count as f64;
+        assert!((fraction - expected).abs() < 0.01);
+
+        // Uniform sampling in value space would give just a half.
+        let below_one = UniformSample::with_count(low, high, count)
+            .filter(|&x| x < 1.0)
+            .count();
+        let fraction = below_one as f64 / count as f64;
+        assert!((fraction - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn with_fixed() {
+        let fixed = [1.0f32, 1.0f32.nextup(), core::f32::consts::FRAC_PI_2];
+
+        for seed in 0..10 {
+            let sample = UniformSample::builder(0.0f32, 2.0)
+                .count(1000)
+                .seed(seed)
+                .build()
+                .with_fixed(&fixed);
+            assert_eq!(sample.fixed_count(), 3);
+
+            let values = sample.collect::<Vec<_>>();
+            assert_eq!(values.len(), 1003);
+            assert_eq!(&values[..3], &fixed);
+        }
+
+        // The function fails only at a fixed input, which random sampling
+        // would never hit.
+        let broken = |x: f32| {
+            if x == 1.0f32.nextup() {
+                (2.0 * x, x)
+            } else {
+                (x, x)
+            }
+        };
+
+        assert_eq!(
+            UniformSample::with_count(0.0f32, 2.0, 1000)
+                .failures(&ErrorBounds::new().rel(0.001), broken)
+                .len(),
+            0
+        );
+
+        let error = UniformSample::with_count(0.0f32, 2.0, 1000)
+            .with_fixed(&fixed)
+            .error(broken);
+        assert_eq!(error.max_rel_arg(), 1.0f32.nextup());
+        assert_eq!(error.max_rel_index(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "error exceeded at 1.5 (sample 0)")]
+    fn with_fixed_assert() {
+        UniformSample::with_count(0.0f32, 2.0, 1000)
+            .with_fixed(&[1.5])
+            .assert(ErrorBounds::new().rel(0.001), |x| {
+                if x == 1.5 {
+                    (2.0 * x, x)
+                } else {
+                    (x, x)
+                }
+            });
+    }
+
+    #[test]
+    fn expected_fail() {
+        let bounds = || ErrorBounds::new().rel(0.001);
+
+        // Deliberately bad approximation.
+        UniformSample::with_count(1.0, 2.0, 1000)
+            .assert_expected_fail(bounds(), |x: f32| (x - (x - 1.0).powi(2) / 2.0, x));
+
+        // Violated bound for root-mean-square error only.
+        UniformSample::with_count(1.0, 2.0, 1000)
+            .assert_expected_fail(ErrorBounds::new().rel(0.001).rms(1e-5), |x: f32| {
+                (x * 1.0001, x)
+            });
+
+        // The approximation is good, which is unexpected.
+        let result = std::panic::catch_unwind(|| {
+            UniformSample::with_count(1.0, 2.0, 1000)
+                .assert_expected_fail(bounds(), |x: f32| (x * 1.0001, x));
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn assert_symmetric() {
+        let bounds = || ErrorBounds::new().rel(0.001).abs(1e-5);
+
+        UniformSample::with_count(0.0f32, 3.0, 10000).assert_symmetric(
+            bounds(),
+            Symmetry::Odd,
+            |x| (x.sin(), x.sin()),
+        );
+
+        UniformSample::with_count(0.0f32, 3.0, 10000).assert_symmetric(
+            bounds(),
+            Symmetry::Even,
+            |x| (x.cos(), x.cos()),
+        );
+
+        // Exactly odd, but the error on the mirrored inputs is too large.
+        let result = std::panic::catch_unwind(|| {
+            UniformSample::with_count(0.0f32, 3.0, 10000).assert_symmetric(
+                bounds(),
+                Symmetry::Odd,
+                |x| (x.sin(), if x < 0.0 { 2.0 * x.sin() } else { x.sin() }),
+            );
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Odd symmetry violated")]
+    fn assert_symmetric_violated() {
+        // Accurate, but not exactly odd.
+        UniformSample::with_count(0.0f32, 3.0, 10000).assert_symmetric(
+            ErrorBounds::new().rel(0.001).abs(1e-5),
+            Symmetry::Odd,
+            |x| (x.sin() + 1e-6, x.sin()),
+        );
+    }
+
+    #[test]
+    fn assert_pythagorean() {
+        UniformSample::with_count(-10.0f32, 10.0, 10000).assert_pythagorean(
+            |x| x.sin(),
+            |x| x.cos(),
+            1e-6,
+        );
+
+        // Cosine with a shifted argument is not consistent with the sine.
+        let result = std::panic::catch_unwind(|| {
+            UniformSample::with_count(-10.0f32, 10.0, 10000).assert_pythagorean(
+                |x| x.sin(),
+                |x| (x + 1e-3).cos(),
+                1e-6,
+            );
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn error_by_decade() {
+        // The error grows with the magnitude of the input.
+        let decades = UniformSample::builder(1e-3f32, 1e+3)
+            .count(10000)
+            .log_scale()
+            .build()
+            .error_by_decade(|x| (x * (1.0 + 1e-4 * x.abs().log10().max(0.0)), x));
+
+        assert_eq!(
+            decades
+                .iter()
+                .map(|(decade, _)| *decade)
+                .collect::<Vec<_>>(),
+            vec![-3, -2, -1, 0, 1, 2]
+        );
+
+        assert!(decades[..3].iter().all(|(_, rel)| *rel < 1e-6));
+        assert!(decades[3].1 < decades[4].1 && decades[4].1 < decades[5].1);
+        assert!((decades[5].1 - 3e-4).abs() < 3e-6);
+    }
+
+    #[test]
+    fn error_tee() {
+        let seen_a = RefCell::new(Vec::new());
+        let seen_b = RefCell::new(Vec::new());
+
+        let (error_a, error_b) = UniformSample::with_count(1.0f32, 2.0, 1000).error_tee(
+            |x| {
+                seen_a.borrow_mut().push(x);
+                (x + 1e-4, x)
+            },
+            |x| {
+                seen_b.borrow_mut().push(x);
+                (x + 1e-3, x)
+            },
+        );
+
+        assert_eq!(seen_a.borrow().len(), 1000);
+        assert_eq!(*seen_a.borrow(), *seen_b.borrow());
+
+        // The maximum relative error is at the smallest input for both.
+        assert_eq!(error_a.max_rel_arg(), error_b.max_rel_arg());
+        assert!(error_a.max_rel() < error_b.max_rel());
+    }
+
+    #[test]
+    fn subnormal_sample() {
+        let low = 1e-45f32;
+        let high = f32::MIN_POSITIVE.nextdown();
+
+        let count = SubnormalSample::with_count(low, high, 10000)
+            .inspect(|&x| assert!(x.is_subnormal() && x >= low && x <= high))
+            .count();
+        assert_eq!(count, 10000);
+
+        assert!(SubnormalSample::with_count(-high, -low, 10000).all(|x| x.is_subnormal()));
+
+        assert!(!0.0f32.is_subnormal());
+        assert!(!f32::MIN_POSITIVE.is_subnormal());
+        assert!(!1.0f64.is_subnormal());
+        assert!(1e-310f64.is_subnormal());
+    }
+
+    #[test]
+    fn exhaustive_single() {
+        assert_eq!(
+            Exhaustive::bounded(1.5f32, 1.5).collect::<Vec<_>>(),
+            vec![1.5]
+        );
+        assert_eq!(1.5f32.floats_between(1.5), 1);
+
+        assert_eq!(Exhaustive::bounded(f32::MAX, f32::MAX).count(), 1);
+        assert_eq!(Exhaustive::bounded(-0.0f64, 0.0).count(), 1);
+        assert_eq!(Exhaustive::bounded_capped(2.0f32, 2.0, 10).count(), 1);
+    }
+
+    #[test]
+    fn exhaustive_capped() {
+        let values = Exhaustive::bounded_capped(-1e30f32, 1e30, 1000).collect::<Vec<_>>();
+
+        assert_eq!(values.len(), 1000);
+        assert_eq!(values[0], -1e30);
+        assert_eq!(values[999], 1e30);
+        assert!(values.windows(2).all(|w| w[0] < w[1]));
+
+        // Below the cap, all machine numbers are used.
+        assert_eq!(
+            Exhaustive::bounded_capped(1.0f32, 1.0f32.nextup().nextup(), 1000).count(),
+            3
+        );
+    }
+
     proptest! {
         #[test]
         fn exhaustive(x: f32, k in 1usize..100) {
@@ -1,33 +1,125 @@
 //! Computation of the error.
 
+use std::cmp::Ordering;
 use std::fmt;
+use std::io::{self, Write};
+use std::ops;
+use std::thread;
 
 use crate::float::FloatExt;
+use crate::report::csv_escape;
+
+type CustomCheck<F, In> = Box<dyn Fn(In, F, F) -> bool>;
+type Reliable<In> = Box<dyn Fn(&In) -> bool>;
+type Weight<In> = Box<dyn Fn(&In) -> f64>;
+
+/// The number of samples that are expected to hit the region of the maximum
+/// relative error in order to consider the maximum trustworthy.
+const CONVERGED_HITS: f64 = 10.0;
+
+/// The default number of digits after the decimal point in the printed errors.
+const DEFAULT_PRECISION: usize = 3;
+
+/// Computes the relative error from the absolute error. It is taken in absolute
+/// value, otherwise it would be negative for negative real values, and such
+/// errors would never become the maximum nor violate the bounds.
+pub(crate) fn relative<F: FloatExt>(abs: F, real: F) -> F {
+    (abs / real).abs()
+}
+
+/// Arguments of the tested functions, that is, numbers or tuples of them.
+pub trait Argument: fmt::Debug + Default + Copy {
+    /// Formats the argument with floating point numbers in scientific notation
+    /// with given number of digits after the decimal point. Integers are
+    /// formatted as they are and tuples are formatted element-wise, including
+    /// the nested ones.
+    fn scientific(&self, precision: usize) -> String;
+}
+
+macro_rules! argument_float {
+    ($($float:ty),*) => {
+        $(
+            impl Argument for $float {
+                fn scientific(&self, precision: usize) -> String {
+                    format!("{:.*e}", precision, self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! argument_int {
+    ($($int:ty),*) => {
+        $(
+            impl Argument for $int {
+                fn scientific(&self, _precision: usize) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! argument_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Argument),*> Argument for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn scientific(&self, precision: usize) -> String {
+                let ($($name,)*) = self;
+                let parts = [$($name.scientific(precision)),*];
+                format!("({})", parts.join(", "))
+            }
+        }
+    };
+}
+
+argument_float!(f32, f64);
+argument_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+argument_tuple!(A, B);
+argument_tuple!(A, B, C);
+argument_tuple!(A, B, C, D);
 
 /// Bounds for errors to be asserted. By default, all are empty and therefore
 /// not checked. By specifying a bound for given error type, you enable checking
 /// it.
 ///
+/// The second generic parameter specifies the input argument(s), which are
+/// passed to the custom check (see [`check_with`]).
+///
 /// # Examples
 ///
 /// ```
 /// use nikisas_test::error::ErrorBounds;
 /// // Specify bounds for relative and absolute errors.
-/// let bounds = ErrorBounds::new().rel(0.001).abs(0.0001);
+/// let bounds: ErrorBounds<f32> = ErrorBounds::new().rel(0.001).abs(0.0001);
 /// ```
-pub struct ErrorBounds<F> {
+///
+/// [`check_with`]: struct.ErrorBounds.html#method.check_with
+pub struct ErrorBounds<F, In = F> {
     rel: Option<F>,
     abs: Option<F>,
     rms: Option<F>,
+    rms_abs: Option<F>,
+    overshoot: Option<F>,
+    undershoot: Option<F>,
+    ulp: Option<u64>,
+    custom: Option<CustomCheck<F, In>>,
+    max_violations: u64,
 }
 
-impl<F: FloatExt> ErrorBounds<F> {
+impl<F: FloatExt, In> ErrorBounds<F, In> {
     /// Creates empty bounds, that is, none is checked.
     pub fn new() -> Self {
         ErrorBounds {
             rel: None,
             abs: None,
             rms: None,
+            rms_abs: None,
+            overshoot: None,
+            undershoot: None,
+            ulp: None,
+            custom: None,
+            max_violations: 0,
         }
     }
 
@@ -49,6 +141,77 @@ impl<F: FloatExt> ErrorBounds<F> {
         self
     }
 
+    /// Specifies the bound for root-mean-square error computed from absolute
+    /// errors.
+    pub fn rms_abs(mut self, bound: F) -> Self {
+        self.rms_abs = Some(bound);
+        self
+    }
+
+    /// Specifies the bound for maximum overshoot, that is, by how much the
+    /// computed value can be greater than the real value.
+    pub fn overshoot(mut self, bound: F) -> Self {
+        self.overshoot = Some(bound);
+        self
+    }
+
+    /// Specifies the bound for maximum undershoot, that is, by how much the
+    /// computed value can be less than the real value.
+    pub fn undershoot(mut self, bound: F) -> Self {
+        self.undershoot = Some(bound);
+        self
+    }
+
+    /// Specifies the bound for maximum error in units in the last place (see
+    /// [`FloatExt::ulp_distance`]). This asserts the common guarantee of being
+    /// within n ULP from the correctly rounded result, provided that the real
+    /// value is rounded to the same type as the computed one. If the real value
+    /// is zero, the computed value must be zero or adjacent to it, because
+    /// the machine numbers near zero are extremely dense and even a tiny
+    /// absolute error would be many units in the last place away.
+    ///
+    /// [`FloatExt::ulp_distance`]: ../float/trait.FloatExt.html#method.ulp_distance
+    pub fn ulp(mut self, bound: u64) -> Self {
+        self.ulp = Some(bound);
+        self
+    }
+
+    /// Specifies the number of inputs violating the bounds that are tolerated,
+    /// for example exactly at a pole. An input violating several bounds at
+    /// once counts as a single violation. By default, it is zero and the first
+    /// violation panics immediately. Otherwise, the violations are counted and
+    /// [`Error::assert`] panics if there are more of them than allowed,
+    /// reporting their total count and the worst one (with the largest
+    /// absolute error).
+    ///
+    /// [`Error::assert`]: struct.Error.html#method.assert
+    pub fn max_violations(mut self, n: u64) -> Self {
+        self.max_violations = n;
+        self
+    }
+
+    /// Specifies a custom check which is given the input argument(s), the
+    /// relative error and the absolute error and decides whether they are
+    /// acceptable. This allows bounds that vary with the input. The check is
+    /// not performed when the relative error is undefined due to dividing by
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::error::ErrorBounds;
+    /// // Be strict near 1, but more benevolent far from it.
+    /// let bounds: ErrorBounds<f32> =
+    ///     ErrorBounds::new().check_with(|x: f32, rel, _| rel <= 0.001 * (x - 1.0).abs());
+    /// ```
+    pub fn check_with<C>(mut self, check: C) -> Self
+    where
+        C: Fn(In, F, F) -> bool + 'static,
+    {
+        self.custom = Some(Box::new(check));
+        self
+    }
+
     /// Checks if the relative and absolute errors satisfy specified bounds.
     pub fn check_rel_or_abs(&self, rel_err: F, abs_err: F) -> bool {
         match (self.rel, self.abs) {
@@ -75,6 +238,117 @@ impl<F: FloatExt> ErrorBounds<F> {
             None => true,
         }
     }
+
+    /// Checks if the error in units in the last place satisfies specified
+    /// bound.
+    pub fn check_ulp(&self, ulp_error: u64) -> bool {
+        match self.ulp {
+            Some(ulp) => ulp_error <= ulp,
+            None => true,
+        }
+    }
+
+    /// Checks if the error in units in the last place satisfies specified
+    /// bound, with the special handling of zero real value (see [`ulp`]).
+    ///
+    /// [`ulp`]: struct.ErrorBounds.html#method.ulp
+    pub fn check_ulp_at(&self, ulp_error: u64, real: F) -> bool {
+        match self.ulp {
+            Some(ulp) if real == F::zero() => ulp_error <= ulp.min(1),
+            _ => self.check_ulp(ulp_error),
+        }
+    }
+
+    /// Checks if the root-mean-square error computed from absolute errors
+    /// satisfies specified bound.
+    pub fn check_rms_abs(&self, rms_abs_error: F) -> bool {
+        match self.rms_abs {
+            Some(rms_abs) => rms_abs_error <= rms_abs,
+            None => true,
+        }
+    }
+
+    /// Checks if the errors for given input satisfy the custom check.
+    pub fn check_custom(&self, arg: In, rel_error: F, abs_error: F) -> bool {
+        match self.custom {
+            Some(ref check) => check(arg, rel_error, abs_error),
+            None => true,
+        }
+    }
+
+    /// Checks if the computed value for given input satisfies all the bounds
+    /// that are checked input by input (that is, all except root-mean-square
+    /// errors). Unlike [`Error::calculate`], it does not panic.
+    ///
+    /// [`Error::calculate`]: struct.Error.html#method.calculate
+    pub fn check(&self, arg: In, computed: F, real: F) -> bool {
+        let abs = (computed - real).abs();
+
+        let shoot = if computed > real {
+            self.check_overshoot(computed - real)
+        } else {
+            self.check_undershoot(real - computed)
+        };
+
+        let rel_or_abs = if real != F::zero() {
+            let rel = relative(abs, real);
+            self.check_rel_or_abs(rel, abs) && self.check_custom(arg, rel, abs)
+        } else {
+            self.check_abs(abs)
+        };
+
+        self.check_ulp_at(computed.ulp_distance(real), real) && shoot && rel_or_abs
+    }
+
+    /// Checks if the overshoot satisfies specified bound.
+    pub fn check_overshoot(&self, overshoot: F) -> bool {
+        match self.overshoot {
+            Some(bound) => overshoot <= bound,
+            None => true,
+        }
+    }
+
+    /// Checks if the undershoot satisfies specified bound.
+    pub fn check_undershoot(&self, undershoot: F) -> bool {
+        match self.undershoot {
+            Some(bound) => undershoot <= bound,
+            None => true,
+        }
+    }
+}
+
+/// Sum of many values using Kahan compensated summation, which keeps the
+/// rounding error independent of the number of summed values.
+#[derive(Clone, Copy, Default)]
+struct Sum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl Sum {
+    fn add(&mut self, value: f64) {
+        let y = value - self.compensation;
+        let t = self.sum + y;
+        self.compensation = (t - self.sum) - y;
+        self.sum = t;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum
+    }
+}
+
+/// The metric used for comparing errors (see [`Error::better_than`]).
+///
+/// [`Error::better_than`]: struct.Error.html#method.better_than
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Maximum relative error.
+    MaxRel,
+    /// Root-mean-square error.
+    Rms,
+    /// Maximum error in units in the last place.
+    MaxUlp,
 }
 
 /// Aggregator structure that compares computed and real values, input by input,
@@ -87,28 +361,222 @@ impl<F: FloatExt> ErrorBounds<F> {
 pub struct Error<F, In> {
     max_abs: (In, F),
     max_rel: (In, F),
-    sum_rel: F,
-    total: F,
-    bounds: ErrorBounds<F>,
+    max_rel_index: u64,
+    max_rel_reliable: (In, F),
+    reliable: Option<Reliable<In>>,
+    weight: Option<Weight<In>>,
+    sum_rel_weighted: Sum,
+    sum_weight: Sum,
+    max_overshoot: (In, F),
+    max_undershoot: (In, F),
+    max_ulp: (In, u64),
+    sum_rel: Sum,
+    total: u64,
+    sum_abs: Sum,
+    sum_ulp: Sum,
+    count: u64,
+    samples: Option<Vec<(In, F)>>,
+    probes: Vec<(String, In, F, F)>,
+    rounding: Option<(f64, f64)>,
+    precision: usize,
+    violations: u64,
+    worst_violation: Option<(F, String)>,
+    bounds: ErrorBounds<F, In>,
 }
 
-impl<F: FloatExt, In: fmt::Debug + Default + Copy> Error<F, In> {
+impl<F: FloatExt, In: Argument> Error<F, In> {
     /// Initializes the structure without any bounds.
     pub fn new() -> Self {
         Error::with_bounds(ErrorBounds::new())
     }
 
     /// Initializes the structure with given bounds.
-    pub fn with_bounds(bounds: ErrorBounds<F>) -> Self {
+    pub fn with_bounds(bounds: ErrorBounds<F, In>) -> Self {
         Error {
             max_abs: (In::default(), F::zero()),
             max_rel: (In::default(), F::zero()),
-            sum_rel: F::zero(),
-            total: F::zero(),
+            max_rel_index: 0,
+            max_rel_reliable: (In::default(), F::zero()),
+            reliable: None,
+            weight: None,
+            sum_rel_weighted: Sum::default(),
+            sum_weight: Sum::default(),
+            max_overshoot: (In::default(), F::zero()),
+            max_undershoot: (In::default(), F::zero()),
+            max_ulp: (In::default(), 0),
+            sum_rel: Sum::default(),
+            total: 0,
+            sum_abs: Sum::default(),
+            sum_ulp: Sum::default(),
+            count: 0,
+            samples: None,
+            probes: Vec::new(),
+            rounding: None,
+            precision: DEFAULT_PRECISION,
+            violations: 0,
+            worst_violation: None,
             bounds,
         }
     }
 
+    /// Retains the arguments and relative errors of all calculated samples, so
+    /// that they can be exported for plotting (see [`write_plot_data`]). The
+    /// memory usage then grows linearly with the number of samples.
+    ///
+    /// [`write_plot_data`]: struct.Error.html#method.write_plot_data
+    pub fn retain_samples(mut self) -> Self {
+        self.samples = Some(Vec::new());
+        self
+    }
+
+    /// Sets the number of digits after the decimal point for printing the
+    /// errors and the arguments in scientific notation (see [`write_plain`]
+    /// and [`write_csv`]). The default is 3, that is, the format `{:.3e}`.
+    ///
+    /// [`write_plain`]: struct.Error.html#method.write_plain
+    /// [`write_csv`]: struct.Error.html#method.write_csv
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.precision = digits;
+        self
+    }
+
+    /// Specifies the reliable sub-range of the inputs, where the reference is
+    /// not too close to zero for the relative error to be meaningful. The
+    /// maximum relative error is then tracked both within this sub-range (see
+    /// [`max_rel_reliable`]) and overall (see [`max_rel`]), so that the
+    /// headline quality is not obscured by the inflated relative errors near
+    /// the zeros, yet the behavior there is not hidden either. The bounds are
+    /// still checked on all inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// let error = UniformSample::with_count(-3.0, 3.0, 1000)
+    ///     .fold(Error::new().reliable(|x: &f32| x.abs() > 0.1), |mut error, x| {
+    ///         error.calculate(x, x.sin() + 1e-6, x.sin());
+    ///         error
+    ///     });
+    ///
+    /// assert!(error.max_rel_reliable() < error.max_rel());
+    /// ```
+    ///
+    /// [`max_rel_reliable`]: struct.Error.html#method.max_rel_reliable
+    /// [`max_rel`]: struct.Error.html#method.max_rel
+    pub fn reliable<R>(mut self, reliable: R) -> Self
+    where
+        R: Fn(&In) -> bool + 'static,
+    {
+        self.reliable = Some(Box::new(reliable));
+        self
+    }
+
+    /// Specifies the weight of each sample for computing the weighted
+    /// root-mean-square error (see [`rms_weighted`]). The weight is the width
+    /// of the local interval the sample represents, that is, the inverse of
+    /// the sampling density up to a constant factor. For uniform sampling, it
+    /// is constant, for sampling in logarithmic scale, it is |x| (see
+    /// [`UniformSample::weight`]). The weighted error then estimates the true
+    /// mean-square error over the domain regardless of the sampling
+    /// distribution.
+    ///
+    /// [`rms_weighted`]: struct.Error.html#method.rms_weighted
+    /// [`UniformSample::weight`]: ../domain/struct.UniformSample.html#method.weight
+    pub fn weighted<W>(mut self, weight: W) -> Self
+    where
+        W: Fn(&In) -> f64 + 'static,
+    {
+        self.weight = Some(Box::new(weight));
+        self
+    }
+
+    /// Initializes the structure with given bounds wrapped in a guard that
+    /// asserts the bounds when dropped (see [`ScopedError`]).
+    ///
+    /// [`ScopedError`]: struct.ScopedError.html
+    pub fn scope(bounds: ErrorBounds<F, In>) -> ScopedError<F, In> {
+        ScopedError {
+            error: Some(Error::with_bounds(bounds)),
+        }
+    }
+
+    /// Calculates the errors like [`calculate`], with the real value being the
+    /// infinitely precise `truth` (approximated in double precision) rounded
+    /// to the floating point type. Moreover, it tracks the maximum relative
+    /// error of the computed value with respect to the truth and the maximum
+    /// relative error of the correctly rounded result, that is, the rounding
+    /// floor below which no implementation can get (see [`rounding_floor`]
+    /// and [`quality_ratio`]). It is meaningful only for types less precise
+    /// than double.
+    ///
+    /// [`calculate`]: struct.Error.html#method.calculate
+    /// [`rounding_floor`]: struct.Error.html#method.rounding_floor
+    /// [`quality_ratio`]: struct.Error.html#method.quality_ratio
+    pub fn calculate_with_truth(&mut self, arg: In, computed: F, truth: f64) {
+        let real = F::from_f64(truth);
+        self.calculate(arg, computed, real);
+
+        if truth != 0.0 {
+            let rel = ((computed.to_f64() - truth) / truth).abs();
+            let floor = ((real.to_f64() - truth) / truth).abs();
+
+            let (max_rel, max_floor) = self.rounding.unwrap_or((0.0, 0.0));
+            self.rounding = Some((max_rel.max(rel), max_floor.max(floor)));
+        }
+    }
+
+    /// Calculates the errors like [`calculate`], but the real value is given as
+    /// a bracket [`real_low`, `real_high`] of acceptable values. If the
+    /// computed value falls within the bracket, the error is zero, otherwise
+    /// it is the error with respect to the nearer bound. This is useful for
+    /// testing guarantees like faithful rounding, where either of the two
+    /// machine numbers around the real value is correct.
+    ///
+    /// [`calculate`]: struct.Error.html#method.calculate
+    pub fn calculate_bracket(&mut self, arg: In, computed: F, real_low: F, real_high: F) {
+        let real = if computed >= real_low && computed <= real_high {
+            computed
+        } else if computed < real_low {
+            real_low
+        } else {
+            // NaN ends up here, so it is reported as an error.
+            real_high
+        };
+
+        self.calculate(arg, computed, real);
+    }
+
+    /// Records the computed and real value at a named input, for example
+    /// `"exp(1)"`. The notable points are kept separately from the calculated
+    /// samples, so they do not affect the errors, and they are printed in their
+    /// own section by [`write_plain`]. This documents the behavior at the
+    /// inputs users care about most.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nikisas_test::prelude::*;
+    ///
+    /// let mut error = UniformSample::with_count(0.0f32, 1.0, 1000).error(|x| (x.exp(), x.exp()));
+    /// error.probe("exp(1)", 1.0, 2.7182817, core::f32::consts::E);
+    ///
+    /// assert_eq!(error.probes().len(), 1);
+    /// ```
+    ///
+    /// [`write_plain`]: struct.Error.html#method.write_plain
+    pub fn probe(&mut self, label: &str, arg: In, computed: F, real: F) {
+        self.probes.push((label.to_string(), arg, computed, real));
+    }
+
+    /// Returns the notable points recorded by [`probe`] as tuples of the
+    /// label, the argument, the computed value and the real value.
+    ///
+    /// [`probe`]: struct.Error.html#method.probe
+    pub fn probes(&self) -> &[(String, In, F, F)] {
+        &self.probes
+    }
+
     /// Calculates the errors between computed value and real value. If it is
     /// the current maximum, its value is stored along with the argument that
     /// caused it.
@@ -119,27 +587,159 @@ impl<F: FloatExt, In: fmt::Debug + Default + Copy> Error<F, In> {
             self.max_abs = (arg, abs);
         }
 
+        let ulp = computed.ulp_distance(real);
+
+        if ulp > self.max_ulp.1 {
+            self.max_ulp = (arg, ulp);
+        }
+
+        // The squares are computed in double precision, so they neither
+        // overflow nor lose precision for single precision errors.
+        self.sum_abs.add(abs.to_f64() * abs.to_f64());
+        self.sum_ulp.add(ulp as f64);
+        let index = self.count;
+        self.count += 1;
+
+        // An input violating several bounds is counted as a single violation.
+        let mut failed = Vec::new();
+
+        if !self.bounds.check_ulp_at(ulp, real) {
+            failed.push(format!(
+                "ulp error exceeded at {:?}, computed = {:?}, real = {:?}, ulp error = {}",
+                arg, computed, real, ulp
+            ));
+        }
+
+        if computed > real {
+            let overshoot = computed - real;
+
+            if overshoot > self.max_overshoot.1 {
+                self.max_overshoot = (arg, overshoot);
+            }
+
+            if !self.bounds.check_overshoot(overshoot) {
+                failed.push(format!(
+                    "overshoot exceeded at {:?}, computed = {:?}, real = {:?}, overshoot = {:?}",
+                    arg, computed, real, overshoot
+                ));
+            }
+        } else {
+            let undershoot = real - computed;
+
+            if undershoot > self.max_undershoot.1 {
+                self.max_undershoot = (arg, undershoot);
+            }
+
+            if !self.bounds.check_undershoot(undershoot) {
+                failed.push(format!(
+                    "undershoot exceeded at {:?}, computed = {:?}, real = {:?}, undershoot = {:?}",
+                    arg, computed, real, undershoot
+                ));
+            }
+        }
+
         if real != F::zero() {
-            let rel = abs / real;
+            let rel = relative(abs, real);
 
             if rel > self.max_rel.1 {
                 self.max_rel = (arg, rel);
+                self.max_rel_index = index;
+            }
+
+            let reliable = match self.reliable.as_ref() {
+                Some(reliable) => reliable(&arg),
+                None => true,
+            };
+
+            if reliable && rel > self.max_rel_reliable.1 {
+                self.max_rel_reliable = (arg, rel);
+            }
+
+            self.sum_rel.add(rel.to_f64() * rel.to_f64());
+            self.total += 1;
+
+            if let Some(weight) = self.weight.as_ref() {
+                let weight = weight(&arg);
+                self.sum_rel_weighted
+                    .add(weight * rel.to_f64() * rel.to_f64());
+                self.sum_weight.add(weight);
             }
 
-            self.sum_rel = self.sum_rel + rel * rel;
-            self.total = self.total + F::one();
+            if let Some(samples) = self.samples.as_mut() {
+                samples.push((arg, rel));
+            }
 
-            if !self.bounds.check_rel_or_abs(rel, abs) {
-                panic!(
-                    "error exceeded at {:?}, relative error = {:?}, absolute error = {:?}",
-                    arg, rel, abs
-                );
+            if !self.bounds.check_rel_or_abs(rel, abs) || !self.bounds.check_custom(arg, rel, abs) {
+                failed.push(format!(
+                    "error exceeded at {:?} (sample {}), computed = {:?}, real = {:?}, relative error = {:?}, absolute error = {:?}",
+                    arg, index, computed, real, rel, abs
+                ));
             }
         } else {
             if !self.bounds.check_abs(abs) {
-                panic!("error exceeded at {:?}, absolute error = {:?}", arg, abs);
+                failed.push(format!(
+                    "error exceeded at {:?} (sample {}), computed = {:?}, real = {:?}, absolute error = {:?}",
+                    arg, index, computed, real, abs
+                ));
             }
         }
+
+        if !failed.is_empty() {
+            self.violate(abs, failed.join("; "));
+        }
+    }
+
+    /// Handles a bound violation. Without any allowance, it panics with given
+    /// message immediately, otherwise the violation is counted and checked in
+    /// [`assert`].
+    ///
+    /// [`assert`]: struct.Error.html#method.assert
+    fn violate(&mut self, abs: F, message: String) {
+        if self.bounds.max_violations == 0 {
+            panic!("{}", message);
+        }
+
+        self.violations += 1;
+
+        let worse = match self.worst_violation.as_ref() {
+            Some((worst, _)) => abs > *worst,
+            None => true,
+        };
+
+        if worse {
+            self.worst_violation = Some((abs, message));
+        }
+    }
+
+    /// Returns the number of bound violations encountered. It can be nonzero
+    /// only if [`ErrorBounds::max_violations`] allows them.
+    ///
+    /// [`ErrorBounds::max_violations`]: struct.ErrorBounds.html#method.max_violations
+    pub fn violations(&self) -> u64 {
+        self.violations
+    }
+
+    /// Heuristically determines whether the maximum relative error encountered
+    /// is likely the true worst case, or whether more samples are warranted.
+    ///
+    /// The squared ratio of the root-mean-square error to the maximum error
+    /// estimates the portion of the interval where the error is close to the
+    /// maximum. If this portion multiplied by the number of samples is small,
+    /// the maximum was found by only a few "lucky" samples and a denser
+    /// sampling would probably reveal even larger errors.
+    pub fn is_likely_converged(&self) -> bool {
+        let max_rel = self.max_rel.1;
+
+        if self.total == 0 {
+            // There is no information about the relative error.
+            false
+        } else if max_rel == F::zero() {
+            true
+        } else {
+            // Equal to total * (rms / max_rel)^2.
+            let hits = self.sum_rel.value() / (max_rel.to_f64() * max_rel.to_f64());
+            hits >= CONVERGED_HITS
+        }
     }
 
     /// Returns maximum relative error encountered.
@@ -152,6 +752,58 @@ impl<F: FloatExt, In: fmt::Debug + Default + Copy> Error<F, In> {
         self.max_rel.0
     }
 
+    /// Returns the maximum relative error of the correctly rounded results
+    /// with respect to the truth given to [`calculate_with_truth`]. This is the
+    /// best achievable maximum relative error on the samples. If no truth was
+    /// given, it is zero.
+    ///
+    /// [`calculate_with_truth`]: struct.Error.html#method.calculate_with_truth
+    pub fn rounding_floor(&self) -> F {
+        F::from_f64(self.rounding.map_or(0.0, |(_, floor)| floor))
+    }
+
+    /// Returns the ratio of the maximum relative error with respect to the
+    /// truth given to [`calculate_with_truth`] and the [`rounding_floor`]. A
+    /// correctly rounded implementation has the ratio 1 and the ratio says how
+    /// many times an implementation is worse than that. If no truth was given
+    /// or the floor is zero, `None` is returned.
+    ///
+    /// [`calculate_with_truth`]: struct.Error.html#method.calculate_with_truth
+    /// [`rounding_floor`]: struct.Error.html#method.rounding_floor
+    pub fn quality_ratio(&self) -> Option<F> {
+        match self.rounding {
+            Some((max_rel, floor)) if floor > 0.0 => Some(F::from_f64(max_rel / floor)),
+            _ => None,
+        }
+    }
+
+    /// Returns maximum relative error encountered within the reliable
+    /// sub-range (see [`reliable`]). If no sub-range was specified, it is equal
+    /// to [`max_rel`].
+    ///
+    /// [`reliable`]: struct.Error.html#method.reliable
+    /// [`max_rel`]: struct.Error.html#method.max_rel
+    pub fn max_rel_reliable(&self) -> F {
+        self.max_rel_reliable.1
+    }
+
+    /// Returns the argument for maximum relative error encountered within the
+    /// reliable sub-range.
+    pub fn max_rel_reliable_arg(&self) -> In {
+        self.max_rel_reliable.0
+    }
+
+    /// Returns the index of the sample for maximum relative error encountered,
+    /// that is, the number of errors calculated before it. When the errors are
+    /// computed over a [`UniformSample`], the argument can be regenerated from
+    /// the index and the [seed] of the sample.
+    ///
+    /// [`UniformSample`]: ../domain/struct.UniformSample.html
+    /// [seed]: ../domain/struct.UniformSample.html#method.seed
+    pub fn max_rel_index(&self) -> u64 {
+        self.max_rel_index
+    }
+
     /// Returns maximum absolute error encountered.
     pub fn max_abs(&self) -> F {
         self.max_abs.1
@@ -162,54 +814,1251 @@ impl<F: FloatExt, In: fmt::Debug + Default + Copy> Error<F, In> {
         self.max_abs.0
     }
 
-    /// Returns root-mean-square error for all values encountered.
+    /// Returns maximum overshoot encountered.
+    pub fn max_overshoot(&self) -> F {
+        self.max_overshoot.1
+    }
+
+    /// Returns the argument for maximum overshoot encountered.
+    pub fn max_overshoot_arg(&self) -> In {
+        self.max_overshoot.0
+    }
+
+    /// Returns maximum undershoot encountered.
+    pub fn max_undershoot(&self) -> F {
+        self.max_undershoot.1
+    }
+
+    /// Returns the argument for maximum undershoot encountered.
+    pub fn max_undershoot_arg(&self) -> In {
+        self.max_undershoot.0
+    }
+
+    /// Returns maximum error in units in the last place encountered.
+    pub fn max_ulp(&self) -> u64 {
+        self.max_ulp.1
+    }
+
+    /// Returns the argument for maximum error in units in the last place
+    /// encountered.
+    pub fn max_ulp_arg(&self) -> In {
+        self.max_ulp.0
+    }
+
+    /// Returns mean error in units in the last place for all values
+    /// encountered. If there was no value, zero is returned.
+    pub fn mean_ulp(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_ulp.value() / self.count as f64
+        }
+    }
+
+    /// Returns root-mean-square error for all values encountered. Only the
+    /// values with nonzero real value contribute to it. If there was no such
+    /// value, zero is returned.
     pub fn rms(&self) -> F {
-        (self.sum_rel / self.total).sqrt()
+        if self.total == 0 {
+            F::zero()
+        } else {
+            F::from_f64((self.sum_rel.value() / self.total as f64).sqrt())
+        }
+    }
+
+    /// Returns root-mean-square error where each value is weighted by the
+    /// width of the interval it represents (see [`weighted`]), which
+    /// approximates the integral mean over the domain. Only the values with
+    /// nonzero real value contribute to it. If no weight was specified, it is
+    /// equal to [`rms`].
+    ///
+    /// [`weighted`]: struct.Error.html#method.weighted
+    /// [`rms`]: struct.Error.html#method.rms
+    pub fn rms_weighted(&self) -> F {
+        if self.weight.is_none() {
+            self.rms()
+        } else if self.sum_weight.value() == 0.0 {
+            F::zero()
+        } else {
+            F::from_f64((self.sum_rel_weighted.value() / self.sum_weight.value()).sqrt())
+        }
+    }
+
+    /// Returns root-mean-square error computed from absolute errors for all
+    /// values encountered. Unlike [`rms`], all values contribute to it,
+    /// including those with zero real value. If there was no value, zero is
+    /// returned.
+    ///
+    /// [`rms`]: struct.Error.html#method.rms
+    pub fn rms_abs(&self) -> F {
+        if self.count == 0 {
+            F::zero()
+        } else {
+            F::from_f64((self.sum_abs.value() / self.count as f64).sqrt())
+        }
+    }
+
+    /// Determines whether the errors are strictly smaller than the other errors
+    /// in given metric. If the errors are equal, neither is better than the
+    /// other, so when picking the best of many candidates, the first one of the
+    /// equally good is kept.
+    pub fn better_than(&self, other: &Self, metric: Metric) -> bool {
+        match metric {
+            Metric::MaxRel => self.max_rel() < other.max_rel(),
+            Metric::Rms => self.rms() < other.rms(),
+            Metric::MaxUlp => self.max_ulp() < other.max_ulp(),
+        }
     }
 
     /// Asserts the bounds for the errors that were encountered.
     pub fn assert(&self) {
-        // The errors for individual inputs are asserted in Error::compare.
+        // The errors for individual inputs are asserted in Error::compare,
+        // unless some violations are allowed.
+        if self.violations > self.bounds.max_violations {
+            let (_, worst) = self.worst_violation.as_ref().unwrap();
+            panic!(
+                "{} bound violations exceed the allowance of {}, the worst: {}",
+                self.violations, self.bounds.max_violations, worst
+            );
+        }
+
         let rms = self.rms();
         if !self.bounds.check_rms(rms) {
             panic!("overall quality is {:?} which is not satisfying", rms);
         }
+
+        let rms_abs = self.rms_abs();
+        if !self.bounds.check_rms_abs(rms_abs) {
+            panic!(
+                "overall quality in absolute terms is {:?} which is not satisfying",
+                rms_abs
+            );
+        }
     }
 
-    /// Prints the errors (and arguments) in a plain, human-readable form.
-    pub fn print_plain(&self, name: &str) {
-        println!(
-            "{}:\trelative = {:?} (at {:?}), absolute = {:?} (at {:?}), root-mean-square = {:?}",
+    /// Captures the current errors (and arguments formatted as strings in
+    /// scientific notation with configured [`precision`]), so they can be
+    /// collected independently of the input type, for example in a
+    /// [`Report`].
+    ///
+    /// [`precision`]: struct.Error.html#method.precision
+    ///
+    /// [`Report`]: ../report/struct.Report.html
+    pub fn snapshot(&self) -> ErrorSnapshot<F> {
+        ErrorSnapshot {
+            max_rel: self.max_rel(),
+            max_rel_arg: self.sci(self.max_rel_arg()),
+            max_abs: self.max_abs(),
+            max_abs_arg: self.sci(self.max_abs_arg()),
+            rms: self.rms(),
+            max_ulp: self.max_ulp(),
+            precision: self.precision,
+        }
+    }
+
+    /// Writes the errors (and arguments) in a plain, human-readable form. If
+    /// the reliable sub-range was specified, its maximum relative error is
+    /// written as well. The numbers are in scientific notation with configured
+    /// [`precision`].
+    ///
+    /// [`precision`]: struct.Error.html#method.precision
+    pub fn write_plain<W: Write>(&self, w: &mut W, name: &str) -> io::Result<()> {
+        write!(
+            w,
+            "{}:\trelative = {} (at {}), absolute = {} (at {}), root-mean-square = {}",
             name,
-            self.max_rel(),
-            self.max_rel_arg(),
-            self.max_abs(),
-            self.max_abs_arg(),
-            self.rms()
-        );
+            self.sci(self.max_rel()),
+            self.sci(self.max_rel_arg()),
+            self.sci(self.max_abs()),
+            self.sci(self.max_abs_arg()),
+            self.sci(self.rms())
+        )?;
+
+        if self.reliable.is_some() {
+            write!(
+                w,
+                ", reliable relative = {} (at {})",
+                self.sci(self.max_rel_reliable()),
+                self.sci(self.max_rel_reliable_arg())
+            )?;
+        }
+
+        if let Some(ratio) = self.quality_ratio() {
+            write!(
+                w,
+                ", rounding floor = {}, quality ratio = {}",
+                self.sci(self.rounding_floor()),
+                self.sci(ratio)
+            )?;
+        }
+
+        writeln!(w)?;
+
+        if !self.probes.is_empty() {
+            writeln!(w, "  notable points:")?;
+        }
+
+        for (label, arg, computed, real) in self.probes.iter() {
+            let abs = (*computed - *real).abs();
+            let rel = if *real != F::zero() {
+                (abs / *real).abs()
+            } else {
+                abs
+            };
+
+            writeln!(
+                w,
+                "    {} (at {}):\tcomputed = {}, real = {}, relative = {}, ulp = {}",
+                label,
+                self.sci(*arg),
+                self.sci(*computed),
+                self.sci(*real),
+                self.sci(rel),
+                computed.ulp_distance(*real)
+            )?;
+        }
+
+        Ok(())
     }
 
-    /// Prints the errors (and arguments) as one line in CSV format. Use
+    /// Writes the errors (and arguments) as one line in CSV format. The numbers
+    /// are formatted in the same way as in [`write_plain`]. Use
     /// [`print_csv_header`] method to print the header for the CSV file.
     ///
+    /// [`write_plain`]: struct.Error.html#method.write_plain
     /// [`print_csv_header`]: struct.Error.html#method.print_csv_header
-    pub fn print_csv(&self, name: &str) {
-        println!(
-            "{},{:?},{:?},{:?},{:?},{:?}",
+    pub fn write_csv<W: Write>(&self, w: &mut W, name: &str) -> io::Result<()> {
+        writeln!(
+            w,
+            "{},{},{},{},{},{}",
             name,
-            self.max_rel(),
-            self.max_rel_arg(),
-            self.max_abs(),
-            self.max_abs_arg(),
-            self.rms()
-        );
+            self.sci(self.max_rel()),
+            csv_escape(&self.sci(self.max_rel_arg())),
+            self.sci(self.max_abs()),
+            csv_escape(&self.sci(self.max_abs_arg())),
+            self.sci(self.rms())
+        )
     }
 
-    /// Prints the header for CSV file which contents are given by [`print_csv`]
-    /// method.
+    /// Prints the errors (and arguments) in a plain, human-readable form to
+    /// standard output. See [`write_plain`].
     ///
-    /// [`print_csv`]: struct.Error.html#method.print_csv
-    pub fn print_csv_header() {
-        println!("function,maximum relative,maximum relative argument,maximum absolute,maximum absolute argument,root-mean-square");
+    /// [`write_plain`]: struct.Error.html#method.write_plain
+    pub fn print_plain(&self, name: &str) {
+        self.write_plain(&mut io::stdout(), name).unwrap();
+    }
+
+    /// Prints the errors (and arguments) as one line in CSV format to standard
+    /// output. Use [`print_csv_header`] method to print the header for the CSV
+    /// file. See [`write_csv`].
+    ///
+    /// [`print_csv_header`]: struct.Error.html#method.print_csv_header
+    /// [`write_csv`]: struct.Error.html#method.write_csv
+    pub fn print_csv(&self, name: &str) {
+        self.write_csv(&mut io::stdout(), name).unwrap();
+    }
+
+    fn sci<T: Argument>(&self, value: T) -> String {
+        value.scientific(self.precision)
+    }
+
+    /// Prints the header for CSV file which contents are given by [`print_csv`]
+    /// method.
+    ///
+    /// [`print_csv`]: struct.Error.html#method.print_csv
+    pub fn print_csv_header() {
+        println!("function,maximum relative,maximum relative argument,maximum absolute,maximum absolute argument,root-mean-square");
+    }
+}
+
+impl<In: Argument> Error<f64, In> {
+    /// Calculates the errors between a computed pair of the value and its low
+    /// order correction, and the real value. The pair is summed in double
+    /// precision, so that the extra precision carried by the correction is not
+    /// lost before the comparison.
+    pub fn calculate_pair<F: FloatExt>(&mut self, arg: In, computed: (F, F), real: f64) {
+        self.calculate(arg, computed.0.to_f64() + computed.1.to_f64(), real);
+    }
+}
+
+impl<F: FloatExt> Error<F, F> {
+    /// Writes the retained samples (see [`retain_samples`]) as two
+    /// whitespace-separated columns, the argument and the relative error,
+    /// sorted by the argument. The output can be directly plotted by gnuplot
+    /// (see [`write_plot_script`]) or loaded by other tools. Samples with zero
+    /// real value, for which the relative error is not defined, are omitted.
+    ///
+    /// [`retain_samples`]: struct.Error.html#method.retain_samples
+    /// [`write_plot_script`]: struct.Error.html#method.write_plot_script
+    pub fn write_plot_data<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut samples = self.samples.clone().unwrap_or_default();
+        samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        for (arg, rel) in samples {
+            writeln!(w, "{:?} {:?}", arg, rel)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a minimal gnuplot script plotting the relative error of function
+    /// `name` from the data file at `data_path` written by
+    /// [`write_plot_data`].
+    ///
+    /// [`write_plot_data`]: struct.Error.html#method.write_plot_data
+    pub fn write_plot_script<W: Write>(w: &mut W, name: &str, data_path: &str) -> io::Result<()> {
+        writeln!(w, "set title \"{}\"", name)?;
+        writeln!(w, "set xlabel \"argument\"")?;
+        writeln!(w, "set ylabel \"relative error\"")?;
+        writeln!(
+            w,
+            "plot \"{}\" using 1:2 with points pointtype 7 pointsize 0.3 notitle",
+            data_path
+        )?;
+        writeln!(w, "pause mouse close")
+    }
+}
+
+/// A guard around [`Error`] that calls [`assert`] when it goes out of scope,
+/// so forgetting to assert the errors cannot silently pass a broken
+/// implementation. It dereferences to the inner [`Error`]. Created by
+/// [`Error::scope`].
+///
+/// # Examples
+///
+/// ```should_panic
+/// use nikisas_test::prelude::*;
+///
+/// let mut error = Error::scope(ErrorBounds::new().rms(0.001));
+///
+/// for x in UniformSample::with_count(1.0f32, 2.0, 1000) {
+///     error.calculate(x, x * 1.01, x);
+/// }
+///
+/// // Panics here, because the root-mean-square error exceeds the bound.
+/// ```
+///
+/// [`Error`]: struct.Error.html
+/// [`assert`]: struct.Error.html#method.assert
+/// [`Error::scope`]: struct.Error.html#method.scope
+pub struct ScopedError<F: FloatExt, In: Argument> {
+    // Taken when disarmed.
+    error: Option<Error<F, In>>,
+}
+
+impl<F: FloatExt, In: Argument> ScopedError<F, In> {
+    /// Disarms the guard and returns the inner error, whose bounds are then
+    /// not asserted automatically.
+    pub fn disarm(mut self) -> Error<F, In> {
+        self.error.take().unwrap()
+    }
+}
+
+impl<F: FloatExt, In: Argument> ops::Deref for ScopedError<F, In> {
+    type Target = Error<F, In>;
+
+    fn deref(&self) -> &Self::Target {
+        self.error.as_ref().unwrap()
+    }
+}
+
+impl<F: FloatExt, In: Argument> ops::DerefMut for ScopedError<F, In> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.error.as_mut().unwrap()
+    }
+}
+
+impl<F: FloatExt, In: Argument> Drop for ScopedError<F, In> {
+    fn drop(&mut self) {
+        // Panicking while already panicking would abort the process.
+        if let Some(error) = self.error.as_ref() {
+            if !thread::panicking() {
+                error.assert();
+            }
+        }
+    }
+}
+
+/// The errors captured at a certain point with the arguments formatted as
+/// strings. See [`Error::snapshot`].
+///
+/// [`Error::snapshot`]: struct.Error.html#method.snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorSnapshot<F> {
+    /// Maximum relative error.
+    pub max_rel: F,
+    /// The argument for maximum relative error.
+    pub max_rel_arg: String,
+    /// Maximum absolute error.
+    pub max_abs: F,
+    /// The argument for maximum absolute error.
+    pub max_abs_arg: String,
+    /// Root-mean-square error.
+    pub rms: F,
+    /// Maximum error in units in the last place.
+    pub max_ulp: u64,
+    /// The number of digits after the decimal point used for the arguments,
+    /// which is used also for the errors when they are printed.
+    pub precision: usize,
+}
+
+impl<F: FloatExt> ErrorSnapshot<F> {
+    /// Computes the change of the errors with respect to a previous run, for
+    /// example before tuning the implementation. Negative changes are
+    /// improvements.
+    pub fn diff(&self, previous: &Self) -> ErrorDelta<F> {
+        ErrorDelta {
+            max_rel: self.max_rel - previous.max_rel,
+            rms: self.rms - previous.rms,
+            max_ulp: self.max_ulp as i64 - previous.max_ulp as i64,
+        }
+    }
+}
+
+/// The errors of an implementation split into the approximation error on the
+/// primary range and the error on the entire domain. See
+/// [`Domain::error_split`].
+///
+/// [`Domain::error_split`]: ../domain/trait.Domain.html#tymethod.error_split
+pub struct ErrorSplit<F, In> {
+    /// Errors on the primary range, that is, the approximation error.
+    pub primary_error: Error<F, In>,
+    /// Errors on the entire domain, including the argument reduction error.
+    pub full_error: Error<F, In>,
+}
+
+impl<F: FloatExt, In: Argument> ErrorSplit<F, In> {
+    /// Returns the increase of the maximum relative error on the entire domain
+    /// over the primary range, which is attributed to the argument reduction.
+    /// It may be negative when the sample of the entire domain misses the
+    /// worst inputs in the primary range.
+    pub fn reduction_error(&self) -> F {
+        self.full_error.max_rel() - self.primary_error.max_rel()
+    }
+
+    /// Returns the increase of the root-mean-square error on the entire domain
+    /// over the primary range (see [`reduction_error`]).
+    ///
+    /// [`reduction_error`]: struct.ErrorSplit.html#method.reduction_error
+    pub fn reduction_rms(&self) -> F {
+        self.full_error.rms() - self.primary_error.rms()
+    }
+}
+
+/// The signed change of the errors between two runs. See
+/// [`ErrorSnapshot::diff`].
+///
+/// [`ErrorSnapshot::diff`]: struct.ErrorSnapshot.html#method.diff
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorDelta<F> {
+    /// Change of the maximum relative error.
+    pub max_rel: F,
+    /// Change of the root-mean-square error.
+    pub rms: F,
+    /// Change of the maximum error in units in the last place.
+    pub max_ulp: i64,
+}
+
+impl<F: FloatExt> ErrorDelta<F> {
+    /// Determines whether the error in given metric decreased.
+    pub fn improved(&self, metric: Metric) -> bool {
+        self.ordering(metric) == Ordering::Less
+    }
+
+    /// Determines whether the error in given metric increased.
+    pub fn regressed(&self, metric: Metric) -> bool {
+        self.ordering(metric) == Ordering::Greater
+    }
+
+    fn ordering(&self, metric: Metric) -> Ordering {
+        match metric {
+            Metric::MaxRel => self.max_rel.partial_cmp(&F::zero()),
+            Metric::Rms => self.rms.partial_cmp(&F::zero()),
+            Metric::MaxUlp => Some(self.max_ulp.cmp(&0)),
+        }
+        .unwrap_or(Ordering::Equal)
+    }
+
+    fn arrow(&self, metric: Metric) -> &'static str {
+        match self.ordering(metric) {
+            Ordering::Less => "↓ improved",
+            Ordering::Greater => "↑ regressed",
+            Ordering::Equal => "= unchanged",
+        }
+    }
+
+    /// Writes the changes in a plain, human-readable form with arrows
+    /// indicating improvements and regressions.
+    pub fn write_plain<W: Write>(&self, w: &mut W, name: &str) -> io::Result<()> {
+        writeln!(
+            w,
+            "{}:\trelative {:+.*e} ({}), root-mean-square {:+.*e} ({}), ulp {:+} ({})",
+            name,
+            DEFAULT_PRECISION,
+            self.max_rel.to_f64(),
+            self.arrow(Metric::MaxRel),
+            DEFAULT_PRECISION,
+            self.rms.to_f64(),
+            self.arrow(Metric::Rms),
+            self.max_ulp,
+            self.arrow(Metric::MaxUlp)
+        )
+    }
+
+    /// Prints the changes to standard output. See [`write_plain`].
+    ///
+    /// [`write_plain`]: struct.ErrorDelta.html#method.write_plain
+    pub fn print_plain(&self, name: &str) {
+        self.write_plain(&mut io::stdout(), name).unwrap();
+    }
+}
+
+/// Aggregator structure for functions with discrete output (e.g. integers),
+/// for which the result is either correct or not. It counts the inputs for
+/// which the computed value exactly matches the real value.
+///
+/// The first generic parameter specifies the type of the output. The second
+/// one specifies the input argument(s).
+pub struct ExactError<T, In> {
+    first_mismatch: Option<(In, T, T)>,
+    matches: u64,
+    total: u64,
+}
+
+impl<T: PartialEq + fmt::Debug + Copy, In: fmt::Debug + Copy> ExactError<T, In> {
+    /// Initializes the structure.
+    pub fn new() -> Self {
+        ExactError {
+            first_mismatch: None,
+            matches: 0,
+            total: 0,
+        }
+    }
+
+    /// Compares computed value and real value. If they differ and it is the
+    /// first mismatch, it is stored along with the argument that caused it.
+    pub fn calculate(&mut self, arg: In, computed: T, real: T) {
+        self.total += 1;
+
+        if computed == real {
+            self.matches += 1;
+        } else if self.first_mismatch.is_none() {
+            self.first_mismatch = Some((arg, computed, real));
+        }
+    }
+
+    /// Returns the number of inputs for which the computed value was correct.
+    pub fn matches(&self) -> u64 {
+        self.matches
+    }
+
+    /// Returns the number of inputs for which the computed value was wrong.
+    pub fn mismatches(&self) -> u64 {
+        self.total - self.matches
+    }
+
+    /// Returns the fraction of inputs for which the computed value was
+    /// correct. If there was no value, one is returned.
+    pub fn match_ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.matches as f64 / self.total as f64
+        }
+    }
+
+    /// Returns the argument, computed value and real value of the first
+    /// mismatch encountered, if any.
+    pub fn first_mismatch(&self) -> Option<(In, T, T)> {
+        self.first_mismatch
+    }
+
+    /// Asserts that the computed values were correct for all inputs.
+    pub fn assert(&self) {
+        if let Some((arg, computed, real)) = self.first_mismatch {
+            panic!(
+                "mismatch at {:?}, computed = {:?}, real = {:?} ({} mismatches out of {})",
+                arg,
+                computed,
+                real,
+                self.mismatches(),
+                self.total
+            );
+        }
+    }
+}
+
+impl<T: PartialEq + fmt::Debug + Copy, In: fmt::Debug + Copy> Default for ExactError<T, In> {
+    fn default() -> Self {
+        ExactError::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Domain, UniformSample};
+
+    // Implementation which is always slightly less than the real value.
+    fn undershooting(x: f32) -> (f32, f32) {
+        (x - 0.01, x)
+    }
+
+    #[test]
+    fn overshoot() {
+        let error = UniformSample::with_count(1.0, 2.0, 1000).error(undershooting);
+        assert_eq!(error.max_overshoot(), 0.0);
+        assert!(error.max_undershoot() > 0.0);
+
+        UniformSample::with_count(1.0, 2.0, 1000)
+            .assert(ErrorBounds::new().overshoot(0.0), undershooting);
+    }
+
+    #[test]
+    fn check_with() {
+        // The error vanishes near 1 and grows with the distance from it.
+        let good = |x: f32| (x + 0.001 * (x - 1.0) * (x - 1.0), x);
+        let bounds =
+            || ErrorBounds::new().check_with(|x: f32, rel, _| rel <= 0.001 * (x - 1.0).abs());
+
+        UniformSample::with_count(0.5, 2.0, 1000).assert(bounds(), good);
+
+        // The error is tiny, but constant even near 1.
+        let bad = |x: f32| (x + 0.0001, x);
+        let result = std::panic::catch_unwind(|| {
+            UniformSample::with_count(0.5, 2.0, 1000).assert(bounds(), bad);
+        });
+        assert!(result.is_err());
+
+        // The same error is fine with a fixed bound.
+        UniformSample::with_count(0.5, 2.0, 1000).assert(ErrorBounds::new().rel(0.001), bad);
+    }
+
+    #[test]
+    #[should_panic(expected = "undershoot exceeded")]
+    fn undershoot() {
+        UniformSample::with_count(1.0, 2.0, 1000)
+            .assert(ErrorBounds::new().undershoot(0.001), undershooting);
+    }
+
+    #[test]
+    fn reliable() {
+        // The absolute error is constant, so the relative error is inflated
+        // near the root of sine.
+        let sin = |x: f32| (x.sin() + 1e-6, x.sin());
+        let error = UniformSample::with_count(-3.0, 3.0, 1000).fold(
+            Error::new().reliable(|x: &f32| x.abs() >= 0.5 && x.abs() <= 2.5),
+            |mut error, x| {
+                let (computed, real) = sin(x);
+                error.calculate(x, computed, real);
+                error
+            },
+        );
+
+        assert!(error.max_rel_reliable() < 3e-6);
+        assert!(error.max_rel_reliable_arg().abs() >= 0.5);
+        assert!(error.max_rel_reliable_arg().abs() <= 2.5);
+        assert!(error.max_rel() > 1e-4);
+        assert!(error.max_rel_arg().abs() < 0.5 || error.max_rel_arg().abs() > 2.5);
+
+        // Without the sub-range, both are the same.
+        let error = UniformSample::with_count(-3.0, 3.0, 1000).error(sin);
+        assert_eq!(error.max_rel_reliable(), error.max_rel());
+    }
+
+    #[test]
+    fn calculate_pair() {
+        // The correction carries the part of the double precision value which
+        // does not fit into single precision.
+        let split = |x: f32| {
+            let real = (x as f64).exp();
+            let hi = real as f32;
+            ((hi, (real - hi as f64) as f32), real)
+        };
+
+        let error = UniformSample::with_count(-1.0, 1.0, 1000).error_pair(split);
+        assert!(error.max_rel() < 1e-13);
+
+        let error = UniformSample::with_count(-1.0, 1.0, 1000).error_pair(|x| {
+            let ((hi, _), real) = split(x);
+            ((hi, 0.0), real)
+        });
+        assert!(error.max_rel() > 1e-8);
+    }
+
+    #[test]
+    fn scientific_format() {
+        let mut error = Error::<f64, f64>::new();
+        error.calculate(0.5, 1.0 + 4.15e-6, 1.0);
+
+        let mut plain = Vec::new();
+        error.write_plain(&mut plain, "exp").unwrap();
+        assert_eq!(
+            String::from_utf8(plain).unwrap(),
+            "exp:\trelative = 4.150e-6 (at 5.000e-1), absolute = 4.150e-6 (at 5.000e-1), root-mean-square = 4.150e-6\n"
+        );
+
+        let error = Error::<f64, (f64, i32)>::new().precision(1);
+        let mut csv = Vec::new();
+        error.write_csv(&mut csv, "powi").unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "powi,0.0e0,\"(0.0e0, 0)\",0.0e0,\"(0.0e0, 0)\",0.0e0\n"
+        );
+
+        assert_eq!(f32::NEG_INFINITY.scientific(2), "-inf");
+        assert_eq!(f64::NAN.scientific(2), "NaN");
+        assert_eq!(1e-45f32.scientific(2), "1.40e-45");
+        assert_eq!(
+            ((1.5f32, -3i32), (0.25f64, 2u8, 1e+10f32)).scientific(2),
+            "((1.50e0, -3), (2.50e-1, 2, 1.00e10))"
+        );
+    }
+
+    #[test]
+    fn quality_ratio() {
+        let error = |compute: &dyn Fn(f32) -> f32| {
+            let mut error = Error::<f32, f32>::new();
+            for x in UniformSample::with_count(0.1f32, 1.5, 10000) {
+                error.calculate_with_truth(x, compute(x), (x as f64).sin());
+            }
+            error
+        };
+
+        // Correctly rounded.
+        let rounded = error(&|x| (x as f64).sin() as f32);
+        assert_eq!(rounded.max_rel(), 0.0);
+        assert!(rounded.rounding_floor() > 0.0 && rounded.rounding_floor() <= f32::EPSILON / 2.0);
+        assert_eq!(rounded.quality_ratio(), Some(1.0));
+
+        // Nearly correctly rounded.
+        let ratio = error(&|x| x.sin()).quality_ratio().unwrap();
+        assert!((1.0..2.0).contains(&ratio), "{}", ratio);
+
+        // Poor approximation.
+        let ratio = error(&|x| x - x * x * x / 6.0).quality_ratio().unwrap();
+        assert!(ratio > 1e+4, "{}", ratio);
+
+        assert_eq!(Error::<f32, f32>::new().quality_ratio(), None);
+        assert_eq!(Error::<f32, f32>::new().rounding_floor(), 0.0);
+    }
+
+    #[test]
+    fn calculate_bracket() {
+        let mut error = Error::new();
+
+        // Faithful rounding: both machine numbers around the real value are
+        // correct.
+        for &x in &[0.1f32, 0.5, 1.0, 3.0, 7.5] {
+            let truth = (x as f64).exp();
+            let rounded = truth as f32;
+            let (low, high) = if (rounded as f64) < truth {
+                (rounded, rounded.nextup())
+            } else {
+                (rounded.nextdown(), rounded)
+            };
+
+            error.calculate_bracket(x, low, low, high);
+            error.calculate_bracket(x, high, low, high);
+        }
+
+        assert_eq!(error.max_abs(), 0.0);
+        assert_eq!(error.max_rel(), 0.0);
+        assert_eq!(error.max_ulp(), 0);
+
+        // Outside the bracket, the error is the distance to the nearer bound.
+        error.calculate_bracket(2.0, 1.5, 2.0, 4.0);
+        assert_eq!(error.max_abs(), 0.5);
+        assert_eq!(error.max_rel(), 0.25);
+        error.calculate_bracket(3.0, 5.0, 2.0, 4.0);
+        assert_eq!(error.max_abs(), 1.0);
+        assert_eq!(error.max_abs_arg(), 3.0);
+
+        let mut error = Error::new();
+        error.calculate_bracket(1.0, f32::NAN, 2.0, 4.0);
+        assert_eq!(error.max_ulp(), u64::MAX);
+    }
+
+    #[test]
+    fn rms_weighted() {
+        // The relative error grows with the magnitude of the input.
+        let compute = |x: f32| (x * (1.0 + 1e-6 * x), x);
+
+        let uniform = UniformSample::builder(1.0f32, 100.0).count(100000).build();
+        let weight = uniform.weight();
+        let uniform = uniform.fold(Error::new().weighted(weight), |mut error, x| {
+            let (computed, real) = compute(x);
+            error.calculate(x, computed, real);
+            error
+        });
+
+        let log = UniformSample::builder(1.0f32, 100.0)
+            .count(100000)
+            .log_scale()
+            .build();
+        let weight = log.weight();
+        let log = log.fold(Error::new().weighted(weight), |mut error, x| {
+            let (computed, real) = compute(x);
+            error.calculate(x, computed, real);
+            error
+        });
+
+        // The true value is sqrt(1/99 * integral of (1e-6 * x)^2 from 1 to 100).
+        let expected = 1e-6 * ((100.0f32.powi(3) - 1.0) / 3.0 / 99.0).sqrt();
+
+        // Unweighted, the sparse large inputs are underrepresented in the
+        // logarithmic scale.
+        assert!(log.rms() < 0.8 * uniform.rms());
+
+        assert_eq!(uniform.rms_weighted(), uniform.rms());
+        assert!((uniform.rms_weighted() - expected).abs() < 0.01 * expected);
+        assert!((log.rms_weighted() - expected).abs() < 0.01 * expected);
+
+        assert_eq!(Error::<f32, f32>::new().rms_weighted(), 0.0);
+    }
+
+    #[test]
+    fn probe() {
+        let mut error = Error::<f64, f64>::new();
+        error.calculate(0.5, 1.0 + 4.15e-6, 1.0);
+        error.probe("exp(1)", 1.0, 2.7, core::f64::consts::E);
+        error.probe("ln(1)", 1.0, 0.0, 0.0);
+
+        // The notable points do not affect the errors.
+        assert!(error.max_rel() < 5e-6);
+        assert_eq!(error.max_rel_arg(), 0.5);
+        assert_eq!(error.probes().len(), 2);
+        assert_eq!(error.probes()[0].0, "exp(1)");
+
+        let mut plain = Vec::new();
+        error.write_plain(&mut plain, "exp").unwrap();
+        let plain = String::from_utf8(plain).unwrap();
+        let lines = plain.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "  notable points:");
+        assert!(lines[2].starts_with(
+            "    exp(1) (at 1.000e0):\tcomputed = 2.700e0, real = 2.718e0, relative = 6.726e-3, ulp = "
+        ));
+        assert_eq!(
+            lines[3],
+            "    ln(1) (at 1.000e0):\tcomputed = 0.000e0, real = 0.000e0, relative = 0.000e0, ulp = 0"
+        );
+    }
+
+    #[test]
+    fn zero_real() {
+        // The reference is identically zero in the whole region, thus only the
+        // absolute error can be computed.
+        let error = UniformSample::with_count(-1.0, 1.0, 1000).error(|_| (1e-6, 0.0));
+        assert_eq!(error.rms(), 0.0);
+        assert_eq!(error.max_rel(), 0.0);
+        assert_eq!(error.max_abs(), 1e-6);
+
+        UniformSample::with_count(-1.0, 1.0, 1000)
+            .assert(ErrorBounds::new().abs(1e-5).rms(1e-3), |_| (1e-6, 0.0));
+
+        assert_eq!(Error::<f32, f32>::new().rms(), 0.0);
+    }
+
+    #[test]
+    fn rms_abs() {
+        // The absolute error is constant, but the relative error grows near
+        // the zeros of the function.
+        let sin = |x: f32| (x.sin() + 1e-4, x.sin());
+        let error = UniformSample::with_count(-3.0, 3.0, 1000).error(sin);
+
+        assert!((error.rms_abs() - 1e-4).abs() < 1e-6);
+        assert!(error.rms() > 5.0 * error.rms_abs());
+
+        UniformSample::with_count(-3.0, 3.0, 1000).assert(ErrorBounds::new().rms_abs(2e-4), sin);
+
+        let result = std::panic::catch_unwind(|| {
+            UniformSample::with_count(-3.0, 3.0, 1000).assert(ErrorBounds::new().rms(2e-4), sin);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rms_compensated() {
+        // Naive summation of this many squares drifts from the real value by
+        // the order of 1e-10 relatively.
+        let mut error = Error::<f64, f64>::new();
+        let rel = 1.1e-4;
+
+        for _ in 0..10_000_000 {
+            error.calculate(1.0, 1.0 + rel, 1.0);
+        }
+
+        let rel = (1.0 + rel) - 1.0;
+        assert!((error.rms() - rel).abs() <= rel * 1e-14);
+        assert!((error.rms_abs() - rel).abs() <= rel * 1e-14);
+    }
+
+    #[test]
+    fn max_rel_index() {
+        let build = |seed| {
+            UniformSample::builder(1e-3f32, 1e+3)
+                .count(10000)
+                .seed(seed)
+                .log_scale()
+                .build()
+        };
+
+        for seed in 0..5 {
+            let sample = build(seed);
+            assert_eq!(sample.seed(), seed);
+
+            let error = sample.error(|x| ((x as f64).ln() as f32, x.ln()));
+            let worst = build(seed).nth(error.max_rel_index() as usize).unwrap();
+            assert_eq!(worst.to_bits(), error.max_rel_arg().to_bits());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "overall quality")]
+    fn scope() {
+        let mut error = Error::scope(ErrorBounds::new().rms(0.001));
+
+        for x in UniformSample::with_count(1.0, 2.0, 1000) {
+            let (computed, real) = undershooting(x);
+            error.calculate(x, computed, real);
+        }
+
+        // No explicit assert, the bounds are asserted when the error is
+        // dropped at the end of the scope.
+    }
+
+    #[test]
+    fn scope_disarm() {
+        let mut error = Error::scope(ErrorBounds::new().rms(0.001));
+
+        for x in UniformSample::with_count(1.0, 2.0, 1000) {
+            let (computed, real) = undershooting(x);
+            error.calculate(x, computed, real);
+        }
+
+        let error = error.disarm();
+        assert!(error.rms() > 0.001);
+    }
+
+    #[test]
+    fn plot_data() {
+        let error = UniformSample::with_count(-1.0f32, 1.0, 1000).fold(
+            Error::new().retain_samples(),
+            |mut error, x| {
+                error.calculate(x, x * 1.01, x);
+                error
+            },
+        );
+
+        let mut data = Vec::new();
+        error.write_plot_data(&mut data).unwrap();
+        let data = String::from_utf8(data).unwrap();
+
+        let rows = data
+            .lines()
+            .map(|line| {
+                let mut columns = line.split_whitespace().map(|c| c.parse::<f32>().unwrap());
+                let row = (columns.next().unwrap(), columns.next().unwrap());
+                assert_eq!(columns.next(), None);
+                row
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows.len(), 1000);
+        assert!(rows.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(rows.iter().all(|&(_, rel)| (rel - 0.01).abs() < 1e-6));
+
+        let mut script = Vec::new();
+        Error::<f32, f32>::write_plot_script(&mut script, "id", "id.dat").unwrap();
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("plot \"id.dat\" using 1:2"));
+    }
+
+    #[test]
+    fn diff() {
+        // One large error, otherwise exact.
+        let mut previous = Error::<f32, f32>::new();
+        previous.calculate(1.0, 1.0f32.nextup().nextup(), 1.0);
+        for &x in &[2.0, 3.0, 4.0, 5.0] {
+            previous.calculate(x, x, x);
+        }
+
+        // Smaller maximum, but larger errors overall.
+        let mut current = Error::<f32, f32>::new();
+        for &x in &[1.0, 2.0, 4.0, 8.0] {
+            current.calculate(x, x.nextup(), x);
+        }
+
+        let delta = current.snapshot().diff(&previous.snapshot());
+        assert_eq!(delta.max_rel, current.max_rel() - previous.max_rel());
+        assert_eq!(delta.rms, current.rms() - previous.rms());
+        assert_eq!(delta.max_ulp, -1);
+
+        assert!(delta.improved(Metric::MaxRel));
+        assert!(!delta.regressed(Metric::MaxRel));
+        assert!(delta.improved(Metric::MaxUlp));
+        assert!(!delta.improved(Metric::Rms));
+        assert!(delta.regressed(Metric::Rms));
+
+        // Diffing against itself shows no change.
+        let same = current.snapshot().diff(&current.snapshot());
+        for &metric in &[Metric::MaxRel, Metric::Rms, Metric::MaxUlp] {
+            assert!(!same.improved(metric) && !same.regressed(metric));
+        }
+
+        let mut plain = Vec::new();
+        delta.write_plain(&mut plain, "id").unwrap();
+        let plain = String::from_utf8(plain).unwrap();
+        assert!(plain.starts_with("id:\trelative -"), "{}", plain);
+        assert!(
+            plain.contains("(↓ improved), root-mean-square +"),
+            "{}",
+            plain
+        );
+        assert!(
+            plain.ends_with("(↑ regressed), ulp -1 (↓ improved)\n"),
+            "{}",
+            plain
+        );
+
+        let mut plain = Vec::new();
+        same.write_plain(&mut plain, "id").unwrap();
+        assert_eq!(
+            String::from_utf8(plain).unwrap(),
+            "id:\trelative +0.000e0 (= unchanged), root-mean-square +0.000e0 (= unchanged), ulp +0 (= unchanged)\n"
+        );
+    }
+
+    #[test]
+    fn negative_real() {
+        let mut error = Error::<f32, f32>::new();
+        error.calculate(-2.0, -2.002, -2.0);
+        error.calculate(1.0, 1.0005, 1.0);
+
+        // Dividing by the negative real value alone would give the relative
+        // error of -1e-3, so the maximum would be reported at 1 instead.
+        assert_eq!(error.max_rel_arg(), -2.0);
+        assert!((error.max_rel() - 1e-3).abs() < 1e-6);
+        assert!((error.rms() - 7.9e-4).abs() < 1e-5);
+
+        let bounds = ErrorBounds::new().rel(5e-4);
+        assert!(!bounds.check(-2.0, -2.002, -2.0));
+        assert!(bounds.check(-2.0, -2.0005, -2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "error exceeded at -2.0")]
+    fn negative_real_violation() {
+        let mut error = Error::<f32, f32>::with_bounds(ErrorBounds::new().rel(5e-4));
+        error.calculate(-2.0, -2.002, -2.0);
+    }
+
+    fn violations(allowance: u64) -> Error<f32, f32> {
+        let mut error = Error::with_bounds(ErrorBounds::new().rel(1e-3).max_violations(allowance));
+
+        // The function is completely wrong at exactly three inputs.
+        for i in 1..=100 {
+            let x = i as f32;
+            let computed = if i % 40 == 0 || i == 7 { 2.0 * x } else { x };
+            error.calculate(x, computed, x);
+        }
+
+        error
+    }
+
+    #[test]
+    fn max_violations() {
+        let error = violations(5);
+        assert_eq!(error.violations(), 3);
+        error.assert();
+
+        let error = violations(3);
+        assert_eq!(error.violations(), 3);
+        error.assert();
+    }
+
+    #[test]
+    fn max_violations_several_bounds() {
+        let bounds = ErrorBounds::new()
+            .rel(1e-3)
+            .ulp(4)
+            .overshoot(1e-3)
+            .max_violations(5);
+        let mut error = Error::with_bounds(bounds);
+
+        // Each of the three wrong inputs violates all the bounds.
+        for i in 1..=100 {
+            let x = i as f32;
+            let computed = if i % 40 == 0 || i == 7 { 2.0 * x } else { x };
+            error.calculate(x, computed, x);
+        }
+
+        assert_eq!(error.violations(), 3);
+        error.assert();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "3 bound violations exceed the allowance of 2, the worst: error exceeded at 80.0"
+    )]
+    fn max_violations_exceeded() {
+        violations(2).assert();
+    }
+
+    #[test]
+    #[should_panic(expected = "error exceeded at 7.0 (sample 6)")]
+    fn max_violations_default() {
+        // Without any allowance, the first violation panics immediately.
+        violations(0);
+    }
+
+    #[test]
+    fn better_than() {
+        // Larger maximum, but smaller errors overall.
+        let mut a = Error::<f32, f32>::new();
+        a.calculate(1.0, 1.0, 1.0);
+        a.calculate(2.0, 2.0, 2.0);
+        a.calculate(3.0, 3.0f32.nextup().nextup(), 3.0);
+
+        // Smaller maximum, but larger errors overall.
+        let mut b = Error::<f32, f32>::new();
+        b.calculate(1.0, 1.0f32.nextup(), 1.0);
+        b.calculate(2.0, 2.0f32.nextup(), 2.0);
+        b.calculate(3.0, 3.0f32.nextup(), 3.0);
+
+        assert!(b.better_than(&a, Metric::MaxRel));
+        assert!(!a.better_than(&b, Metric::MaxRel));
+        assert!(a.better_than(&b, Metric::Rms));
+        assert!(!b.better_than(&a, Metric::Rms));
+        assert!(b.better_than(&a, Metric::MaxUlp));
+        assert!(!a.better_than(&b, Metric::MaxUlp));
+
+        // Ties.
+        for &metric in &[Metric::MaxRel, Metric::Rms, Metric::MaxUlp] {
+            assert!(!a.better_than(&a, metric));
+        }
+    }
+
+    #[test]
+    fn exact() {
+        let mut error = ExactError::new();
+        assert_eq!(error.match_ratio(), 1.0);
+
+        for x in 0..100 {
+            error.calculate(x, x / 10, if x == 42 || x == 57 { 0 } else { x / 10 });
+        }
+
+        assert_eq!(error.matches(), 98);
+        assert_eq!(error.mismatches(), 2);
+        assert_eq!(error.match_ratio(), 0.98);
+        assert_eq!(error.first_mismatch(), Some((42, 4, 0)));
+
+        let result = std::panic::catch_unwind(|| error.assert());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn likely_converged() {
+        // The error grows smoothly with the distance from 1.
+        let smooth = |x: f32| (x * (1.0 + 0.001 * (x - 1.0)), x);
+        assert!(UniformSample::with_count(1.0, 2.0, 1000)
+            .error(smooth)
+            .is_likely_converged());
+
+        // The error is large only in a narrow region.
+        let spiky = |x: f32| {
+            if (x - 1.5).abs() < 0.025 {
+                (x * 1.1, x)
+            } else {
+                (x * (1.0 + 1e-6), x)
+            }
+        };
+        assert!(!UniformSample::with_count(1.0, 2.0, 100)
+            .error(spiky)
+            .is_likely_converged());
+        assert!(UniformSample::with_count(1.0, 2.0, 10000)
+            .error(spiky)
+            .is_likely_converged());
+    }
+
+    #[test]
+    fn ulp() {
+        let error = UniformSample::with_count(1.0, 2.0, 1000).error(|x: f32| (x.nextup(), x));
+        assert_eq!(error.max_ulp(), 1);
+        assert_eq!(error.mean_ulp(), 1.0);
+
+        // Values straddling zero have a large, but finite distance.
+        let mut error = Error::<f32, f32>::new();
+        error.calculate(0.0, 1e-30, -1e-30);
+        assert_eq!(error.max_ulp(), 2 * 1e-30f32.to_ordinal() as u64);
+
+        UniformSample::with_count(1.0, 2.0, 1000)
+            .assert(ErrorBounds::new().ulp(1), |x: f32| (x.nextup(), x));
+
+        let result = std::panic::catch_unwind(|| {
+            UniformSample::with_count(1.0, 2.0, 1000)
+                .assert(ErrorBounds::new().ulp(1), |x: f32| (x.nextup().nextup(), x));
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn ulp_guarantee() {
+        let bounds = ErrorBounds::new().ulp(2);
+        let real = |x: f32| (x as f64).exp() as f32;
+
+        // The exponential from the standard library is within 2 ULP, the
+        // approximation by compound interest is not.
+        let good = UniformSample::with_count(-10.0, 10.0, 10000)
+            .failures(&bounds, |x: f32| (x.exp(), real(x)));
+        assert!(good.is_empty());
+
+        let bad = UniformSample::with_count(-10.0, 10.0, 10000)
+            .failures(&bounds, |x: f32| ((1.0 + x / 4096.0).powi(4096), real(x)));
+        assert!(!bad.is_empty());
+    }
+
+    #[test]
+    fn ulp_at_zero() {
+        let bounds = ErrorBounds::<f32>::new().ulp(2);
+        assert!(bounds.check(0.0, 0.0, 0.0));
+        assert!(bounds.check(0.0, -0.0, 0.0));
+        assert!(bounds.check(0.0, 1e-45, 0.0));
+        assert!(bounds.check(0.0, -1e-45, 0.0));
+        assert!(!bounds.check(0.0, 3e-45, 0.0));
+
+        // Outside of zero, the bound applies as usual.
+        assert!(bounds.check(0.0, 3e-45, 1e-45));
+
+        // The special handling does not make the zero bound looser.
+        let exact = ErrorBounds::<f32>::new().ulp(0);
+        assert!(!exact.check(0.0, 1e-45, 0.0));
+    }
+
+    #[test]
+    fn panic_message() {
+        let message = |computed: f32, real: f32, bounds: ErrorBounds<f32>| {
+            let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                Error::with_bounds(bounds).calculate(2.0f32, computed, real);
+            }))
+            .unwrap_err();
+
+            payload.downcast_ref::<String>().unwrap().clone()
+        };
+
+        let msg = message(1.25, 1.5, ErrorBounds::new().rel(0.001));
+        assert!(msg.starts_with("error exceeded at 2.0"));
+        assert!(msg.contains("computed = 1.25"));
+        assert!(msg.contains("real = 1.5"));
+
+        let msg = message(0.25, 0.0, ErrorBounds::new().abs(0.001));
+        assert!(msg.contains("computed = 0.25"));
+        assert!(msg.contains("real = 0.0"));
+
+        let msg = message(1.75, 1.5, ErrorBounds::new().overshoot(0.0));
+        assert!(msg.contains("computed = 1.75"));
+        assert!(msg.contains("real = 1.5"));
     }
 }
@@ -1,10 +1,13 @@
 //! Traits and constants to abstract f32 and f64 types.
 
 use std::fmt;
+use std::num::FpCategory;
 use std::ops;
 
 use rand::distributions::uniform::SampleUniform;
 
+use crate::error::Argument;
+
 /// Trait for all operations on floating point numbers that are required by the
 /// crate. It also define some useful methods like [`nextup`], [`decompose`] or
 /// [`floats_between`].
@@ -14,6 +17,7 @@ use rand::distributions::uniform::SampleUniform;
 /// [`floats_between`]: trait.FloatExt#method.floats_between
 pub trait FloatExt:
     SampleUniform
+    + Argument
     + Copy
     + fmt::Debug
     + Default
@@ -29,6 +33,26 @@ pub trait FloatExt:
     /// Gives the previous machine number before self.
     fn nextdown(self) -> Self;
 
+    /// Gives the next machine number after self in the direction of `toward`.
+    /// If self equals `toward`, `toward` is returned (this matters only for
+    /// zeros of different signs). If either of the numbers is NaN, the result
+    /// is NaN. Unlike [`nextup`] and [`nextdown`], infinities are supported and
+    /// the steps across zero skip the negative zero.
+    ///
+    /// [`nextup`]: trait.FloatExt#method.nextup
+    /// [`nextdown`]: trait.FloatExt#method.nextdown
+    fn nextafter(self, toward: Self) -> Self {
+        if self.is_nan() || toward.is_nan() {
+            self + toward
+        } else if self < toward {
+            Self::from_ordinal(self.to_ordinal() + 1)
+        } else if self > toward {
+            Self::from_ordinal(self.to_ordinal() - 1)
+        } else {
+            toward
+        }
+    }
+
     /// Decomposes the floating number into real f and integer n, such that self
     /// = f * 2^n and 1 ≤ f < 2.
     fn decompose(self) -> (Self, i32);
@@ -36,6 +60,57 @@ pub trait FloatExt:
     /// Gets the total number of machine numbers between self and other.
     fn floats_between(self, other: Self) -> u64;
 
+    /// Returns the category of the number (zero, subnormal, normal, infinite
+    /// or NaN). Special values should be handled by matching on the category
+    /// rather than by ad-hoc comparisons.
+    fn classify(self) -> FpCategory;
+
+    /// Determines whether the number is subnormal, that is, nonzero and less
+    /// than the smallest positive normal number in magnitude.
+    fn is_subnormal(self) -> bool {
+        self.classify() == FpCategory::Subnormal
+    }
+
+    /// Maps the number to an integer such that the order is preserved and
+    /// consecutive machine numbers map to consecutive integers. Both zeros map
+    /// to 0.
+    fn to_ordinal(self) -> i64;
+
+    /// Inverse of [`to_ordinal`].
+    ///
+    /// [`to_ordinal`]: trait.FloatExt#method.to_ordinal
+    fn from_ordinal(ordinal: i64) -> Self;
+
+    /// Gets the distance between self and other in units in the last place,
+    /// that is, the number of steps by [`nextup`] needed to get from the
+    /// smaller to the greater. If the numbers have different signs, the
+    /// distance is the sum of their distances from zero. Two NaNs have zero
+    /// distance, while the distance between NaN and a number is `u64::MAX`.
+    ///
+    /// [`nextup`]: trait.FloatExt#method.nextup
+    fn ulp_distance(self, other: Self) -> u64 {
+        match (self.is_nan(), other.is_nan()) {
+            // The difference of ordinals may not fit into i64, but the
+            // distance always fits into u64.
+            (false, false) => {
+                (self.to_ordinal() as i128 - other.to_ordinal() as i128).unsigned_abs() as u64
+            }
+            (true, true) => 0,
+            _ => u64::MAX,
+        }
+    }
+
+    /// Returns a number with the magnitude of self and the sign of `sign`.
+    /// Unlike negation, it gives the same result for both x and -x, so it is
+    /// handy for generating sign-paired inputs.
+    fn copysign(self, sign: Self) -> Self;
+
+    /// Returns 1 if the number is positive (including +0 and +∞), -1 if it is
+    /// negative (including -0 and -∞) and NaN if it is NaN.
+    fn signum(self) -> Self;
+
+    #[doc(hidden)]
+    fn is_nan(self) -> bool;
     #[doc(hidden)]
     fn abs(self) -> Self;
     #[doc(hidden)]
@@ -43,6 +118,10 @@ pub trait FloatExt:
     #[doc(hidden)]
     fn round(self) -> Self;
     #[doc(hidden)]
+    fn ln(self) -> Self;
+    #[doc(hidden)]
+    fn exp(self) -> Self;
+    #[doc(hidden)]
     fn modulo(self, m: i64) -> i64;
     #[doc(hidden)]
     fn zero() -> Self;
@@ -50,6 +129,10 @@ pub trait FloatExt:
     fn one() -> Self;
     #[doc(hidden)]
     fn eps() -> Self;
+    #[doc(hidden)]
+    fn from_f64(value: f64) -> Self;
+    #[doc(hidden)]
+    fn to_f64(self) -> f64;
 }
 
 macro_rules! nextup {
@@ -138,12 +221,41 @@ macro_rules! floats_between {
     }};
 }
 
+macro_rules! to_ordinal {
+    ($value:expr, $float:tt) => {{
+        debug_assert!(!$value.is_nan());
+
+        let bits = $value.to_bits();
+        let magnitude = (bits & !consts::$float::SIGN_MASK) as i64;
+
+        if bits & consts::$float::SIGN_MASK == 0 {
+            magnitude
+        } else {
+            -magnitude
+        }
+    }};
+}
+
+macro_rules! from_ordinal {
+    ($ordinal:expr, $float:tt, $uint:ty) => {{
+        let ordinal: i64 = $ordinal;
+        let magnitude = ordinal.unsigned_abs() as $uint;
+
+        if ordinal < 0 {
+            <$float>::from_bits(magnitude | consts::$float::SIGN_MASK)
+        } else {
+            <$float>::from_bits(magnitude)
+        }
+    }};
+}
+
 mod consts {
     pub mod f32 {
         pub const EXP_MASK: u32 = 0x7f800000;
         pub const EXP_BIAS: i32 = 127;
         pub const MANTISSA_MASK: u32 = 0x007fffff;
         pub const MANTISSA_DIGITS: u32 = 23;
+        pub const SIGN_MASK: u32 = 0x80000000;
     }
 
     pub mod f64 {
@@ -151,6 +263,7 @@ mod consts {
         pub const EXP_BIAS: i32 = 1023;
         pub const MANTISSA_MASK: u64 = 0x000fffffffffffff;
         pub const MANTISSA_DIGITS: u64 = 52;
+        pub const SIGN_MASK: u64 = 0x8000000000000000;
     }
 }
 
@@ -171,6 +284,30 @@ impl FloatExt for f32 {
         floats_between!(self, other, f32)
     }
 
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
+
+    fn to_ordinal(self) -> i64 {
+        to_ordinal!(self, f32)
+    }
+
+    fn from_ordinal(ordinal: i64) -> Self {
+        from_ordinal!(ordinal, f32, u32)
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        self.copysign(sign)
+    }
+
+    fn signum(self) -> Self {
+        self.signum()
+    }
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
     fn abs(self) -> Self {
         self.abs()
     }
@@ -183,6 +320,14 @@ impl FloatExt for f32 {
         self.round()
     }
 
+    fn ln(self) -> Self {
+        self.ln()
+    }
+
+    fn exp(self) -> Self {
+        self.exp()
+    }
+
     fn modulo(self, m: i64) -> i64 {
         (self.round() as i64) % m
     }
@@ -198,6 +343,14 @@ impl FloatExt for f32 {
     fn eps() -> Self {
         std::f32::EPSILON
     }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
 }
 
 impl FloatExt for f64 {
@@ -217,6 +370,30 @@ impl FloatExt for f64 {
         floats_between!(self, other, f64)
     }
 
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
+
+    fn to_ordinal(self) -> i64 {
+        to_ordinal!(self, f64)
+    }
+
+    fn from_ordinal(ordinal: i64) -> Self {
+        from_ordinal!(ordinal, f64, u64)
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        self.copysign(sign)
+    }
+
+    fn signum(self) -> Self {
+        self.signum()
+    }
+
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
     fn abs(self) -> Self {
         self.abs()
     }
@@ -229,6 +406,14 @@ impl FloatExt for f64 {
         self.round()
     }
 
+    fn ln(self) -> Self {
+        self.ln()
+    }
+
+    fn exp(self) -> Self {
+        self.exp()
+    }
+
     fn modulo(self, m: i64) -> i64 {
         (self.round() as i64) % m
     }
@@ -244,6 +429,14 @@ impl FloatExt for f64 {
     fn eps() -> Self {
         std::f64::EPSILON
     }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
 }
 
 #[cfg(test)]
@@ -261,6 +454,115 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn next_monotonic(x: f32) {
+            if x.is_finite() {
+                assert!(x.nextup() > x);
+                assert!(x.nextdown() < x);
+
+                if x.abs() < f32::MAX {
+                    assert_eq!(x.nextdown().nextup(), x);
+                    assert_eq!(x.nextup().nextdown(), x);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn classify() {
+        fn check<F: FloatExt>(min_positive: F, max: F) {
+            let zero = F::zero();
+            let subnormal = F::from_ordinal(1);
+            let infinity = max + max;
+            let nan = infinity - infinity;
+
+            assert_eq!(FloatExt::classify(zero), FpCategory::Zero);
+            assert_eq!(FloatExt::classify(zero - zero), FpCategory::Zero);
+            assert_eq!(FloatExt::classify(subnormal), FpCategory::Subnormal);
+            assert_eq!(
+                FloatExt::classify(min_positive.nextdown()),
+                FpCategory::Subnormal
+            );
+            assert_eq!(FloatExt::classify(min_positive), FpCategory::Normal);
+            assert_eq!(FloatExt::classify(F::one()), FpCategory::Normal);
+            assert_eq!(FloatExt::classify(zero - max), FpCategory::Normal);
+            assert_eq!(FloatExt::classify(infinity), FpCategory::Infinite);
+            assert_eq!(FloatExt::classify(zero - infinity), FpCategory::Infinite);
+            assert_eq!(FloatExt::classify(nan), FpCategory::Nan);
+        }
+
+        check(f32::MIN_POSITIVE, f32::MAX);
+        check(f64::MIN_POSITIVE, f64::MAX);
+    }
+
+    #[test]
+    fn copysign_signum() {
+        fn check<F: FloatExt>(x: F) {
+            let zero = F::zero();
+            let one = F::one();
+            let infinity = F::from_f64(f64::INFINITY);
+
+            assert_eq!(x.signum(), one);
+            assert_eq!((zero - x).signum(), zero - one);
+            assert_eq!(x.copysign(zero - one), zero - x);
+            assert_eq!((zero - x).copysign(one), x);
+            assert_eq!(x.copysign(zero - one), (zero - x).copysign(zero - one));
+
+            // Signed zeros and infinities.
+            let negative_zero = zero.copysign(zero - one);
+            assert_eq!(zero.signum(), one);
+            assert_eq!(negative_zero.signum(), zero - one);
+            assert_eq!(x.copysign(negative_zero), zero - x);
+            assert_eq!((zero - infinity).signum(), zero - one);
+            assert!((infinity - infinity).signum().is_nan());
+        }
+
+        check(2.5f32);
+        check(2.5f64);
+        check(f32::MIN_POSITIVE);
+        check(f64::MAX);
+    }
+
+    #[test]
+    fn nextafter() {
+        fn check<F: FloatExt>(max: F) {
+            let zero = F::zero();
+            let one = F::one();
+            let subnormal = F::from_ordinal(1);
+            let infinity = max + max;
+
+            // Direction selection.
+            assert_eq!(one.nextafter(infinity), one.nextup());
+            assert_eq!(one.nextafter(zero), one.nextdown());
+            assert_eq!((zero - one).nextafter(zero), (zero - one).nextup());
+            assert_eq!(one.nextafter(one + one), one.nextup());
+            assert_eq!(one.nextafter(one), one);
+
+            // The zero boundary.
+            assert_eq!(zero.nextafter(one), subnormal);
+            assert_eq!(zero.nextafter(zero - one), zero - subnormal);
+            assert_eq!(subnormal.nextafter(zero - one), zero);
+            assert_eq!((zero - subnormal).nextafter(one), zero);
+            assert_eq!((zero - zero).nextafter(one), subnormal);
+            assert_eq!(zero.nextafter(zero - zero).to_ordinal(), 0);
+
+            // Infinities and NaN.
+            assert_eq!(max.nextafter(infinity), infinity);
+            assert_eq!(infinity.nextafter(zero), max);
+            assert_eq!((zero - infinity).nextafter(zero), zero - max);
+            assert!(one.nextafter(infinity - infinity).is_nan());
+            assert!((infinity - infinity).nextafter(one).is_nan());
+        }
+
+        check(f32::MAX);
+        check(f64::MAX);
+
+        // Zeros of different signs.
+        assert!(0.0f32.nextafter(-0.0).is_sign_negative());
+        assert!((-0.0f64).nextafter(0.0).is_sign_positive());
+    }
+
     #[test]
     fn next_special() {
         assert!(0.0f32.nextup() > 0.0);
@@ -268,6 +570,109 @@ mod tests {
         assert!(0.0f32.nextdown().nextup().nextup() > 0.0);
     }
 
+    #[test]
+    fn next_boundaries() {
+        let min_subnormal = f32::from_bits(1);
+
+        // Both zeros are treated the same.
+        assert_eq!(0.0f32.nextup().to_bits(), min_subnormal.to_bits());
+        assert_eq!((-0.0f32).nextup().to_bits(), min_subnormal.to_bits());
+        assert_eq!(0.0f32.nextdown().to_bits(), (-min_subnormal).to_bits());
+        assert_eq!((-0.0f32).nextdown().to_bits(), (-min_subnormal).to_bits());
+
+        // Stepping from the smallest subnormals lands on a zero.
+        assert_eq!(min_subnormal.nextdown(), 0.0);
+        assert_eq!((-min_subnormal).nextup(), 0.0);
+
+        for &x in &[
+            min_subnormal,
+            -min_subnormal,
+            f32::MIN_POSITIVE,
+            -f32::MIN_POSITIVE,
+            f32::MIN_POSITIVE.nextdown(),
+            1.0,
+            -1.0,
+            f32::MAX.nextdown(),
+            f32::MIN.nextup(),
+        ] {
+            assert!(x.nextup() > x);
+            assert!(x.nextdown() < x);
+            assert_eq!(x.nextup().nextdown(), x);
+            assert_eq!(x.nextdown().nextup(), x);
+        }
+
+        // The largest finite numbers step to infinities.
+        assert_eq!(f32::MAX.nextup(), f32::INFINITY);
+        assert_eq!(f32::MIN.nextdown(), f32::NEG_INFINITY);
+
+        assert_eq!(0.0f64.nextup().to_bits(), 1);
+        assert_eq!((-0.0f64).nextup().to_bits(), 1);
+        assert_eq!(f64::MAX.nextup(), f64::INFINITY);
+    }
+
+    #[test]
+    fn next_crosses_zero() {
+        let eps = f32::from_bits(5);
+
+        let mut x = -eps;
+        let mut steps = 0;
+        while x < eps {
+            let next = x.nextup();
+            assert!(next > x);
+            x = next;
+            steps += 1;
+        }
+
+        assert_eq!(x, eps);
+        // The zero is visited only once.
+        assert_eq!(steps, 10);
+        assert_eq!(steps + 1, (-eps).floats_between(eps));
+    }
+
+    proptest! {
+        #[test]
+        fn ordinal(x: f32, y: f32) {
+            if x.is_finite() && y.is_finite() {
+                // Negative zero maps to positive zero.
+                let x = if x == -0.0 { 0.0 } else { x };
+                assert_eq!(f32::from_ordinal(x.to_ordinal()).to_bits(), x.to_bits());
+                assert_eq!(x.to_ordinal() < y.to_ordinal(), x < y);
+            }
+        }
+    }
+
+    #[test]
+    fn ordinal_special() {
+        assert_eq!(0.0f32.to_ordinal(), 0);
+        assert_eq!((-0.0f32).to_ordinal(), 0);
+        assert_eq!(0.0f32.nextup().to_ordinal(), 1);
+        assert_eq!(0.0f32.nextdown().to_ordinal(), -1);
+        assert_eq!(1.0f64.nextup().to_ordinal() - 1.0f64.to_ordinal(), 1);
+        assert_eq!(
+            (1.0f32.to_ordinal() - (-1.0f32).to_ordinal() + 1) as u64,
+            (-1.0f32).floats_between(1.0)
+        );
+    }
+
+    #[test]
+    fn ulp_distance() {
+        assert_eq!(1.0f32.ulp_distance(1.0), 0);
+        assert_eq!(1.0f32.ulp_distance(1.0.nextup()), 1);
+        assert_eq!(1.0f32.nextup().ulp_distance(1.0), 1);
+        assert_eq!(0.0f32.ulp_distance(-0.0), 0);
+        assert_eq!(
+            1.0f32.ulp_distance(-1.0),
+            2 * (0.0f32.floats_between(1.0) - 1)
+        );
+        assert_eq!(f32::MAX.ulp_distance(f32::INFINITY), 1);
+        assert_eq!(f32::NAN.ulp_distance(f32::NAN), 0);
+        assert_eq!(f32::NAN.ulp_distance(1.0), u64::MAX);
+        assert_eq!(
+            f64::INFINITY.ulp_distance(f64::NEG_INFINITY),
+            2 * f64::INFINITY.to_ordinal() as u64
+        );
+    }
+
     #[test]
     fn floats_between() {
         let floats_per_exponent = (1 << MANTISSA_DIGITS) as u64;
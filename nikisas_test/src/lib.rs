@@ -41,6 +41,10 @@
 //!     .assert(ErrorBounds::new().rel(0.001).abs(0.0001), |x| (exp(x), x.exp()));
 //! ```
 //!
+//! When the errors are calculated manually, [`Error::scope`] creates a guard
+//! that asserts the bounds when it goes out of scope, so the final assertion
+//! cannot be forgotten.
+//!
 //! # Errors
 //!
 //! ## Relative error
@@ -89,12 +93,88 @@
 //! rms_err = sqrt( 1 / N * sum_{x_i in N} ( (F(x_i) - f(x_i)) / f(x_i) )^2 )
 //! ```
 //!
-//! where N is the total number of sampled values. If the root-mean-square error
+//! where N is the total number of sampled values for which f(x_i) is nonzero
+//! (if there are none, the error is zero). If the root-mean-square error
 //! is close to the maximum relative error, it indicates that the implementation
 //! is very stable without pathological inputs. If it is significantly lower,
 //! that means than there are pathological inputs at which the implementation
 //! performs poorly in comparison with others.
 //!
+//! The squares are accumulated in double precision using compensated
+//! summation, so the result stays accurate even for very large samples.
+//!
+//! For the same reasons as discussed in the case of absolute error, the
+//! root-mean-square error can be also computed from absolute errors (see
+//! [`Error::rms_abs`]). This is more meaningful for functions with bounded
+//! output like sine, for which the relative errors near zeros of the function
+//! would dominate the overall quality.
+//!
+//! Rather than excluding the regions near the zeros from the tests, which
+//! would hide genuinely bad behavior there, a reliable sub-range of the inputs
+//! can be specified (see [`Error::reliable`]). The maximum relative error is
+//! then reported both within this sub-range and overall.
+//!
+//! ## Overshoot and undershoot
+//!
+//! Overshoot and undershoot are one-sided absolute errors, that is, the maximum
+//! amount by which the computed value is greater, respectively less, than the
+//! real value. They are useful when the result must never exceed (or fall
+//! below) a certain value, for example a probability estimate that must stay
+//! below or equal to one.
+//!
+//! ## Units in the last place
+//!
+//! The error in units in the last place (ULP) is the number of machine numbers
+//! between the computed and the real value. Unlike the relative error, it is
+//! aware of the precision of the floating point type, and so it is usually the
+//! most meaningful single number describing the quality of an implementation.
+//! When the values have different signs, the error is the sum of their
+//! distances from zero. Both maximum and mean are tracked and the maximum can
+//! be bounded using [`ErrorBounds::ulp`]. The real value should be rounded to
+//! the same type as the computed one.
+//!
+//! ## Rounding floor
+//!
+//! Even a correctly rounded result has a nonzero error with respect to the
+//! infinitely precise value. When the truth is given in double precision (see
+//! [`Error::calculate_with_truth`]), this rounding floor is tracked as well and
+//! the maximum relative error is normalized by it. The ratio says how many
+//! times the implementation is worse than the best achievable one.
+//!
+//! ## Exact match
+//!
+//! Functions with discrete output, like integers, are either correct or not.
+//! For them, [`Domain::error_exact`] counts the inputs for which the computed
+//! value exactly matches the real value (see [`ExactError`]).
+//!
+//! ## Double-word results
+//!
+//! Implementations which return the result as a pair of the value and a low
+//! order correction are tested using [`Domain::error_pair`]. The pair is summed
+//! in double precision before comparing with the real value, so the errors
+//! show whether the extra precision is real.
+//!
+//! ## Transformed output
+//!
+//! Sometimes the meaningful error is in a transformed output space, for
+//! example logit space for probabilities. [`Domain::error_transformed`] applies
+//! a transformation to both computed and real value before the errors are
+//! calculated.
+//!
+//! ## Reports
+//!
+//! When testing multiple functions, their errors can be collected into a
+//! single [`Report`] and then printed all at once as a plain table, CSV, JSON
+//! or Markdown table (the format used in the documentation of nikisas).
+//!
+//! To see how the error changes across the domain, the samples can be
+//! retained (see [`Error::retain_samples`]) and written as data for plotting,
+//! together with a minimal gnuplot script.
+//!
+//! For analyzing the error distributions at scale, the per-sample values and
+//! errors can be collected into an Arrow record batch using [`SampleBatch`]
+//! (requires `arrow` feature).
+//!
 //! # Domain
 //!
 //! The approximations usually reduce the input into a small *primary* range,
@@ -104,16 +184,36 @@
 //! The tests should therefore be split at least to two parts: the first one
 //! samples inputs from the primary range to exercise the approximation error,
 //! and the second one samples inputs from the entire input range to determine
-//! the additional error caused by argument reduction.
+//! the additional error caused by argument reduction. Both parts can be
+//! computed at once using [`Domain::error_split`].
 //!
 //! Values from primary range should be sampled uniformly. For the whole range
-//! that is usually much bigger, values should be sampled in logarithmic scale,
-//! because that more simulates the distribution of numbers encountered in
-//! real-world. This is not implemented yet.
+//! that is usually much bigger, values should be sampled in logarithmic scale
+//! (see [`UniformSample::builder`]), because that more simulates the
+//! distribution of numbers encountered in real-world. Alternatively, values can
+//! be sampled using [`BitUniformSample`], where each machine number in the
+//! interval has the same probability. Subnormal numbers, which often need
+//! special handling, can be sampled using [`SubnormalSample`]. To find out
+//! which magnitudes contribute to the error the most, use
+//! [`Domain::error_by_decade`].
+//!
+//! Implementations often handle special cases separately, and random sampling
+//! may easily miss them. Whether all branches were exercised can be checked
+//! using [`BranchCounter`], either incremented in the `compute` closure or
+//! filled by [`Domain::branch_coverage`]. Known-hard inputs, like exact powers
+//! of two or the endpoints of the domain, can be always included in addition to
+//! the random samples using [`Domain::with_fixed`].
+//!
+//! The random samples are reproducible. The index of the sample with maximum
+//! relative error is available in [`Error::max_rel_index`], and together with
+//! the seed of the [`UniformSample`] it regenerates exactly that input.
+//!
+//! Inputs at which the bounds are violated can be collected using
+//! [`Domain::failures`] and saved to a file, which is then replayed in future
+//! test runs (see [`corpus`] module).
 //!
 //! # TODO
 //!
-//! * Logarithmic distribution for large intervals.
 //! * Confidence estimation for the error bounds.
 //! * More comfortable testing for multiple-argument functions.
 //!
@@ -122,19 +222,59 @@
 //! nikisas_test is licensed under MIT. Feel free to use it, contribute or
 //! spread the word.
 //!
+//! [`Error::scope`]: error/struct.Error.html#method.scope
+//! [`Error::max_rel_index`]: error/struct.Error.html#method.max_rel_index
+//! [`UniformSample`]: domain/struct.UniformSample.html
+//! [`Error::retain_samples`]: error/struct.Error.html#method.retain_samples
+//! [`Error::rms_abs`]: error/struct.Error.html#method.rms_abs
+//! [`Error::reliable`]: error/struct.Error.html#method.reliable
+//! [`Error::calculate_with_truth`]: error/struct.Error.html#method.calculate_with_truth
 //! [`ErrorBounds`]: error/struct.ErrorBounds.html
+//! [`ErrorBounds::ulp`]: error/struct.ErrorBounds.html#method.ulp
+//! [`BitUniformSample`]: domain/struct.BitUniformSample.html
+//! [`SubnormalSample`]: domain/struct.SubnormalSample.html
+//! [`UniformSample::builder`]: domain/struct.UniformSample.html#method.builder
+//! [`Report`]: report/struct.Report.html
+//! [`Domain::error_exact`]: domain/trait.Domain.html#tymethod.error_exact
+//! [`Domain::error_pair`]: domain/trait.Domain.html#tymethod.error_pair
+//! [`ExactError`]: error/struct.ExactError.html
+//! [`Domain::error_by_decade`]: domain/trait.Domain.html#tymethod.error_by_decade
+//! [`Domain::failures`]: domain/trait.Domain.html#tymethod.failures
+//! [`BranchCounter`]: coverage/struct.BranchCounter.html
+//! [`Domain::branch_coverage`]: domain/trait.Domain.html#tymethod.branch_coverage
+//! [`Domain::with_fixed`]: domain/trait.Domain.html#tymethod.with_fixed
+//! [`Domain::error_split`]: domain/trait.Domain.html#tymethod.error_split
+//! [`Domain::error_transformed`]: domain/trait.Domain.html#tymethod.error_transformed
+//! [`corpus`]: corpus/index.html
+//! [`SampleBatch`]: arrow/struct.SampleBatch.html
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod corpus;
+pub mod coverage;
 pub mod domain;
 pub mod error;
 pub mod float;
+pub mod report;
 pub mod utils;
 
-pub use domain::{Domain, Exhaustive, UniformSample};
-pub use error::{Error, ErrorBounds};
+pub use coverage::BranchCounter;
+pub use domain::{
+    BitUniformSample, Domain, Exhaustive, SubnormalSample, Symmetry, UniformSample,
+    UniformSampleBuilder, WithFixed,
+};
+pub use error::{
+    Argument, Error, ErrorBounds, ErrorDelta, ErrorSnapshot, ErrorSplit, ExactError, Metric,
+    ScopedError,
+};
+pub use report::Report;
 
 /// Convenience re-export of common members.
 pub mod prelude {
-    pub use super::{Domain, Error, ErrorBounds, Exhaustive, UniformSample};
+    pub use super::{
+        BitUniformSample, Domain, Error, ErrorBounds, Exhaustive, SubnormalSample, Symmetry,
+        UniformSample,
+    };
 }
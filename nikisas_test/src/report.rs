@@ -0,0 +1,347 @@
+//! Collecting the errors of multiple functions into a single report.
+//!
+//! # Examples
+//!
+//! ```
+//! use nikisas_test::prelude::*;
+//! use nikisas_test::report::Report;
+//!
+//! # fn exp(x: f32) -> f32 { x.exp() }
+//! # fn ln(x: f32) -> f32 { x.ln() }
+//! let mut report = Report::new();
+//!
+//! let error = UniformSample::with_count(-87.3, 88.7, 10000).error(|x| (exp(x), x.exp()));
+//! report.add("exp", &error);
+//!
+//! let error = UniformSample::with_count(1e-30, 3.4e+38, 10000).error(|x| (ln(x), x.ln()));
+//! report.add("ln", &error);
+//!
+//! // Prints the table in the same format as is in the README of nikisas.
+//! report.print_markdown();
+//! ```
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::error::{Argument, Error, ErrorSnapshot};
+use crate::float::FloatExt;
+
+/// Collection of named errors which can be printed in several formats at once.
+pub struct Report<F> {
+    entries: Vec<(String, ErrorSnapshot<F>)>,
+}
+
+impl<F: FloatExt + fmt::LowerExp> Report<F> {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Report {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the errors of a function with given name.
+    pub fn add<In: Argument>(&mut self, name: &str, error: &Error<F, In>) {
+        self.add_snapshot(name, error.snapshot());
+    }
+
+    /// Adds already captured errors of a function with given name.
+    pub fn add_snapshot(&mut self, name: &str, snapshot: ErrorSnapshot<F>) {
+        self.entries.push((name.to_string(), snapshot));
+    }
+
+    /// Returns the collected entries.
+    pub fn entries(&self) -> &[(String, ErrorSnapshot<F>)] {
+        &self.entries
+    }
+
+    /// Writes the errors in a plain, human-readable form, one function per
+    /// line. See [`Error::print_plain`].
+    ///
+    /// [`Error::print_plain`]: ../error/struct.Error.html#method.print_plain
+    pub fn write_plain<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (name, e) in self.entries.iter() {
+            writeln!(
+                w,
+                "{}:\trelative = {} (at {}), absolute = {} (at {}), root-mean-square = {}",
+                name,
+                e.max_rel.scientific(e.precision),
+                e.max_rel_arg,
+                e.max_abs.scientific(e.precision),
+                e.max_abs_arg,
+                e.rms.scientific(e.precision)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the errors in CSV format including the header. See
+    /// [`Error::print_csv`].
+    ///
+    /// [`Error::print_csv`]: ../error/struct.Error.html#method.print_csv
+    pub fn write_csv<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "function,maximum relative,maximum relative argument,maximum absolute,maximum absolute argument,root-mean-square")?;
+
+        for (name, e) in self.entries.iter() {
+            writeln!(
+                w,
+                "{},{},{},{},{},{}",
+                name,
+                e.max_rel.scientific(e.precision),
+                csv_escape(&e.max_rel_arg),
+                e.max_abs.scientific(e.precision),
+                csv_escape(&e.max_abs_arg),
+                e.rms.scientific(e.precision)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the maximum relative and root-mean-square errors as a Markdown
+    /// table, in the same format as is used in the documentation of nikisas.
+    /// The maximum relative errors are rounded up, so that they remain upper
+    /// bounds.
+    pub fn write_markdown<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let header = [
+            "function",
+            "maximum relative",
+            "root mean square (overall quality)",
+        ];
+
+        let rows = self
+            .entries
+            .iter()
+            .map(|(name, e)| [name.clone(), round_up(e.max_rel), format!("{:.2e}", e.rms)])
+            .collect::<Vec<_>>();
+
+        let mut widths = [header[0].len(), header[1].len(), header[2].len()];
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        writeln!(
+            w,
+            "| {:w0$} | {:w1$} | {:w2$} |",
+            header[0],
+            header[1],
+            header[2],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )?;
+
+        writeln!(
+            w,
+            "| {} | {} | {} |",
+            "-".repeat(widths[0]),
+            "-".repeat(widths[1]),
+            "-".repeat(widths[2])
+        )?;
+
+        for row in rows.iter() {
+            writeln!(
+                w,
+                "| {:w0$} | {:w1$} | {:w2$} |",
+                row[0],
+                row[1],
+                row[2],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the errors as a JSON array of objects, one per function.
+    pub fn write_json<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "[")?;
+
+        for (i, (name, e)) in self.entries.iter().enumerate() {
+            let separator = if i + 1 < self.entries.len() { "," } else { "" };
+            writeln!(
+                w,
+                "  {{\"function\": {}, \"max_rel\": {}, \"max_rel_arg\": {}, \"max_abs\": {}, \"max_abs_arg\": {}, \"rms\": {}}}{}",
+                json_string(name),
+                json_number(e.max_rel),
+                json_string(&e.max_rel_arg),
+                json_number(e.max_abs),
+                json_string(&e.max_abs_arg),
+                json_number(e.rms),
+                separator
+            )?;
+        }
+
+        writeln!(w, "]")
+    }
+
+    /// Prints the report in a plain, human-readable form to standard output.
+    pub fn print_plain(&self) {
+        self.write_plain(&mut io::stdout()).unwrap();
+    }
+
+    /// Prints the report in CSV format to standard output.
+    pub fn print_csv(&self) {
+        self.write_csv(&mut io::stdout()).unwrap();
+    }
+
+    /// Prints the report as a Markdown table to standard output.
+    pub fn print_markdown(&self) {
+        self.write_markdown(&mut io::stdout()).unwrap();
+    }
+
+    /// Prints the report in JSON format to standard output.
+    pub fn print_json(&self) {
+        self.write_json(&mut io::stdout()).unwrap();
+    }
+}
+
+impl<F: FloatExt + fmt::LowerExp> Default for Report<F> {
+    fn default() -> Self {
+        Report::new()
+    }
+}
+
+pub(crate) fn csv_escape(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn round_up<F: FloatExt>(value: F) -> String {
+    let value = value.to_f64();
+    let rounded = format!("{:.2e}", value);
+
+    match rounded.parse::<f64>() {
+        Ok(parsed) if parsed < value => {
+            // Increment the last digit, the formatting takes care of the carry.
+            let (mantissa, exponent) = rounded.split_once('e').unwrap();
+            let digits = mantissa.replace('.', "").parse::<i64>().unwrap() + 1;
+            let exponent = exponent.parse::<i32>().unwrap() - 2;
+            format!(
+                "{:.2e}",
+                format!("{}e{}", digits, exponent).parse::<f64>().unwrap()
+            )
+        }
+        _ => rounded,
+    }
+}
+
+fn json_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn json_number<F: FloatExt>(value: F) -> String {
+    let value = format!("{:?}", value);
+
+    // JSON does not support NaN nor infinities.
+    if value.contains("NaN") || value.contains("inf") {
+        String::from("null")
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report<f32> {
+        let mut exp = Error::new();
+        exp.calculate(0.5f32, 1.6487, 1.6487212);
+        exp.calculate(2.0f32, 7.3890, 7.389056);
+
+        let mut pow = Error::new();
+        pow.calculate((3.0f32, 0.5f32), 1.7320, 1.7320508);
+
+        let mut report = Report::new();
+        report.add("exp", &exp);
+        report.add("pow", &pow);
+        report
+    }
+
+    fn output<T: Fn(&Report<f32>, &mut Vec<u8>) -> io::Result<()>>(write: T) -> String {
+        let mut buffer = Vec::new();
+        write(&report(), &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn entries() {
+        let report = report();
+        assert_eq!(report.entries().len(), 2);
+        assert_eq!(report.entries()[0].0, "exp");
+        assert_eq!(report.entries()[1].1.max_rel_arg, "(3.000e0, 5.000e-1)");
+    }
+
+    #[test]
+    fn plain() {
+        let output = output(Report::write_plain);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("exp:\trelative = "));
+        assert!(lines[1].contains("(at (3.000e0, 5.000e-1))"));
+    }
+
+    #[test]
+    fn csv() {
+        let output = output(Report::write_csv);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("function,maximum relative"));
+        assert!(lines[1].starts_with("exp,"));
+        assert!(lines[2].contains(",\"(3.000e0, 5.000e-1)\","));
+    }
+
+    #[test]
+    fn markdown() {
+        let output = output(Report::write_markdown);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "| function | maximum relative | root mean square (overall quality) |"
+        );
+        assert_eq!(
+            lines[1],
+            "| -------- | ---------------- | ---------------------------------- |"
+        );
+        assert!(lines[2].starts_with("| exp      | "));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+
+    #[test]
+    fn markdown_rounds_up() {
+        assert_eq!(round_up(2.312651e-6f32), "2.32e-6");
+        assert_eq!(round_up(9.996e-6f32), "1.00e-5");
+        assert_eq!(round_up(1.5f32), "1.50e0");
+        assert_eq!(round_up(0.0f32), "0.00e0");
+        assert_eq!(round_up(f32::INFINITY), "inf");
+    }
+
+    #[test]
+    fn json() {
+        let output = output(Report::write_json);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "[");
+        assert!(lines[1].starts_with("  {\"function\": \"exp\", \"max_rel\": "));
+        assert!(lines[1].ends_with("},"));
+        assert!(lines[2].contains("\"max_rel_arg\": \"(3.000e0, 5.000e-1)\""));
+        assert!(lines[2].ends_with("}"));
+        assert_eq!(lines[3], "]");
+    }
+
+    #[test]
+    fn json_non_finite() {
+        assert_eq!(json_number(f32::NAN), "null");
+        assert_eq!(json_number(f32::NEG_INFINITY), "null");
+        assert_eq!(json_number(1.5f32), "1.5");
+    }
+}
@@ -29,6 +29,10 @@
 //!     .filter(avoid_odd_mults(core::f32::consts::PI / 2.0))
 //!     .error(|x| (tan(x), x.tan()));
 //! ```
+use std::fmt;
+
+use crate::domain::{Domain, Exhaustive, UniformSample};
+use crate::error::{Error, ErrorBounds};
 use crate::float::FloatExt;
 
 /// Returns x - [`machine
@@ -125,6 +129,289 @@ pub fn avoid_odd_mults<F: FloatExt>(x: F) -> impl Fn(&F) -> bool {
     }
 }
 
+/// Creates a closure for checking the identity f(x) * c = g(x) between two
+/// implementations, for example log2(x) * ln(2) = ln(x). It can be passed as
+/// `compute` argument to [`Domain`] methods, where the left side is treated as
+/// the computed value and the right side as the real one. Since both sides
+/// usually share most of the computation, the bounds should be tight, for
+/// example a couple of units in the last place.
+///
+/// ```
+/// use nikisas_test::prelude::*;
+/// use nikisas_test::utils::{scaled_identity, shift_right};
+///
+/// fn log2(x: f32) -> f32 {
+///     // your implementation
+///     # x.log2()
+/// }
+///
+/// UniformSample::with_count(shift_right(0.0), 1e+30, 10000).assert(
+///     ErrorBounds::new().ulp(2),
+///     scaled_identity(log2, core::f32::consts::LN_2, |x: f32| x.ln()),
+/// );
+/// ```
+///
+/// [`Domain`]: ../domain/trait.Domain.html
+pub fn scaled_identity<F, A, B>(f: A, c: F, g: B) -> impl Fn(F) -> (F, F)
+where
+    F: FloatExt,
+    A: Fn(F) -> F,
+    B: Fn(F) -> F,
+{
+    move |x| (f(x) * c, g(x))
+}
+
+/// Creates a closure for checking that g is the inverse of f, that is, g(f(x))
+/// = x. It can be passed as `compute` argument to [`Domain`] methods, where
+/// g(f(x)) is treated as the computed value and x as the real one. The errors
+/// of both functions contribute to the result, so a mismatch reveals an
+/// inconsistency between the forward and inverse approximations.
+///
+/// ```
+/// use nikisas_test::prelude::*;
+/// use nikisas_test::utils::roundtrip;
+///
+/// fn exp(x: f32) -> f32 {
+///     // your implementation
+///     # x.exp()
+/// }
+///
+/// UniformSample::with_count(-87.0, 88.0, 10000).assert(
+///     ErrorBounds::new().rel(1e-3).abs(1e-4),
+///     roundtrip(exp, |y: f32| y.ln()),
+/// );
+/// ```
+///
+/// [`Domain`]: ../domain/trait.Domain.html
+pub fn roundtrip<F, A, B>(f: A, g: B) -> impl Fn(F) -> (F, F)
+where
+    F: FloatExt,
+    A: Fn(F) -> F,
+    B: Fn(F) -> F,
+{
+    move |x| (g(f(x)), x)
+}
+
+/// Asserts the bounds on all machine numbers in the neighborhoods of given
+/// extrema (or other points of interest, like roots), which random sampling
+/// most likely misses. Each neighborhood spans `radius_floats` machine numbers
+/// to both sides of the extremum.
+///
+/// ```
+/// use nikisas_test::prelude::*;
+/// use nikisas_test::utils::sweep_extrema;
+///
+/// fn ln(x: f32) -> f32 {
+///     // your implementation
+///     # x.ln()
+/// }
+///
+/// // The root of logarithm.
+/// sweep_extrema(|x| (ln(x), x.ln()), &[1.0], 1000, ErrorBounds::new().abs(1e-6));
+/// ```
+pub fn sweep_extrema<F, T>(compute: T, extrema: &[F], radius_floats: u64, bounds: ErrorBounds<F>)
+where
+    F: FloatExt,
+    T: Fn(F) -> (F, F),
+{
+    let radius = radius_floats as i64;
+    let mut error = Error::with_bounds(bounds);
+
+    for &extremum in extrema {
+        let ordinal = extremum.to_ordinal();
+        let low = F::from_ordinal(ordinal - radius);
+        let high = F::from_ordinal(ordinal + radius);
+
+        for x in Exhaustive::bounded(low, high) {
+            let (computed, real) = compute(x);
+            error.calculate(x, computed, real);
+        }
+    }
+
+    error.assert();
+}
+
+/// Guards the integrity of polynomial coefficients, typically transcribed from
+/// the output of a tool like Sollya. The sign of each coefficient is flipped
+/// one at a time and the maximum relative error of `eval` with respect to
+/// `reference` on the interval [low, high] must grow at least `factor` times.
+/// An accidentally flipped sign would make the shipped coefficients fail this
+/// check, because flipping it back would improve the error instead.
+///
+/// The `eval` closure is given the (perturbed) coefficients and the argument.
+/// For the check to be meaningful, the error of the shipped coefficients should
+/// be dominated by the approximation, not by the rounding. Therefore, it is
+/// often better to evaluate the polynomial in higher precision.
+///
+/// ```
+/// use nikisas_test::utils::assert_coefficient_signs;
+///
+/// // Taylor polynomial of exp(x) = 1 + x + x^2 * P(x).
+/// let coeffs = [1.0 / 2.0, 1.0 / 6.0, 1.0 / 24.0, 1.0 / 120.0];
+/// let eval = |c: &[f64], x: f64| 1.0 + x + x * x * (c[0] + x * (c[1] + x * (c[2] + x * c[3])));
+///
+/// assert_coefficient_signs(&coeffs, eval, |x| x.exp(), -0.35, 0.35, 10.0);
+/// ```
+pub fn assert_coefficient_signs<F, P, R>(
+    coeffs: &[F],
+    eval: P,
+    reference: R,
+    low: F,
+    high: F,
+    factor: F,
+) where
+    F: FloatExt,
+    P: Fn(&[F], F) -> F,
+    R: Fn(F) -> F,
+{
+    let max_rel = |coeffs: &[F]| {
+        UniformSample::with_count(low, high, 10000)
+            .error(|x| (eval(coeffs, x), reference(x)))
+            .max_rel()
+    };
+
+    let shipped = max_rel(coeffs);
+    let mut perturbed = coeffs.to_vec();
+
+    for i in 0..coeffs.len() {
+        perturbed[i] = F::zero() - coeffs[i];
+        let flipped = max_rel(&perturbed);
+        perturbed[i] = coeffs[i];
+
+        if flipped < factor * shipped {
+            panic!(
+                "flipping the sign of coefficient {} ({:?}) changes the relative error only from {:?} to {:?}",
+                i, coeffs[i], shipped, flipped
+            );
+        }
+    }
+}
+
+/// Comparison of a numerically stable implementation with the naive one,
+/// decade by decade (see [`cancellation_gain`]).
+///
+/// [`cancellation_gain`]: fn.cancellation_gain.html
+#[derive(Debug, Clone)]
+pub struct CancellationGain<F> {
+    decades: Vec<(i32, F, F)>,
+}
+
+impl<F: FloatExt> CancellationGain<F> {
+    /// Returns the decades of the inputs, that is, the integral parts of
+    /// log10(|x|), together with the maximum relative errors of the stable and
+    /// the naive implementation, respectively, sorted by the decade.
+    pub fn decades(&self) -> &[(i32, F, F)] {
+        &self.decades
+    }
+
+    /// Returns the maximum ratio of the naive error to the stable error over
+    /// all decades. The stable error is floored at half of the machine
+    /// epsilon, which is the error of a correctly rounded result, so that the
+    /// ratio is finite.
+    pub fn max_gain(&self) -> F {
+        self.decades
+            .iter()
+            .map(|&(_, stable, naive)| gain(stable, naive))
+            .fold(F::zero(), |max, gain| if gain > max { gain } else { max })
+    }
+
+    /// Returns the lowest decade from which the naive error is at most
+    /// `factor` times the stable error in all higher decades. Below this
+    /// crossover, the stable implementation is needed. If the naive
+    /// implementation is worse even in the highest decade, `None` is returned.
+    pub fn crossover(&self, factor: F) -> Option<i32> {
+        let mut crossover = None;
+
+        for &(decade, stable, naive) in self.decades.iter().rev() {
+            if gain(stable, naive) > factor {
+                break;
+            }
+            crossover = Some(decade);
+        }
+
+        crossover
+    }
+}
+
+impl<F: FloatExt> fmt::Display for CancellationGain<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "decade  stable  naive  gain")?;
+        for &(decade, stable, naive) in self.decades.iter() {
+            writeln!(
+                f,
+                "1e{}  {:?}  {:?}  {:?}",
+                decade,
+                stable,
+                naive,
+                gain(stable, naive)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn gain<F: FloatExt>(stable: F, naive: F) -> F {
+    let floor = F::eps() / (F::one() + F::one());
+    naive / if stable > floor { stable } else { floor }
+}
+
+/// Quantifies the value of an implementation which avoids catastrophic
+/// cancellation, like exp(x) - 1 computed directly instead of by subtracting
+/// one from exp(x). Both `stable` and `naive` implementations are evaluated on
+/// the same inputs sampled in logarithmic scale from the interval [low, high]
+/// and their maximum relative errors with respect to `real` are compared for
+/// each decade of the inputs. The result shows where the cancellation occurs
+/// and how much the stable implementation improves the error there.
+///
+/// ```
+/// use nikisas_test::utils::cancellation_gain;
+///
+/// let gain = cancellation_gain(
+///     |x: f32| x.exp_m1(),
+///     |x: f32| x.exp() - 1.0,
+///     |x: f32| (x as f64).exp_m1() as f32,
+///     1e-6,
+///     10.0,
+///     10000,
+/// );
+///
+/// assert!(gain.max_gain() > 1000.0);
+/// assert!(gain.crossover(10.0).unwrap() <= 0);
+/// ```
+pub fn cancellation_gain<F, S, N, R>(
+    stable: S,
+    naive: N,
+    real: R,
+    low: F,
+    high: F,
+    count: usize,
+) -> CancellationGain<F>
+where
+    F: FloatExt,
+    S: Fn(F) -> F,
+    N: Fn(F) -> F,
+    R: Fn(F) -> F,
+{
+    // The seed is the same, so both samples contain the same inputs.
+    let sample = || {
+        UniformSample::builder(low, high)
+            .count(count)
+            .log_scale()
+            .build()
+    };
+
+    let stable = sample().error_by_decade(|x| (stable(x), real(x)));
+    let naive = sample().error_by_decade(|x| (naive(x), real(x)));
+
+    let decades = stable
+        .into_iter()
+        .zip(naive)
+        .map(|((decade, stable), (_, naive))| (decade, stable, naive))
+        .collect();
+
+    CancellationGain { decades }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -165,4 +452,60 @@ mod tests {
         assert_eq!(super::avoid_odd_mults(2.0)(&14.0), false);
         assert_eq!(super::avoid_odd_mults(2.0)(&15.0), true);
     }
+
+    // Taylor polynomial of sin(x) = x + x^3 * P(x^2).
+    fn sin_taylor(c: &[f64], x: f64) -> f64 {
+        let x2 = x * x;
+        x + x * x2 * (c[0] + x2 * (c[1] + x2 * c[2]))
+    }
+
+    #[test]
+    fn coefficient_signs() {
+        let coeffs = [-1.0 / 6.0, 1.0 / 120.0, -1.0 / 5040.0];
+        super::assert_coefficient_signs(&coeffs, sin_taylor, |x| x.sin(), -0.8, 0.8, 10.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "coefficient 2")]
+    fn coefficient_signs_flipped() {
+        // The sign of the last coefficient is wrong, but the error is still
+        // small in the most of the interval.
+        let coeffs = [-1.0 / 6.0, 1.0 / 120.0, 1.0 / 5040.0];
+        super::assert_coefficient_signs(&coeffs, sin_taylor, |x| x.sin(), -0.8, 0.8, 10.0);
+    }
+
+    #[test]
+    fn cancellation_gain() {
+        let gain = super::cancellation_gain(
+            |x: f32| x.exp_m1(),
+            |x: f32| x.exp() - 1.0,
+            |x: f32| (x as f64).exp_m1() as f32,
+            1e-6,
+            100.0,
+            10000,
+        );
+
+        let decades = gain.decades().iter().map(|d| d.0).collect::<Vec<_>>();
+        assert_eq!(decades, vec![-6, -5, -4, -3, -2, -1, 0, 1]);
+
+        // The cancellation is the worst for the smallest inputs.
+        assert!(gain.decades()[0].2 > 1e-2);
+        assert!(gain.decades()[0].1 < 1e-6);
+        assert!(gain.max_gain() > 1e+4);
+
+        let crossover = gain.crossover(10.0).unwrap();
+        assert!((-2..=0).contains(&crossover), "{}", gain);
+
+        // Without cancellation, there is no gain at all.
+        let gain = super::cancellation_gain(
+            |x: f32| x.exp(),
+            |x: f32| x.exp(),
+            |x: f32| (x as f64).exp() as f32,
+            1e-6,
+            100.0,
+            10000,
+        );
+        assert!(gain.max_gain() <= 1.0);
+        assert_eq!(gain.crossover(1.0), Some(-6));
+    }
 }
@@ -0,0 +1,86 @@
+# Coefficients found by interpolation at Chebyshev nodes, which is close to the
+# minimax polynomial. Used for the tables for which there is no Sollya program.
+# Requires only Python 3 standard library:
+#
+#   python3 chebyshev.py
+
+import struct
+from decimal import Decimal, getcontext
+
+getcontext().prec = 60
+
+PI = Decimal("3.14159265358979323846264338327950288419716939937510582097494")
+LN_2 = Decimal(2).ln()
+
+
+def cos(x):
+    # Taylor series, the arguments are in [0, π].
+    term, total, n = Decimal(1), Decimal(1), 0
+    while abs(term) > Decimal(10) ** -55:
+        n += 2
+        term = -term * x * x / (n * (n - 1))
+        total += term
+    return total
+
+
+def nodes(a, b, n):
+    return [
+        (a + b) / 2 + (b - a) / 2 * cos(PI * (2 * i + 1) / (2 * n)) for i in range(n)
+    ]
+
+
+def interpolate(f, a, b, n):
+    # Solves the Vandermonde system for the monomial coefficients.
+    xs = nodes(a, b, n)
+    m = [[x ** j for j in range(n)] + [f(x)] for x in xs]
+
+    for i in range(n):
+        p = max(range(i, n), key=lambda k: abs(m[k][i]))
+        m[i], m[p] = m[p], m[i]
+        for k in range(i + 1, n):
+            c = m[k][i] / m[i][i]
+            for j in range(i, n + 1):
+                m[k][j] -= c * m[i][j]
+
+    c = [Decimal(0)] * n
+    for i in reversed(range(n)):
+        c[i] = (m[i][n] - sum(m[i][j] * c[j] for j in range(i + 1, n))) / m[i][i]
+    return c
+
+
+def single(x):
+    return "0x%08x" % struct.unpack("<I", struct.pack("<f", float(x)))[0]
+
+
+def double(x):
+    return "0x%016x" % struct.unpack("<Q", struct.pack("<d", float(x)))[0]
+
+
+# exp(z) ≈ 1 + z + 1/2 * z^2 + z^3 * P(z) for |z| ≤ ln(2) / 2.
+def exp_rest(z):
+    return (z.exp() - 1 - z - z * z / 2) / z ** 3
+
+
+P = interpolate(exp_rest, -LN_2 / 2, LN_2 / 2, 10)
+print("P_EXP_F64 = ")
+print(", ".join(double(c) for c in P))
+
+# ln(y) = 2 * atanh(s) ≈ 2 * s + s^3 * P(s^2) for |s| ≤ (sqrt(2) - 1) / (sqrt(2) + 1).
+def atanh_rest(t):
+    s = t.sqrt()
+    return (((1 + s) / (1 - s)).ln() - 2 * s) / (s * t)
+
+
+S = (Decimal(2).sqrt() - 1) / (Decimal(2).sqrt() + 1)
+P = interpolate(atanh_rest, Decimal(0), S * S, 7)
+print("P_LN_F64 = ")
+print(", ".join(double(c) for c in P))
+
+# 2^y ≈ 1 + y * P(y) for y in [-0.5, 0].
+def pow2_rest(y):
+    return ((y * LN_2).exp() - 1) / y
+
+
+P = interpolate(pow2_rest, Decimal("-0.5"), Decimal(0), 5)
+print("P_POW2_NEG = ")
+print(", ".join(single(c) for c in P))
@@ -0,0 +1,186 @@
+//! Measured worst-case errors of the implemented functions.
+//!
+//! The values are the maximum relative errors with respect to the standard
+//! library and they match the table in the crate documentation. They are
+//! obtained by evaluating the functions for all single precision numbers in
+//! their domains, so they are guaranteed. The test suite checks that the errors
+//! do not exceed them in the primary ranges, where the errors of logarithms are
+//! the largest, and on random samples of the entire domains.
+//!
+//! The values for trigonometric functions do not hold with `f32-only` feature.
+
+use crate::float::F;
+
+/// Maximum relative error of [`cos`](../fn.cos.html), measured exhaustively.
+pub const COS_MAX_REL: F = 1.25e-7;
+
+/// Maximum relative error of [`cot`](../fn.cot.html), measured exhaustively.
+pub const COT_MAX_REL: F = 8.06e-7;
+
+/// Maximum relative error of [`exp`](../fn.exp.html), measured exhaustively.
+pub const EXP_MAX_REL: F = 4.19e-6;
+
+/// Maximum relative error of [`ln`](../fn.ln.html), measured exhaustively.
+pub const LN_MAX_REL: F = 2.32e-6;
+
+/// Maximum relative error of [`log2`](../fn.log2.html), measured exhaustively.
+pub const LOG2_MAX_REL: F = 2.34e-6;
+
+/// Maximum relative error of [`log10`](../fn.log10.html), measured
+/// exhaustively.
+pub const LOG10_MAX_REL: F = 2.36e-6;
+
+/// Maximum relative error of [`pow2`](../fn.pow2.html), measured exhaustively.
+pub const POW2_MAX_REL: F = 1.20e-7;
+
+/// Maximum relative error of [`pow10`](../fn.pow10.html), measured
+/// exhaustively.
+pub const POW10_MAX_REL: F = 4.55e-6;
+
+/// Maximum relative error of [`sin`](../fn.sin.html), measured exhaustively.
+pub const SIN_MAX_REL: F = 1.25e-7;
+
+/// Maximum relative error of [`tan`](../fn.tan.html), measured exhaustively.
+pub const TAN_MAX_REL: F = 7.32e-7;
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::{FRAC_1_SQRT_2, SQRT_2};
+    use nikisas_test::prelude::*;
+
+    // The domain of logarithms sampled uniformly in the logarithmic scale.
+    // Uniform sampling of such a wide interval would almost never hit the
+    // primary range, where the error is the largest.
+    fn log_domain() -> UniformSample<f32> {
+        UniformSample::builder(1e-45, 3.4e+38)
+            .count(10000)
+            .log_scale()
+            .build()
+    }
+
+    #[test]
+    #[cfg(not(feature = "f32-only"))]
+    fn cos() {
+        use nikisas_test::float::FloatExt;
+
+        let error =
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).error(|x| (crate::cos(x), x.cos()));
+        assert!(error.max_rel() <= super::COS_MAX_REL);
+
+        // The bound holds also near the zeros of the function.
+        let error = (-1000..1000)
+            .map(|k| (k as f32 + 0.5) * core::f32::consts::PI)
+            .flat_map(|x| Exhaustive::bounded(x.nextdown(), x.nextup()))
+            .error(|x| (crate::cos(x), x.cos()));
+        assert!(error.max_rel() <= super::COS_MAX_REL);
+    }
+
+    #[test]
+    #[cfg(not(feature = "f32-only"))]
+    fn cot() {
+        let error = UniformSample::with_count(-2.1e+9, 2.1e+9, 10000)
+            .error(|x| (crate::cot(x), 1.0 / x.tan()));
+        assert!(error.max_rel() <= super::COT_MAX_REL);
+    }
+
+    #[test]
+    fn exp() {
+        let error = UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+            .error(|x| (crate::exp(x), x.exp()));
+        assert!(error.max_rel() <= super::EXP_MAX_REL);
+
+        let error =
+            UniformSample::with_count(-87.3, 88.7, 10000).error(|x| (crate::exp(x), x.exp()));
+        assert!(error.max_rel() <= super::EXP_MAX_REL);
+    }
+
+    #[test]
+    fn ln() {
+        // The primary range is small enough to be tested exhaustively.
+        let error = Exhaustive::bounded(FRAC_1_SQRT_2, SQRT_2).error(|x| (crate::ln(x), x.ln()));
+        assert!(error.max_rel() <= super::LN_MAX_REL);
+
+        let error = log_domain().error(|x| (crate::ln(x), x.ln()));
+        assert!(error.max_rel() <= super::LN_MAX_REL);
+    }
+
+    #[test]
+    fn log2() {
+        let error =
+            Exhaustive::bounded(FRAC_1_SQRT_2, SQRT_2).error(|x| (crate::log2(x), x.log2()));
+        assert!(error.max_rel() <= super::LOG2_MAX_REL);
+
+        let error = log_domain().error(|x| (crate::log2(x), x.log2()));
+        assert!(error.max_rel() <= super::LOG2_MAX_REL);
+    }
+
+    #[test]
+    fn log10() {
+        let error =
+            Exhaustive::bounded(FRAC_1_SQRT_2, SQRT_2).error(|x| (crate::log10(x), x.log10()));
+        assert!(error.max_rel() <= super::LOG10_MAX_REL);
+
+        let error = log_domain().error(|x| (crate::log10(x), x.log10()));
+        assert!(error.max_rel() <= super::LOG10_MAX_REL);
+    }
+
+    #[test]
+    fn pow2() {
+        let error =
+            UniformSample::with_count(-0.5, 0.5, 100000).error(|x| (crate::pow2(x), x.exp2()));
+        assert!(error.max_rel() <= super::POW2_MAX_REL);
+
+        let error =
+            UniformSample::with_count(-126.0, 127.9, 10000).error(|x| (crate::pow2(x), x.exp2()));
+        assert!(error.max_rel() <= super::POW2_MAX_REL);
+    }
+
+    #[test]
+    fn pow10() {
+        let error = UniformSample::with_count(-0.5, 0.5, 100000)
+            .error(|x| (crate::pow10(x), 10.0f32.powf(x)));
+        assert!(error.max_rel() <= super::POW10_MAX_REL);
+
+        let error = UniformSample::with_count(-37.9, 38.5, 10000)
+            .error(|x| (crate::pow10(x), 10.0f32.powf(x)));
+        assert!(error.max_rel() <= super::POW10_MAX_REL);
+    }
+
+    #[test]
+    #[cfg(not(feature = "f32-only"))]
+    fn sin() {
+        let error =
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).error(|x| (crate::sin(x), x.sin()));
+        assert!(error.max_rel() <= super::SIN_MAX_REL);
+
+        // The bound holds also for tiny inputs, including subnormal numbers.
+        let error =
+            BitUniformSample::with_count(1e-45, 1e-6, 10000).error(|x| (crate::sin(x), x.sin()));
+        assert!(error.max_rel() <= super::SIN_MAX_REL);
+    }
+
+    #[test]
+    #[cfg(not(feature = "f32-only"))]
+    fn tan() {
+        use nikisas_test::float::FloatExt;
+
+        let error = UniformSample::with_count(
+            -core::f32::consts::FRAC_PI_4,
+            core::f32::consts::FRAC_PI_4,
+            100000,
+        )
+        .error(|x| (crate::tan(x), x.tan()));
+        assert!(error.max_rel() <= super::TAN_MAX_REL);
+
+        let error =
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).error(|x| (crate::tan(x), x.tan()));
+        assert!(error.max_rel() <= super::TAN_MAX_REL);
+
+        // The bound holds also near the asymptotes of the function.
+        let error = (-1000..1000)
+            .map(|k| (k as f32 + 0.5) * core::f32::consts::PI)
+            .flat_map(|x| Exhaustive::bounded(x.nextdown(), x.nextup()))
+            .error(|x| (crate::tan(x), x.tan()));
+        assert!(error.max_rel() <= super::TAN_MAX_REL);
+    }
+}
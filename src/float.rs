@@ -14,14 +14,36 @@ pub const EXP_MAX: I = 255;
 /// Right offset of exponent value in single-precision floating point number.
 pub const MANTISSA_BITS: U = 23;
 
+/// Mask for the stored mantissa bits in single-precision floating point number.
+pub const MANTISSA_MASK: U = (1 << MANTISSA_BITS) - 1;
+
 /// Sign mask in single-precision floating point number.
 pub const SIGN_MASK: U = 0x80000000;
 
+/// Mask for exponent value in double-precision floating point number.
+#[cfg(not(feature = "bare-metal"))]
+pub const EXP_MASK_F64: u64 = 0x7ff0000000000000;
+
+/// Exponent bias in double-precision floating point number.
+#[cfg(not(feature = "bare-metal"))]
+pub const EXP_BIAS_F64: I = 1023;
+
+/// Maximum exponent value in double-precision floating point number.
+#[cfg(not(feature = "bare-metal"))]
+pub const EXP_MAX_F64: I = 2047;
+
+/// Right offset of exponent value in double-precision floating point number.
+#[cfg(not(feature = "bare-metal"))]
+pub const MANTISSA_BITS_F64: u64 = 52;
+
 /// Constant 2^52 + 2^51 for being used in `round` function.
 pub const ROUND_ADD: f64 = 6755399441055744.0;
 
 /// Mask for getting lower 32 bits from double-precision floating point number.
 pub const ROUND_MASK: u64 = 0xffffffff;
 
+/// Constant 2^23 for being used in `round` function without double precision.
+pub const ROUND_ADD_F32: F = 8388608.0;
+
 /// Equality check tolerance, equal to MACHINE_EPSILON.
 pub const EPSILON: F = 1.19209290e-07;
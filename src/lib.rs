@@ -23,12 +23,29 @@
 //! Not much. This is (at least for now) for educational purposes. Here is the
 //! list:
 //!
-//! * exponentiation - `exp(x)`, `pow(x, p)`, `pow2(p)`, `pow10(p)`
-//! * logarithms - `ln(x)`, `log2(x)`, `log10(x)`
-//! * trigonometric functions - `sin(x)`, `cos(x)`, `tan(x)`, `cot(x)`
-//!
-//! Note that implementation of trigonometric functions give poor results for
-//! some inputs (and therefore they fail our current tests).
+//! * exponentiation - `exp(x)`, `exp_neg(x)`, `exp_mixed(x)`, `expm1(x)`,
+//!   `exp_f64(x)`, `exp_dd(x)`, `pow(x, p)`, `powu(x, n)`, `pow2(p)`,
+//!   `pow10(p)`
+//! * logarithms - `ln(x)`, `ln_f64(x)`, `ln_dd(x)`, `log2(x)`, `log10(x)`
+//! * roots - `sqrt(x)`, `sqrt_signed(x)`, `recip_sqrt(x)`,
+//!   `sqrt_slice(input, output)`, `recip_sqrt_slice(input, output)`,
+//!   `hypot(x, y)`
+//! * trigonometric functions - `sin(x)`, `cos(x)`, `sincos(x)`, `tan(x)`,
+//!   `cot(x)`, `sinpi(x)`, `cospi(x)`, `tanpi(x)`
+//! * inverse trigonometric functions - `atan(x)`, `atan2(y, x)`
+//! * 2D vectors - `rotate(x, y, angle)`, `angle_between(u, v)`,
+//!   `to_polar(x, y)`, `from_polar(r, theta)`
+//! * other - `remainder(x, y)`, `ilogb(x)`, `mantissa(x)`, `exponent(x)`,
+//!   `from_parts(sign, exponent, mantissa)`
+//!
+//! The trigonometric functions reduce the argument in double precision and are
+//! accurate on their entire domain, unless `f32-only` feature is enabled (see
+//! [`sin`](fn.sin.html) for details).
+//!
+//! The functions `exp` and `ln` are `const fn`, so they can be used to compute
+//! constants and tables at compile time. Floating point arithmetic in `const
+//! fn` was stabilized in Rust 1.83, which is therefore the minimum supported
+//! Rust version of the crate.
 //!
 //! # Errors
 //!
@@ -37,20 +54,72 @@
 //! interval. The ground truth for error computation are the implementations of
 //! the corresponding functions in the Rust's standard library.
 //!
-//! The table of real errors is here:
+//! The table of real errors is here (the maximum relative errors are also
+//! available programmatically in [`errors`](errors/index.html) module):
 //!
 //! | function | maximum relative | root mean square (overall quality) |
 //! | -------- | ---------------- | ---------------------------------- |
-//! | cos      | N/A              | N/A                                |
-//! | cot      | N/A              | N/A                                |
-//! | exp      | 4.15e-6          | 1.39e-6                            |
-//! | ln       | 9.60e-8          | 4.05e-8                            |
-//! | log2     | 1.29e-7          | 4.08e-8                            |
-//! | log10    | 2.02e-7          | 6.24e-8                            |
-//! | pow2     | 1.19e-7          | 3.53e-8                            |
-//! | pow10    | 4.47e-6          | 1.49e-6                            |
-//! | sin      | N/A              | N/A                                |
-//! | tan      | N/A              | N/A                                |
+//! | cos      | 1.25e-7          | 3.44e-8                            |
+//! | cot      | 8.06e-7          | 2.06e-7                            |
+//! | exp      | 4.19e-6          | 1.39e-6                            |
+//! | ln       | 2.32e-6          | 7.98e-8                            |
+//! | log2     | 2.34e-6          | 8.63e-8                            |
+//! | log10    | 2.36e-6          | 9.18e-8                            |
+//! | pow2     | 1.20e-7          | 2.59e-8                            |
+//! | pow10    | 4.55e-6          | 1.51e-6                            |
+//! | sin      | 1.25e-7          | 3.55e-8                            |
+//! | tan      | 7.32e-7          | 2.04e-7                            |
+//!
+//! The maximum relative errors are measured for all single precision numbers in
+//! the domains of the functions, the root mean square errors come from random
+//! sampling.
+//!
+//! The errors of all functions, both in their primary ranges and on their
+//! entire domains, can be computed by running
+//!
+//! ```plain
+//! cargo run --release --example quality_report
+//! ```
+//!
+//! # Features
+//!
+//! * `f32-only` - Rounding in argument reductions uses double precision
+//!   arithmetic by default, which is fast on most platforms. This feature
+//!   replaces it by single precision only implementation for targets without
+//!   efficient support of f64. The results are the same, except for `sin`,
+//!   `cos`, `sincos`, `tan` and `cot`, whose argument reduction is then less
+//!   precise for large inputs.
+//! * `table-ln` - Uses a small table of precomputed logarithms in
+//!   [`ln`](fn.ln.html) (and so in `log2` and `log10` too), which improves the
+//!   accuracy at the cost of 104 bytes of memory.
+//! * `bare-metal` - Restricts the API to the functions that use only single
+//!   precision arithmetic and implies `f32-only` (see [Bare-metal
+//!   targets](#bare-metal-targets)).
+//! * `cross-check` - Used only in the tests. Compares the accuracy of `exp`
+//!   and `ln` with the micromath crate on the same inputs and checks that
+//!   nikisas is not worse.
+//!
+//! # Bare-metal targets
+//!
+//! The functions are implemented using the basic arithmetic operations,
+//! comparisons and bit manipulations of floating point numbers that are
+//! available in `core`. They do not call `sqrt`, `round` or other operations
+//! that would need an FPU instruction or a libm. The rounding, for example, is
+//! done by adding and subtracting a large constant.
+//!
+//! By default, some single precision functions use double precision arithmetic
+//! internally for rounding and argument reduction, and the functions
+//! `exp_f64`, `ln_f64` and `exp_mixed` use double precision by their nature.
+//! On targets without a double precision FPU, it is emulated in software by
+//! the compiler, which is correct, but slow. The `bare-metal` feature
+//! restricts the API to the functions that use only single precision
+//! arithmetic. It enables `f32-only` and removes `exp_f64`, `ln_f64` and
+//! `exp_mixed`, so using any of them is a compile error. The restricted subset
+//! is built and tested by
+//!
+//! ```plain
+//! cargo test --features bare-metal
+//! ```
 //!
 //! # Name
 //!
@@ -74,10 +143,12 @@
 #![warn(missing_docs)]
 
 pub mod consts;
+pub mod errors;
 mod float;
 mod math;
 #[cfg(test)]
 mod test;
+pub mod tolerances;
 mod utils;
 
 pub use math::*;
@@ -0,0 +1,104 @@
+use super::data::{PI_HALF, PI_QUARTER, POLY_ATAN, TAN_PI_EIGHTH};
+use crate::float::F;
+use crate::utils::{abs_sgn, f, poly};
+
+/// Computes the arctangent of a number. The result is in radians in the
+/// interval [-π/2, π/2].
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{atan, consts::PI};
+/// assert_eq!(atan(1.0), 0.25 * PI);
+/// ```
+///
+/// # Implementation details
+///
+/// Since the arctangent is an odd function, we compute it for |x| and apply the
+/// sign of x to the result. If |x| > 1, the input is inverted using the
+/// identity
+///
+/// ```plain
+///   atan(x) = π/2 - atan(1 / x), for x > 0
+/// ```
+///
+/// and so we get y in [0, 1]. The inverted branch joins the direct one at
+/// |x| = 1 without a jump and the error does not spike in the neighborhood, so
+/// no finer reduction is needed there. If y > tan(π/8), it is further reduced
+/// using
+///
+/// ```plain
+///   atan(y) = π/4 + atan((y - 1) / (y + 1))
+/// ```
+///
+/// and we get z with |z| ≤ tan(π/8) ≈ 0.414. The approximation of atan(z) is
+/// done using polynomial in the form:
+///
+/// ```plain
+///   atan(z) ≈ z + z^3 * P(z^2)
+/// ```
+///
+/// The "prefix" corresponds to coefficients of low-degree Taylor polynomial of
+/// atan(z) for z = 0 and P is found using special minimax algorithm in Sollya.
+/// Infinite inputs are inverted to zero, so the results are exactly ±π/2.
+pub fn atan(x: F) -> F {
+    let (x, sgn) = abs_sgn(x);
+
+    let (y, inv) = if x > 1.0 { (1.0 / x, true) } else { (x, false) };
+
+    let (z, shift) = if y > f(TAN_PI_EIGHTH) {
+        ((y - 1.0) / (y + 1.0), f(PI_QUARTER))
+    } else {
+        (y, 0.0)
+    };
+
+    let z2 = z * z;
+    let atany = shift + (z + z2 * z * poly(z2, POLY_ATAN));
+    let atanx = if inv { f(PI_HALF) - atany } else { atany };
+
+    sgn * atanx
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::float::FloatExt;
+    use nikisas_test::prelude::*;
+    use nikisas_test::utils::sweep_extrema;
+
+    #[test]
+    fn atan() {
+        assert_eq!(super::atan(0.0), 0.0);
+        assert_eq!(super::atan(1.0), core::f32::consts::FRAC_PI_4);
+        assert_eq!(super::atan(-1.0), -core::f32::consts::FRAC_PI_4);
+        assert_eq!(super::atan(f32::INFINITY), core::f32::consts::FRAC_PI_2);
+        assert_eq!(
+            super::atan(f32::NEG_INFINITY),
+            -core::f32::consts::FRAC_PI_2
+        );
+
+        UniformSample::with_count(-1.0, 1.0, 100000)
+            .assert(error_bounds(), |x| (super::atan(x), x.atan()));
+
+        UniformSample::with_count(-3.4e+38, 3.4e+38, 10000)
+            .assert(error_bounds(), |x| (super::atan(x), x.atan()));
+    }
+
+    #[test]
+    fn branch_boundary() {
+        // The reference is computed in double precision and then rounded.
+        let atan = |x: f32| (super::atan(x), (x as f64).atan() as f32);
+
+        // Both branches of the inversion must agree at |x| = 1, the error must
+        // not spike at the boundary.
+        sweep_extrema(atan, &[-1.0, 1.0], 100000, ErrorBounds::new().ulp(2));
+
+        // The function must be monotonic across the boundary.
+        let mut previous = super::atan(0.99f32);
+        for x in Exhaustive::bounded(0.99f32.nextup(), 1.01) {
+            let y = super::atan(x);
+            assert!(y >= previous, "{}", x);
+            previous = y;
+        }
+    }
+}
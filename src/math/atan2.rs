@@ -0,0 +1,81 @@
+use super::atan::atan;
+use super::data::PI_HALF;
+use crate::float::F;
+use crate::utils::{abs_sgn, f};
+
+/// Computes the four-quadrant arctangent of y / x, that is, the angle between
+/// the positive x-axis and the point (x, y). The result is in radians in the
+/// interval [-π, π].
+///
+/// # Notes
+///
+/// If both x and y are zero, the result is ±0 for positive x and ±π for
+/// negative x (the sign being the sign of y). If both x and y are infinite, the
+/// result is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{atan2, consts::PI};
+/// assert_eq!(atan2(1.0, -1.0), 0.75 * PI);
+/// ```
+///
+/// # Implementation details
+///
+/// The angle a = atan(|y| / |x|) in [0, π/2] is computed using [`atan`]
+/// routine. The quadrant is then determined by the signs of x and y:
+///
+/// ```plain
+///   atan2(y, x) = sgn(y) * a,         if x is positive
+///   atan2(y, x) = sgn(y) * (π - a),   if x is negative
+/// ```
+///
+/// The signs are read from the sign bits, so negative zeros are handled as
+/// negative numbers.
+///
+/// [`atan`]: fn.atan.html
+pub fn atan2(y: F, x: F) -> F {
+    let (y, y_sgn) = abs_sgn(y);
+    let (x, x_sgn) = abs_sgn(x);
+
+    let a = if y == 0.0 && x == 0.0 {
+        0.0
+    } else {
+        atan(y / x)
+    };
+
+    let a = if x_sgn < 0.0 { 2.0 * f(PI_HALF) - a } else { a };
+
+    y_sgn * a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn atan2() {
+        let pi = core::f32::consts::PI;
+
+        assert_eq!(super::atan2(0.0, 1.0), 0.0);
+        assert_eq!(super::atan2(1.0, 0.0), pi / 2.0);
+        assert_eq!(super::atan2(0.0, -1.0), pi);
+        assert_eq!(super::atan2(-0.0, -1.0), -pi);
+        assert_eq!(super::atan2(-1.0, 0.0), -pi / 2.0);
+        assert_eq!(super::atan2(0.0, 0.0), 0.0);
+        assert_eq!(super::atan2(0.0, -0.0), pi);
+        assert_eq!(super::atan2(1.0, f32::INFINITY), 0.0);
+        assert_eq!(super::atan2(1.0, f32::NEG_INFINITY), pi);
+        assert_eq!(super::atan2(f32::INFINITY, 1.0), pi / 2.0);
+
+        UniformSample::with_count(-100.0f32, 100.0, 500)
+            .fold(Error::with_bounds(error_bounds()), |error, y| {
+                UniformSample::with_count(-100.0f32, 100.0, 500).fold(error, |mut error, x| {
+                    error.calculate((y, x), super::atan2(y, x), y.atan2(x));
+                    error
+                })
+            })
+            .assert();
+    }
+}
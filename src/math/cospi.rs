@@ -0,0 +1,68 @@
+use super::sinpi::sinpi_shifted;
+use crate::float::F;
+use crate::utils::abs;
+
+/// Computes the cosine of a number multiplied by π, that is, cos(π * x).
+///
+/// # Notes
+///
+/// The input domain is all finite numbers, for infinities and NaN, NaN is
+/// returned. The results for integers and half-integers are exact.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::cospi;
+/// assert_eq!(cospi(1.0), -1.0);
+/// assert_eq!(cospi(0.5), 0.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The cosine is an even function, so it is computed for |x|. The identity
+///
+/// ```plain
+///   cos(π * x) = sin(π * x + π / 2)
+/// ```
+///
+/// is then used, but instead of adding 1/2 to x, which would be inexact, the
+/// quarter period is added to the integer k of the reduction in [`sinpi`].
+///
+/// [`sinpi`]: fn.sinpi.html
+#[inline]
+pub fn cospi(x: F) -> F {
+    sinpi_shifted(abs(x), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    fn cospi_f64(x: f32) -> f32 {
+        (core::f64::consts::PI * x as f64).cos() as f32
+    }
+
+    #[test]
+    fn cospi() {
+        for k in -1000..=1000 {
+            let x = k as f32;
+            let expected = if k % 2 == 0 { 1.0 } else { -1.0 };
+            assert_eq!(super::cospi(x), expected, "{}", x);
+            assert_eq!(super::cospi(x + 0.5), 0.0, "{}", x + 0.5);
+        }
+
+        assert_eq!(super::cospi(16777216.0), 1.0);
+        assert_eq!(super::cospi(-8388609.0), -1.0);
+        assert!(super::cospi(f32::INFINITY).is_nan());
+        assert!(super::cospi(f32::NEG_INFINITY).is_nan());
+        assert!(super::cospi(f32::NAN).is_nan());
+
+        UniformSample::with_count(-0.25, 0.25, 100000).assert(ErrorBounds::new().ulp(2), |x| {
+            (super::cospi(x), cospi_f64(x))
+        });
+
+        UniformSample::with_count(-1e+6, 1e+6, 100000)
+            .assert(error_bounds(), |x| (super::cospi(x), cospi_f64(x)));
+    }
+}
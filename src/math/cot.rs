@@ -28,7 +28,7 @@ pub fn cot(x: F) -> F {
 mod tests {
     use crate::test::error_bounds;
     use nikisas_test::prelude::*;
-    use nikisas_test::utils::{avoid_odd_mults, shift_left, shift_right};
+    use nikisas_test::utils::{avoid, avoid_odd_mults, shift_left, shift_right};
 
     #[test]
     fn cot() {
@@ -37,10 +37,20 @@ mod tests {
             shift_left(core::f32::consts::PI / 2.0),
             100000,
         )
-        .assert(error_bounds(), |x| (super::tan(x), x.tan()));
+        .filter(avoid(0.0))
+        .assert(error_bounds(), |x| (super::cot(x), 1.0 / x.tan()));
 
-        UniformSample::with_count(-2.1e+9, 2.1e+9, 10000)
-            .filter(avoid_odd_mults(core::f32::consts::PI / 2.0))
-            .assert(error_bounds(), |x| (super::tan(x), x.tan()));
+        // The argument reduction is imprecise for large inputs unless it is
+        // done in double precision.
+        let full_range = || {
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000)
+                .filter(avoid_odd_mults(core::f32::consts::PI / 2.0))
+        };
+
+        if cfg!(feature = "f32-only") {
+            full_range().assert_expected_fail(error_bounds(), |x| (super::cot(x), 1.0 / x.tan()));
+        } else {
+            full_range().assert(error_bounds(), |x| (super::cot(x), 1.0 / x.tan()));
+        }
     }
 }
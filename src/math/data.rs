@@ -1,19 +1,151 @@
 use crate::float::U;
 
 pub(crate) const E: U = 0x402df854;
+// The largest x such that exp(x) is finite and the smallest x such that exp(x)
+// is normal.
+pub(crate) const EXP_MAX_ARG: U = 0x42b17217;
+pub(crate) const EXP_MIN_ARG: U = 0xc2aeac4f;
 pub(crate) const LN_2: U = 0x3f317218;
 pub(crate) const LN_2_INV: U = 0x3fb8aa3b;
 pub(crate) const SQRT_2: U = 0x3fb504f3;
+pub(crate) const SQRT_10: U = 0x404a62c2;
 pub(crate) const LOG2_E: U = 0x3fb8aa3b;
 pub(crate) const LOG10_E: U = 0x3ede5bd9;
 pub(crate) const PI_HALF: U = 0x3fc90fdb;
 pub(crate) const PI_HALF_INV: U = 0x3f22f983;
+pub(crate) const PI_HALF_LO: U = 0xb33bbd2e;
 pub(crate) const PI_QUARTER: U = 0x3f490fdb;
+pub(crate) const TAN_PI_EIGHTH: U = 0x3ed413cd;
 
 pub(crate) const POLY_EXP: [U; 5] = [0x3e2aaa83, 0x3d2aaa70, 0x3c08c01f, 0x3ab6aaed, 0x39063f86];
+#[cfg(not(feature = "table-ln"))]
 pub(crate) const POLY_LN1P: [U; 5] = [0x3eaa95d3, 0xbe7f5a82, 0x3e51db4d, 0xbe3d687c, 0x3defc7b9];
 pub(crate) const POLY_POW2: [U; 5] = [0x3f31721a, 0x3e75fcfc, 0x3d637c2c, 0x3c1b5267, 0x3acf2bc8];
+pub(crate) const POLY_POW2_NEG: [U; 5] =
+    [0x3f317218, 0x3e75fdb8, 0x3d634a3d, 0x3c1c5734, 0x3a977222];
 pub(crate) const POLY_POW10: [U; 5] = [0x4013623b, 0x402929c4, 0x40069c52, 0x3f694226, 0x3f7749be];
 pub(crate) const POLY_SIN: [U; 5] = [0xbe2aaaa8, 0x3c0886a0, 0xb94e294d, 0xb477034f, 0x35ea3ca9];
 pub(crate) const POLY_COS: [U; 5] = [0xbf000000, 0x3d2aaaab, 0xbab60baa, 0x37d033fe, 0xb499e1e4];
 pub(crate) const POLY_TAN: [U; 5] = [0x3eaaaf56, 0x3e07e0db, 0x3d6d3401, 0x3c3750d4, 0x3cae109d];
+pub(crate) const POLY_ATAN: [U; 5] = [0xbeaaaaa0, 0x3e4cc748, 0xbe11c2e9, 0x3dd7c79a, 0xbd73a50d];
+
+// Powers of ten 10^k for k from -37 to 38, that is, all in the normal range,
+// rounded to single precision.
+#[rustfmt::skip]
+pub(crate) const POW10_TABLE: [U; 76] = [0x02081cea, 0x03aa2425, 0x0554ad2e, 0x0704ec3d, 0x08a6274c, 0x0a4fb11f, 0x0c01ceb3, 0x0da24260, 0x0f4ad2f8, 0x10fd87b6, 0x129e74d2, 0x14461206, 0x15f79688, 0x179abe15, 0x19416d9a, 0x1af1c901, 0x1c971da0, 0x1e3ce508, 0x1fec1e4a, 0x219392ef, 0x233877aa, 0x24e69595, 0x26901d7d, 0x283424dc, 0x29e12e13, 0x2b8cbccc, 0x2d2febff, 0x2edbe6ff, 0x3089705f, 0x322bcc77, 0x33d6bf95, 0x358637bd, 0x3727c5ac, 0x38d1b717, 0x3a83126f, 0x3c23d70a, 0x3dcccccd, 0x3f800000, 0x41200000, 0x42c80000, 0x447a0000, 0x461c4000, 0x47c35000, 0x49742400, 0x4b189680, 0x4cbebc20, 0x4e6e6b28, 0x501502f9, 0x51ba43b7, 0x5368d4a5, 0x551184e7, 0x56b5e621, 0x58635fa9, 0x5a0e1bca, 0x5bb1a2bc, 0x5d5e0b6b, 0x5f0ac723, 0x60ad78ec, 0x6258d727, 0x64078678, 0x65a96816, 0x6753c21c, 0x69045951, 0x6aa56fa6, 0x6c4ecb8f, 0x6e013f39, 0x6fa18f08, 0x7149f2ca, 0x72fc6f7c, 0x749dc5ae, 0x76453719, 0x77f684df, 0x799a130c, 0x7b4097ce, 0x7cf0bdc2, 0x7e967699];
+
+// Two-word ln(2) and coefficients of 2 * atanh(s) = 2 * s + s^3 * P(s^2) for
+// double-float implementations.
+pub(crate) const LN_2_HI: U = 0x3f317200;
+pub(crate) const LN_2_LO: U = 0x35bfbe8e;
+pub(crate) const POLY_ATANH: [U; 5] = [0x3f2aaaab, 0x3ecccccd, 0x3e924925, 0x3e638e39, 0x3e3a2e8c];
+
+// Double precision constants and coefficients for f64 implementations.
+#[cfg(not(feature = "bare-metal"))]
+pub(crate) const LN_2_HI_F64: u64 = 0x3fe62e42fee00000;
+#[cfg(not(feature = "bare-metal"))]
+pub(crate) const LN_2_LO_F64: u64 = 0x3dea39ef35793c76;
+pub(crate) const PI_HALF_HI_F64: u64 = 0x3ff921fb00000000;
+pub(crate) const PI_HALF_LO_F64: u64 = 0x3e95110b4611a626;
+pub(crate) const PI_HALF_INV_F64: u64 = 0x3fe45f306dc9c883;
+#[cfg(not(feature = "bare-metal"))]
+#[rustfmt::skip]
+pub(crate) const POLY_EXP_F64: [u64; 10] = [0x3fc5555555555556, 0x3fa5555555555555, 0x3f811111111109b5, 0x3f56c16c16c167e2, 0x3f2a01a01a7c2efe, 0x3efa01a01a47a591, 0x3ec71de0db2f6b19, 0x3e927e4e1f7222cb, 0x3e5af389ecfc4b9c, 0x3e21f66d948a47d2];
+#[cfg(not(feature = "bare-metal"))]
+#[rustfmt::skip]
+pub(crate) const POLY_LN_F64: [u64; 7] = [0x3fe5555555555558, 0x3fd99999999952e2, 0x3fd2492492df148d, 0x3fcc71c62e5800a1, 0x3fc7462b4ab2ef6b, 0x3fc39fe606542dde, 0x3fc2b584aae78a57];
+
+#[cfg(feature = "table-ln")]
+pub(crate) const POLY_LN1P_SHORT: [U; 3] = [0x3eaaaa9e, 0xbe80287f, 0x3e4d48cb];
+#[cfg(feature = "table-ln")]
+#[rustfmt::skip]
+pub(crate) const LN_TABLE: [U; 13] = [0xbebfd7d2, 0xbe934b11, 0xbe549f6a, 0xbe08bc74, 0xbd842cc6, 0x0, 0x3d785186, 0x3df1383b, 0x3e2ff984, 0x3e647fbe, 0x3e8b3ae5, 0x3ea30c5e, 0x3eb9cec0];
+#[cfg(feature = "table-ln")]
+#[rustfmt::skip]
+pub(crate) const LN_INV_TABLE: [U; 13] = [0x3fba2e8c, 0x3faaaaab, 0x3f9d89d9, 0x3f924925, 0x3f888889, 0x3f800000, 0x3f70f0f1, 0x3f638e39, 0x3f579436, 0x3f4ccccd, 0x3f430c31, 0x3f3a2e8c, 0x3f321643];
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts;
+
+    // Rounding the double precision value to single precision gives the
+    // correctly-rounded value, because the double precision value is much more
+    // precise.
+    fn assert_correctly_rounded(bits: super::U, real: f64) {
+        assert_eq!(bits, (real as f32).to_bits(), "{:#x} != {:?}", bits, real);
+    }
+
+    #[test]
+    fn reduction_constants() {
+        // The inverses must be the correctly-rounded inverses of the real
+        // constants, not the inverses of their single precision values.
+        assert_correctly_rounded(super::LN_2, consts::LN_2);
+        assert_correctly_rounded(super::LN_2_INV, 1.0 / consts::LN_2);
+        assert_correctly_rounded(super::PI_HALF, consts::FRAC_PI_2);
+        assert_correctly_rounded(super::PI_HALF_INV, consts::FRAC_2_PI);
+
+        // The low part is the rounded remainder of the high part.
+        let pi_half = f32::from_bits(super::PI_HALF) as f64;
+        assert_correctly_rounded(super::PI_HALF_LO, consts::FRAC_PI_2 - pi_half);
+
+        // The high part has trailing zeros, so that its multiples by integers
+        // up to 2^9 are exact.
+        let ln_2_hi = f32::from_bits(super::LN_2_HI) as f64;
+        assert_eq!(super::LN_2_HI & 0x1ff, 0);
+        assert_correctly_rounded(super::LN_2_LO, consts::LN_2 - ln_2_hi);
+
+        // The high part of π/2 has 32 trailing zeros, so that its multiples by
+        // all 32-bit integers are exact.
+        let pi_half_hi = f64::from_bits(super::PI_HALF_HI_F64);
+        assert_eq!(super::PI_HALF_HI_F64 & 0xffffffff, 0);
+        assert_eq!(
+            pi_half_hi + f64::from_bits(super::PI_HALF_LO_F64),
+            consts::FRAC_PI_2
+        );
+        assert_eq!(f64::from_bits(super::PI_HALF_INV_F64), consts::FRAC_2_PI);
+    }
+
+    #[test]
+    #[cfg(not(feature = "bare-metal"))]
+    fn reduction_constants_f64() {
+        // The same as for single precision ln(2), up to 2^21.
+        let ln_2_hi = f64::from_bits(super::LN_2_HI_F64);
+        assert_eq!(super::LN_2_HI_F64 & 0x1fffff, 0);
+        assert!((ln_2_hi + f64::from_bits(super::LN_2_LO_F64) - consts::LN_2).abs() < 1e-25);
+    }
+
+    #[test]
+    #[cfg(feature = "table-ln")]
+    fn ln_table() {
+        for j in -5..=7 {
+            let i = (j + 5) as usize;
+            let c = 1.0 + j as f64 / 16.0;
+            assert_correctly_rounded(super::LN_TABLE[i], c.ln());
+            assert_correctly_rounded(super::LN_INV_TABLE[i], 1.0 / c);
+        }
+    }
+
+    #[test]
+    fn pow10_table() {
+        extern crate std;
+        use std::format;
+
+        for k in -37..=38 {
+            let i = (k + 37) as usize;
+            // Parsing the decimal representation is correctly rounded.
+            let expected = format!("1e{}", k).parse::<f32>().unwrap();
+            assert_eq!(super::POW10_TABLE[i], expected.to_bits(), "10^{}", k);
+        }
+    }
+
+    #[test]
+    fn constants() {
+        assert_correctly_rounded(super::E, consts::E);
+        assert_correctly_rounded(super::SQRT_2, consts::SQRT_2);
+        assert_correctly_rounded(super::SQRT_10, 10.0f64.sqrt());
+        assert_correctly_rounded(super::LOG2_E, consts::LOG2_E);
+        assert_correctly_rounded(super::LOG10_E, consts::LOG10_E);
+        assert_correctly_rounded(super::PI_QUARTER, consts::FRAC_PI_4);
+        assert_correctly_rounded(super::TAN_PI_EIGHTH, consts::SQRT_2 - 1.0);
+    }
+}
@@ -1,5 +1,6 @@
-use super::data::{E, LN_2, LN_2_INV, POLY_EXP};
-use crate::float::{EPSILON, F};
+use super::data::{E, EXP_MAX_ARG, EXP_MIN_ARG, LN_2, LN_2_INV, POLY_EXP};
+use crate::float::F;
+use crate::tolerances::{EXP_ONE_TOL, EXP_ZERO_TOL};
 use crate::utils::{f, nearly_equal, poly, reduce, scale};
 
 /// Computes exponentiation function of a number.
@@ -8,6 +9,9 @@ use crate::utils::{f, nearly_equal, poly, reduce, scale};
 ///
 /// The input domain is limited to approximately [ln(min(positive f32)),
 /// ln(max(f32))] ≈ [-87.3, 88.7] due to limits of machine representation.
+/// Above the domain, the result is infinity, below it, the result is zero.
+///
+/// The function is `const`, so it can be evaluated at compile time.
 ///
 /// # Example
 ///
@@ -18,8 +22,12 @@ use crate::utils::{f, nearly_equal, poly, reduce, scale};
 ///
 /// # Implementation details
 ///
-/// First, special cases are handled. If x is 1, then the result is simply
-/// [`Euler's number`]. If x is near zero, then the result is simply 1.
+/// First, special cases are handled. If x is near 1, then the result is simply
+/// [`Euler's number`]. The window contains only 1 and its lower neighbor, for
+/// which the polynomial below gives the same value, so there is no jump at its
+/// edges. If x is near zero, then the result is simply 1. If x is outside of
+/// the domain (including infinities), the result overflows, respectively
+/// underflows, and the reduction below would be meaningless.
 /// Otherwise, input x is reduced to an integer k and real z such that
 ///
 /// ```plain
@@ -45,12 +53,21 @@ use crate::utils::{f, nearly_equal, poly, reduce, scale};
 /// and multiplying by 2^k can be implemented exactly using bit manipulation of
 /// floating point number representation.
 ///
+/// The bit manipulation gives the same results as actual multiplication by 2^k,
+/// which is also exact. However, it works even for x close to the upper limit
+/// of the domain, where k = 128 and 2^k itself is not representable.
+///
 /// [`Euler's number`]: consts/constant.E.html
-pub fn exp(x: F) -> F {
-    if x == 1.0 {
+#[inline]
+pub const fn exp(x: F) -> F {
+    if nearly_equal(x, 1.0, EXP_ONE_TOL) {
         return f(E);
-    } else if nearly_equal(x, 0.0, EPSILON) {
+    } else if nearly_equal(x, 0.0, EXP_ZERO_TOL) {
         return 1.0;
+    } else if x > f(EXP_MAX_ARG) {
+        return F::INFINITY;
+    } else if x < f(EXP_MIN_ARG) {
+        return 0.0;
     }
 
     let (k, z) = reduce(x, f(LN_2), f(LN_2_INV));
@@ -61,11 +78,61 @@ pub fn exp(x: F) -> F {
     scale(expz, k)
 }
 
+/// Computes exp(-x) for non-negative x, which is common in decays or softmax.
+///
+/// # Notes
+///
+/// Unlike [`exp`], the results below the smallest positive normal number are
+/// correctly represented as subnormal numbers, so the function decays
+/// monotonically to exactly zero, which is reached for x greater than
+/// approximately 103.97.
+///
+/// # Example
+///
+/// ```
+/// use nikisas::exp_neg;
+/// assert_eq!(exp_neg(0.0), 1.0);
+/// assert_eq!(exp_neg(200.0), 0.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The input is negated and reduced exactly as in [`exp`], so the results are
+/// the same as exp(-x) whenever exp(-x) is a normal number. If k < -125, the
+/// result may be subnormal. The reconstruction is then split to multiplying by
+/// 2^(k + 64), which is still exact, and by 2^-64, which rounds the result to
+/// the nearest subnormal number (or zero).
+///
+/// [`exp`]: fn.exp.html
+pub fn exp_neg(x: F) -> F {
+    // exp(-x) is less than half of the smallest subnormal number, rounded to
+    // zero.
+    if x > 104.0 {
+        return 0.0;
+    } else if nearly_equal(x, 0.0, EXP_ZERO_TOL) {
+        return 1.0;
+    }
+
+    let (k, z) = reduce(-x, f(LN_2), f(LN_2_INV));
+
+    let z2 = z * z;
+    let expz = 1.0 + z + 0.5 * z2 + z2 * z * poly(z, POLY_EXP);
+
+    if k < -125 {
+        // 2^-64
+        scale(expz, k + 64) * f(0x1f800000)
+    } else {
+        scale(expz, k)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test::error_bounds;
+    use crate::test::{baseline_bounds, error_bounds, EXP_BASELINE};
     use crate::utils::f;
+    use nikisas_test::float::FloatExt;
     use nikisas_test::prelude::*;
+    use nikisas_test::utils::assert_coefficient_signs;
 
     #[test]
     fn exp() {
@@ -77,5 +144,247 @@ mod tests {
 
         UniformSample::with_count(-87.3, 88.7, 10000)
             .assert(error_bounds(), |x| (super::exp(x), x.exp()));
+
+        UniformSample::with_count(-87.3, 88.7, 10000)
+            .assert(baseline_bounds(EXP_BASELINE), |x| (super::exp(x), x.exp()));
+    }
+
+    #[test]
+    fn out_of_domain() {
+        // The integer k of the reduction does not fit in 32 bits for most of
+        // these inputs.
+        for &x in &[88.8, 1e+10, f32::MAX, f32::INFINITY] {
+            assert_eq!(super::exp(x), f32::INFINITY, "{}", x);
+            assert_eq!(super::exp(-x), 0.0, "{}", -x);
+        }
+
+        assert!(super::exp(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn log_output_space() {
+        let sample = || UniformSample::with_count(-87.3, 88.7, 10000);
+        let exp = |x: f32| (super::exp(x), x.exp());
+
+        // In linear space, the absolute error grows with the magnitude of the
+        // result.
+        let linear = sample().error(exp);
+        assert!(linear.max_abs() > 1e+30);
+
+        // In log space, the absolute error is roughly the relative error in
+        // linear space, plus the rounding error of the logarithm.
+        let log = sample().error_transformed(exp, f32::ln);
+        assert!(log.max_abs() <= 2e-5);
+        assert!(log.rms_abs() <= 5e-6);
+    }
+
+    #[test]
+    fn error_split() {
+        let split = UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+            .error_split(UniformSample::with_count(-87.3, 88.7, 10000), |x| {
+                (super::exp(x), x.exp())
+            });
+
+        // The reconstruction 2^k multiplies the error of the reduction by k.
+        assert!(split.full_error.max_rel() >= split.primary_error.max_rel());
+        assert!(split.reduction_error() >= 0.0);
+        assert!(split.primary_error.max_rel() <= f32::EPSILON * 2.0);
+    }
+
+    #[test]
+    fn const_eval() {
+        const E_APPROX: f32 = super::exp(1.0);
+        const TABLE: [f32; 3] = [super::exp(-1.0), super::exp(0.5), super::exp(10.0)];
+
+        assert!((E_APPROX - core::f32::consts::E).abs() <= f32::EPSILON);
+
+        // The evaluation at compile time gives the same results as at runtime.
+        let inputs = [-1.0f32, 0.5, 10.0];
+        for (value, x) in TABLE.iter().zip(inputs.iter()) {
+            assert_eq!(value.to_bits(), super::exp(*x).to_bits());
+        }
+    }
+
+    #[test]
+    fn exp_neg() {
+        assert_eq!(super::exp_neg(0.0), 1.0);
+        assert_eq!(super::exp_neg(1.0), super::exp(-1.0));
+
+        UniformSample::with_count(0.0, 87.3, 100000).for_each(|x| {
+            assert_eq!(
+                super::exp_neg(x).to_bits(),
+                super::exp(-x).to_bits(),
+                "{}",
+                x
+            );
+        });
+
+        // Subnormal results. The error of the reduction is carried over from the
+        // normal range, but the precision of the subnormal numbers is lower.
+        UniformSample::with_count(87.3, 104.0, 100000).assert(error_bounds().ulp(32), |x| {
+            (super::exp_neg(x), (-x as f64).exp() as f32)
+        });
+    }
+
+    #[test]
+    fn exp_neg_decay() {
+        let mut previous = super::exp_neg(0.0);
+        for i in 1..=12000 {
+            let x = i as f32 * 0.01;
+            let y = super::exp_neg(x);
+            assert!(y <= previous, "{}", x);
+            previous = y;
+        }
+
+        // Past the underflow threshold, the result is exactly zero.
+        assert_eq!(super::exp_neg(103.98), 0.0);
+        assert_eq!(super::exp_neg(104.5), 0.0);
+        assert_eq!(super::exp_neg(1e+30), 0.0);
+        assert_eq!(super::exp_neg(f32::INFINITY), 0.0);
+        assert!(super::exp_neg(103.9) > 0.0);
+    }
+
+    #[test]
+    fn coefficient_signs() {
+        // The polynomial is evaluated in double precision, so that the error
+        // is dominated by the approximation and not by the rounding.
+        let mut coeffs = [0.0; 5];
+        for (coeff, &bits) in coeffs.iter_mut().zip(super::POLY_EXP.iter()) {
+            *coeff = f(bits) as f64;
+        }
+
+        let eval = |c: &[f64], z: f64| {
+            let p = c[0] + z * (c[1] + z * (c[2] + z * (c[3] + z * c[4])));
+            1.0 + z + 0.5 * z * z + z * z * z * p
+        };
+
+        let bound = core::f64::consts::LN_2 / 2.0;
+        assert_coefficient_signs(&coeffs, eval, |z| z.exp(), -bound, bound, 10.0);
+    }
+
+    #[test]
+    fn ulp() {
+        // The reference is computed in double precision and then rounded.
+        let exp = |x: f32| (super::exp(x), (x as f64).exp() as f32);
+
+        UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+            .assert(ErrorBounds::new().ulp(8), exp);
+
+        // In the whole domain, the error of the argument reduction grows with
+        // the magnitude of k.
+        UniformSample::with_count(-87.3, 88.7, 100000).assert(ErrorBounds::new().ulp(80), exp);
+    }
+
+    // Variant of exp that reconstructs the result by multiplying by 2^k instead
+    // of manipulating the exponent bits.
+    fn exp_mul(x: f32) -> f32 {
+        let (k, z) = super::reduce(x, f(super::LN_2), f(super::LN_2_INV));
+
+        let z2 = z * z;
+        let expz = 1.0 + z + 0.5 * z2 + z2 * z * super::poly(z, super::POLY_EXP);
+
+        crate::math::pow::square_mul(2.0, k) * expz
+    }
+
+    #[test]
+    fn reconstruction() {
+        // Multiplying by a power of two is exact as long as the result is
+        // normal, so both approaches must give identical results. The only
+        // exception is k = 128, for which 2^k itself overflows.
+        UniformSample::with_count(-87.3, 88.7, 100000)
+            .filter(|x| *x != 1.0 && !super::nearly_equal(*x, 0.0, super::EXP_ZERO_TOL))
+            .for_each(|x| {
+                let (k, _) = super::reduce(x, f(super::LN_2), f(super::LN_2_INV));
+                if k < 128 {
+                    assert_eq!(super::exp(x).to_bits(), exp_mul(x).to_bits(), "{}", x);
+                } else {
+                    assert!(super::exp(x).is_finite(), "{}", x);
+                    assert!(exp_mul(x).is_infinite(), "{}", x);
+                }
+            });
+    }
+
+    #[test]
+    fn branches() {
+        let branch = |x: f32| {
+            if (x - 1.0).abs() <= crate::tolerances::EXP_ONE_TOL {
+                "one"
+            } else if x.abs() <= crate::tolerances::EXP_ZERO_TOL {
+                "zero"
+            } else {
+                "general"
+            }
+        };
+
+        // Uniform sampling of the primary range almost never hits the special
+        // cases, so the neighborhoods of the thresholds are sampled
+        // exhaustively.
+        UniformSample::with_count(-0.5, 0.5, 1000)
+            .chain(Exhaustive::near(crate::tolerances::EXP_ZERO_TOL, 1e-10))
+            .chain(Exhaustive::near(1.0, 1e-6))
+            .branch_coverage(branch)
+            .assert_covered(&["one", "zero", "general"]);
+    }
+
+    #[test]
+    fn zero_snap() {
+        let tol = super::EXP_ZERO_TOL;
+
+        // The whole region is snapped to the exact value.
+        assert_eq!(super::exp(tol), 1.0);
+        assert_eq!(super::exp(-tol), 1.0);
+
+        // Right outside of the region, the approximation is already able to
+        // distinguish the result from one.
+        assert!(super::exp(tol.nextup()) > 1.0);
+        assert!(super::exp(-tol.nextup()) < 1.0);
+    }
+
+    #[test]
+    fn one_snap() {
+        let tol = super::EXP_ONE_TOL;
+        let e = f(super::E);
+
+        // The whole region is snapped to the exact value.
+        assert_eq!(super::exp(1.0 - tol), e);
+        assert_eq!(super::exp(1.0 + tol), e);
+
+        // Right outside of the region, the approximation is within one unit in
+        // the last place from the snapped value.
+        assert!(super::exp((1.0 - tol).nextdown()).ulp_distance(e) <= 1);
+        assert!(super::exp((1.0 + tol).nextup()).ulp_distance(e) <= 1);
+    }
+
+    #[test]
+    fn smooth_near_one() {
+        // The reference is computed in double precision and then rounded.
+        Exhaustive::near(1.0f32, 1e-5).assert(ErrorBounds::new().ulp(1), |x| {
+            (super::exp(x), (x as f64).exp() as f32)
+        });
+
+        let mut previous = super::exp(1.0f32 - 1e-5);
+        for x in Exhaustive::near(1.0f32, 1e-5) {
+            let y = super::exp(x);
+            assert!(y >= previous, "{}", x);
+            previous = y;
+        }
+    }
+
+    #[cfg(feature = "cross-check")]
+    #[test]
+    fn micromath() {
+        use micromath::F32Ext;
+
+        // The same inputs are fed to both implementations.
+        let (error, error_micromath) =
+            UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+                .error_tee(|x| (super::exp(x), x.exp()), |x| (F32Ext::exp(x), x.exp()));
+        assert!(error.max_rel() <= error_micromath.max_rel());
+        assert!(error.rms() <= error_micromath.rms());
+
+        let (error, error_micromath) = UniformSample::with_count(-87.3, 88.7, 100000)
+            .error_tee(|x| (super::exp(x), x.exp()), |x| (F32Ext::exp(x), x.exp()));
+        assert!(error.max_rel() <= error_micromath.max_rel());
+        assert!(error.rms() <= error_micromath.rms());
     }
 }
@@ -0,0 +1,150 @@
+use super::data::{EXP_MAX_ARG, EXP_MIN_ARG, LN_2_HI, LN_2_INV, LN_2_LO, POLY_EXP};
+use crate::float::F;
+use crate::utils::{f, poly, saturating_round, scale, two_prod, two_sum};
+
+/// Computes exponentiation function of a number as an unevaluated sum of two
+/// single precision numbers hi + lo, where hi is the result rounded to single
+/// precision and lo is the correction. The pair carries roughly twice as many
+/// significant bits as [`exp`] alone and can be used as an input to further
+/// computations without losing the precision.
+///
+/// # Notes
+///
+/// The input domain is the same as for [`exp`], that is, approximately
+/// [-87.3, 88.7]. Above the domain, the result is (infinity, 0), below it, the
+/// result is (0, 0).
+///
+/// The relative error of hi + lo is below 5e-9, which is limited by the
+/// polynomial approximation. For x below approximately -70, the low part is
+/// subnormal and loses its precision, down to the point where it is zero and
+/// the result is no better than [`exp`].
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::exp_dd;
+/// let (hi, lo) = exp_dd(1.0);
+/// assert_eq!(hi, core::f32::consts::E);
+/// assert!((hi as f64 + lo as f64 - core::f64::consts::E).abs() < 1e-8);
+/// ```
+///
+/// # Implementation details
+///
+/// The inputs outside of the domain (including infinities) are handled first as
+/// in [`exp`].
+///
+/// The input x is reduced in the same way as in [`exp`], but ln(2) is split to
+/// two parts. The high part has trailing zeros, so that k * ln2_hi is exact and
+/// so is the subtraction of it from x. The reduced argument is then represented
+/// as a pair
+///
+/// ```plain
+///   z = zh + zl, where zh + zl = x - k * ln2_hi - k * ln2_lo
+/// ```
+///
+/// using error-free transformations [`two_sum`] and [`two_prod`] (Knuth's and
+/// Dekker's algorithms). The polynomial
+///
+/// ```plain
+///   exp(zh) ≈ 1 + zh + 1/2 * zh^2 + zh^3 * P(zh)
+/// ```
+///
+/// from [`exp`] is evaluated such that the rounding errors of 1 + zh and zh^2
+/// are kept in the low part. The high-degree tail is small and so its rounding
+/// error does not matter. The correction for zl follows from exp(zh + zl) ≈
+/// exp(zh) * (1 + zl).
+///
+/// Finally, both parts are multiplied by 2^k. The low part is multiplied by two
+/// halves of 2^k, so that it is correctly rounded even if it is subnormal.
+///
+/// [`exp`]: fn.exp.html
+/// [`two_sum`]: https://en.wikipedia.org/wiki/2Sum
+/// [`two_prod`]: https://doi.org/10.1007/BF01397083
+#[inline]
+pub fn exp_dd(x: F) -> (F, F) {
+    if x > f(EXP_MAX_ARG) {
+        return (F::INFINITY, 0.0);
+    } else if x < f(EXP_MIN_ARG) {
+        return (0.0, 0.0);
+    }
+
+    let k = saturating_round(x * f(LN_2_INV));
+    let kd = k as F;
+
+    let (zh, zl) = two_sum(x - kd * f(LN_2_HI), -kd * f(LN_2_LO));
+
+    let (s, s_err) = two_sum(1.0, zh);
+    let (p, p_err) = two_prod(zh, zh);
+    let tail = zh * p * poly(zh, POLY_EXP);
+
+    let (h, l) = two_sum(s, 0.5 * p);
+    let l = l + (s_err + 0.5 * p_err + tail);
+    let l = l + (h + l) * zl;
+    let (h, l) = two_sum(h, l);
+
+    let hi = scale(h, k);
+    let lo = l * scale(1.0, k / 2) * scale(1.0, k - k / 2);
+    (hi, lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use nikisas_test::prelude::*;
+
+    fn errors(low: f32, high: f32) -> (Error<f64, f32>, Error<f64, f32>) {
+        let sample = || UniformSample::with_count(low, high, 100000);
+        let real = |x: f32| (x as f64).exp();
+
+        let error_hi = sample().error_pair(|x| ((super::exp_dd(x).0, 0.0), real(x)));
+        let error_pair = sample().error_pair(|x| (super::exp_dd(x), real(x)));
+        (error_hi, error_pair)
+    }
+
+    #[test]
+    fn exp_dd() {
+        assert_eq!(super::exp_dd(0.0), (1.0, 0.0));
+        assert_eq!(super::exp_dd(1.0).0, core::f32::consts::E);
+
+        // The high part alone is the result rounded to single precision, the
+        // pair is much closer to the real value.
+        for &(low, high) in &[(-0.35, 0.35), (-70.0, 88.7)] {
+            let (error_hi, error_pair) = errors(low, high);
+            assert!(error_hi.max_rel() > 2e-8, "{:e}", error_hi.max_rel());
+            assert!(error_hi.max_rel() < 1e-7, "{:e}", error_hi.max_rel());
+            assert!(error_pair.max_rel() < 5e-9, "{:e}", error_pair.max_rel());
+        }
+
+        // Near the lower limit of the domain, the low part is subnormal.
+        let (error_hi, error_pair) = errors(-87.3, -80.0);
+        assert!(error_pair.max_rel() > 5e-9);
+        assert!(error_pair.max_rel() <= error_hi.max_rel());
+    }
+
+    #[test]
+    fn out_of_domain() {
+        for &x in &[88.8, 1e+10, f32::MAX, f32::INFINITY] {
+            assert_eq!(super::exp_dd(x), (f32::INFINITY, 0.0), "{}", x);
+            assert_eq!(super::exp_dd(-x), (0.0, 0.0), "{}", -x);
+        }
+
+        assert!(super::exp_dd(f32::NAN).0.is_nan());
+
+        // The edge of the domain.
+        let (hi, lo) = super::exp_dd(crate::utils::f(crate::math::data::EXP_MAX_ARG));
+        assert!(hi.is_finite() && lo.is_finite());
+    }
+
+    #[test]
+    fn single_word() {
+        let sample = || UniformSample::with_count(-70.0, 88.7, 100000);
+        let real = |x: f32| (x as f64).exp();
+
+        let error = sample().error_pair(|x| ((crate::exp(x), 0.0), real(x)));
+        let error_dd = sample().error_pair(|x| (super::exp_dd(x), real(x)));
+
+        // The effective precision of the pair is much better than what the
+        // single precision exp achieves.
+        assert!(error_dd.max_rel() < 0.01 * error.max_rel());
+        assert!(error_dd.rms() < 0.01 * error.rms());
+    }
+}
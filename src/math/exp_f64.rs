@@ -0,0 +1,103 @@
+use super::data::{LN_2_HI_F64, LN_2_LO_F64, POLY_EXP_F64};
+use crate::utils::{poly_f64, round_f64, scale_f64};
+
+use core::f64::consts::{LN_2, LOG2_E};
+
+/// Computes exponentiation function of a double precision number.
+///
+/// # Notes
+///
+/// The input domain is limited to approximately [ln(min(positive f64)),
+/// ln(max(f64))] ≈ [-708.3, 709.7] due to limits of machine representation.
+/// Above the domain, the result is infinity, below it, the result is zero.
+///
+/// The relative error is below 1e-15 on the whole domain.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::exp_f64;
+/// assert_eq!(exp_f64(0.0), 1.0);
+/// ```
+///
+/// # Implementation details
+///
+/// First, the inputs outside of the domain (including infinities) are handled,
+/// because the integer k below would not fit in 32 bits for them. The bounds
+/// are 1024 * ln(2) and -1022 * ln(2), where the result leaves the range of
+/// normal double precision numbers.
+///
+/// The algorithm is the same as in [`exp`], except that it is done in double
+/// precision and that the argument reduction
+///
+/// ```plain
+///   x = k * ln(2) + z and |z| ≤ ln(2) / 2
+/// ```
+///
+/// uses ln(2) split to two parts, ln(2) = hi + lo, where hi has enough
+/// trailing zeros so that k * hi is exact for all valid k. The reduced argument
+/// is then computed as z = (x - k * hi) - k * lo, which is much more precise
+/// than x - k * ln(2).
+///
+/// The exponential of z is approximated using a polynomial in the form:
+///
+/// ```plain
+///   exp(z) ≈ 1 + z + 1/2 * z^2 + z^3 * P(z)
+/// ```
+///
+/// where P has degree 9 and its coefficients are found by interpolation at
+/// Chebyshev nodes, which is close to the minimax polynomial. The script
+/// computing them is `chebyshev.py` in the `sollya` directory.
+///
+/// [`exp`]: fn.exp.html
+#[inline]
+pub fn exp_f64(x: f64) -> f64 {
+    if x >= 1024.0 * LN_2 {
+        return f64::INFINITY;
+    } else if x < -1022.0 * LN_2 {
+        return 0.0;
+    }
+
+    let k = round_f64(x * LOG2_E);
+    let kd = k as f64;
+    let z = (x - kd * f64::from_bits(LN_2_HI_F64)) - kd * f64::from_bits(LN_2_LO_F64);
+
+    let z2 = z * z;
+    let expz = 1.0 + z + 0.5 * z2 + z2 * z * poly_f64(z, &POLY_EXP_F64);
+
+    scale_f64(expz, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds_f64;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn exp_f64() {
+        assert_eq!(super::exp_f64(0.0), 1.0);
+
+        let ln_2_half = core::f64::consts::LN_2 / 2.0;
+        UniformSample::with_count(-ln_2_half, ln_2_half, 100000)
+            .assert(error_bounds_f64(), |x| (super::exp_f64(x), x.exp()));
+
+        UniformSample::with_count(-708.3, 709.7, 100000)
+            .assert(error_bounds_f64(), |x| (super::exp_f64(x), x.exp()));
+    }
+
+    #[test]
+    fn out_of_domain() {
+        for &x in &[709.8, 800.0, 1e+10, 1e+300, f64::MAX, f64::INFINITY] {
+            assert_eq!(super::exp_f64(x), f64::INFINITY, "{}", x);
+            assert_eq!(super::exp_f64(-x), 0.0, "{}", -x);
+        }
+
+        assert!(super::exp_f64(f64::NAN).is_nan());
+
+        // The edges of the domain.
+        assert!(super::exp_f64(709.78).is_finite());
+        let min = -1022.0 * core::f64::consts::LN_2;
+        assert!(super::exp_f64(min).is_normal());
+        assert!((super::exp_f64(min) / min.exp() - 1.0).abs() <= 1e-15);
+    }
+}
@@ -0,0 +1,109 @@
+use super::data::POLY_EXP;
+use crate::float::F;
+use crate::utils::{poly, round_f64, scale};
+
+use core::f64::consts::{LN_2, LOG2_E};
+
+/// Computes exponentiation function of a double precision number with single
+/// precision result.
+///
+/// # Notes
+///
+/// The input domain is limited to approximately [ln(min(positive f32)),
+/// ln(max(f32))] ≈ [-87.3, 88.7] due to limits of machine representation.
+/// Above the domain, the result is infinity, below it, the result is zero.
+///
+/// Compared to [`exp`], the error does not grow with the magnitude of x and it
+/// stays the same as in the primary range on the whole domain. The cost is a
+/// few double precision operations.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{exp_mixed, consts::E};
+/// assert_eq!(exp_mixed(1.0), E);
+/// ```
+///
+/// # Implementation details
+///
+/// The inputs outside of the domain (including infinities) are handled first,
+/// because the integer k below would not fit in 32 bits for them. The bounds
+/// are 128 * ln(2) and -126 * ln(2), where the result leaves the range of
+/// normal single precision numbers.
+///
+/// The algorithm is the same as in [`exp`], except that the argument reduction
+///
+/// ```plain
+///   x = k * ln(2) + z and |z| ≤ ln(2) / 2
+/// ```
+///
+/// is done in double precision. For large k, the representation error of ln(2)
+/// multiplied by k is the dominant source of error in [`exp`], but in double
+/// precision it is negligible. The reduced argument z is then rounded to single
+/// precision and both the polynomial approximation and the reconstruction are
+/// done in single precision.
+///
+/// [`exp`]: fn.exp.html
+#[inline]
+pub fn exp_mixed(x: f64) -> F {
+    if x >= 128.0 * LN_2 {
+        return F::INFINITY;
+    } else if x < -126.0 * LN_2 {
+        return 0.0;
+    }
+
+    let k = round_f64(x * LOG2_E);
+    let z = (x - k as f64 * LN_2) as F;
+
+    let z2 = z * z;
+    let expz = 1.0 + z + 0.5 * z2 + z2 * z * poly(z, POLY_EXP);
+
+    scale(expz, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn exp_mixed() {
+        assert_eq!(super::exp_mixed(0.0), 1.0);
+
+        UniformSample::with_count(-87.3, 88.7, 100000)
+            .assert(error_bounds(), |x| (super::exp_mixed(x as f64), x.exp()));
+    }
+
+    #[test]
+    fn out_of_domain() {
+        for &x in &[88.73, 100.0, 200.0, 1e+300, f64::MAX, f64::INFINITY] {
+            assert_eq!(super::exp_mixed(x), f32::INFINITY, "{}", x);
+            assert_eq!(super::exp_mixed(-x), 0.0, "{}", -x);
+        }
+
+        assert!(super::exp_mixed(f64::NAN).is_nan());
+
+        // The edges of the domain.
+        let max = 128.0 * core::f64::consts::LN_2;
+        assert_eq!(super::exp_mixed(max - 1e-6), (max - 1e-6).exp() as f32);
+        let min = -126.0 * core::f64::consts::LN_2;
+        assert_eq!(super::exp_mixed(min), f32::MIN_POSITIVE);
+    }
+
+    #[test]
+    fn large_arguments() {
+        // The inputs are single precision numbers, so both functions get
+        // exactly the same argument.
+        let exp = |x: f32| (crate::exp(x), (x as f64).exp() as f32);
+        let exp_mixed = |x: f32| (super::exp_mixed(x as f64), (x as f64).exp() as f32);
+
+        let error = UniformSample::with_count(60.0, 88.7, 100000).error(exp);
+        let error_mixed = UniformSample::with_count(60.0, 88.7, 100000).error(exp_mixed);
+
+        assert!(error.max_ulp() > 40);
+        assert!(error_mixed.max_ulp() <= 8);
+
+        UniformSample::with_count(-87.3, -60.0, 100000)
+            .assert(ErrorBounds::new().ulp(8), exp_mixed);
+    }
+}
@@ -0,0 +1,135 @@
+use super::data::{EXP_MAX_ARG, EXP_MIN_ARG, LN_2, LN_2_INV, POLY_EXP};
+use crate::float::F;
+use crate::utils::{f, poly, reduce, scale};
+
+/// Computes exp(x) - 1 accurately also for x near zero, where the
+/// straightforward formula suffers from catastrophic cancellation.
+///
+/// # Notes
+///
+/// The input domain is limited in the same way as in [`exp`]. Above it, the
+/// result is infinity, below it, the result is -1.
+///
+/// # Example
+///
+/// ```
+/// use nikisas::expm1;
+/// assert_eq!(expm1(0.0), 0.0);
+/// assert_eq!(expm1(1e-10), 1e-10);
+/// ```
+///
+/// # Implementation details
+///
+/// First, the inputs outside of the domain (including infinities) are handled
+/// as in [`exp`], for which the reduction below would be meaningless.
+///
+/// The input x is reduced to an integer k and real z such that
+///
+/// ```plain
+///   x = k * ln(2) + z and |z| ≤ ln(2) / 2
+/// ```
+///
+/// exactly as in [`exp`]. The polynomial approximation of exp(z) has the form
+/// 1 + z + 1/2 * z^2 + z^3 * P(z), and so exp(z) - 1 is obtained simply by
+/// omitting the leading one, without any subtraction:
+///
+/// ```plain
+///   expm1(z) ≈ z + 1/2 * z^2 + z^3 * P(z)
+/// ```
+///
+/// The reconstruction follows this identity:
+///
+/// ```plain
+///   exp(x) - 1 = 2^k * (expm1(z) + 1) - 1 = 2^k * expm1(z) + (2^k - 1)
+/// ```
+///
+/// For |k| ≤ 24, 2^k - 1 is computed exactly and the two terms do not cancel
+/// significantly. For larger |k|, the subtraction of one affects at most the
+/// last bit of the result, so it is computed as in [`exp`].
+///
+/// [`exp`]: fn.exp.html
+pub fn expm1(x: F) -> F {
+    if x > f(EXP_MAX_ARG) {
+        return F::INFINITY;
+    } else if x < f(EXP_MIN_ARG) {
+        return -1.0;
+    }
+
+    let (k, z) = reduce(x, f(LN_2), f(LN_2_INV));
+
+    let z2 = z * z;
+    let expm1z = z + 0.5 * z2 + z2 * z * poly(z, POLY_EXP);
+
+    if k == 0 {
+        expm1z
+    } else if k.unsigned_abs() <= 24 {
+        let p = scale(1.0, k);
+        expm1z * p + (p - 1.0)
+    } else {
+        scale(1.0 + expm1z, k) - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::float::FloatExt;
+    use nikisas_test::prelude::*;
+    use nikisas_test::utils::cancellation_gain;
+
+    #[test]
+    fn expm1() {
+        assert_eq!(super::expm1(0.0), 0.0);
+        assert_eq!(super::expm1(-0.0), 0.0);
+        assert_eq!(super::expm1(-100.0), -1.0);
+
+        UniformSample::with_count(-2.0f32.ln() / 2.0, 2.0f32.ln() / 2.0, 100000)
+            .assert(error_bounds(), |x| (super::expm1(x), x.exp_m1()));
+
+        UniformSample::with_count(-87.3, 88.7, 10000)
+            .assert(error_bounds(), |x| (super::expm1(x), x.exp_m1()));
+
+        UniformSample::builder(1e-30, 1.0)
+            .count(10000)
+            .log_scale()
+            .build()
+            .flat_map(|x| core::iter::once(x).chain(core::iter::once(-x)))
+            .assert(error_bounds(), |x| (super::expm1(x), x.exp_m1()));
+    }
+
+    #[test]
+    fn out_of_domain() {
+        for &x in &[88.8, 89.0, 100.0, 1e+10, f32::MAX, f32::INFINITY] {
+            assert_eq!(super::expm1(x), f32::INFINITY, "{}", x);
+            assert_eq!(super::expm1(-x), -1.0, "{}", -x);
+        }
+
+        assert!(super::expm1(f32::NAN).is_nan());
+
+        // The edges of the domain.
+        let max = crate::utils::f(crate::math::data::EXP_MAX_ARG);
+        assert!(super::expm1(max).is_finite());
+        assert_eq!(super::expm1(max.nextup()), f32::INFINITY);
+    }
+
+    #[test]
+    fn cancellation() {
+        let gain = cancellation_gain(
+            super::expm1,
+            |x| crate::exp(x) - 1.0,
+            |x| (x as f64).exp_m1() as f32,
+            1e-10,
+            10.0,
+            10000,
+        );
+
+        // Near zero, subtracting one from exp(x) loses all the digits, while
+        // the error of expm1 stays at the level of the rounding.
+        assert!(gain.max_gain() > 1e+6, "{}", gain);
+
+        // For |x| of order 1 and more, there is no cancellation and the naive
+        // formula is equally good.
+        let crossover = gain.crossover(10.0).unwrap();
+        assert_eq!(crossover, -1, "{}", gain);
+    }
+}
@@ -0,0 +1,113 @@
+use super::sqrt::sqrt;
+use crate::float::F;
+use crate::utils::abs;
+
+/// Computes the length of the hypotenuse of a right-angle triangle given legs
+/// of lengths x and y, that is, sqrt(x^2 + y^2).
+///
+/// # Notes
+///
+/// Unlike the straightforward formula, the implementation does not overflow
+/// nor underflow in intermediate computations, so the result is finite as long
+/// as the real result is finite. If any of the arguments is infinite, the
+/// result is infinity, even if the other one is NaN.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::hypot;
+/// assert_eq!(hypot(3.0, 4.0), 5.0);
+/// ```
+///
+/// # Implementation details
+///
+/// First, infinite arguments are handled, so that the scaling below does not
+/// produce infinity divided by infinity. Let a = max(|x|, |y|) and
+/// b = min(|x|, |y|). If b is zero, the result is simply a. Otherwise, the
+/// following identity is used:
+///
+/// ```plain
+///   sqrt(x^2 + y^2) = sqrt(a^2 + b^2) = a * sqrt(1 + (b / a)^2)
+/// ```
+///
+/// Since 0 < b / a ≤ 1, the squaring can neither overflow nor underflow to a
+/// significant extent. For computing the square root, [`sqrt`] routine is used.
+///
+/// [`sqrt`]: fn.sqrt.html
+pub fn hypot(x: F, y: F) -> F {
+    let (x, y) = (abs(x), abs(y));
+
+    if x == F::INFINITY || y == F::INFINITY {
+        return F::INFINITY;
+    }
+
+    let (a, b) = if x > y { (x, y) } else { (y, x) };
+
+    if b == 0.0 {
+        return a;
+    }
+
+    let r = b / a;
+    a * sqrt(1.0 + r * r)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    // Computed in double precision to avoid overflow and underflow.
+    fn hypot_f64(x: f32, y: f32) -> f32 {
+        (x as f64).hypot(y as f64) as f32
+    }
+
+    #[test]
+    fn hypot_special() {
+        assert_eq!(super::hypot(3.0, 4.0), 5.0);
+        assert_eq!(super::hypot(-3.0, 4.0), 5.0);
+
+        // Exact passthrough if one argument is zero.
+        assert_eq!(super::hypot(1.5, 0.0), 1.5);
+        assert_eq!(super::hypot(0.0, -2.5), 2.5);
+        assert_eq!(super::hypot(0.0, 0.0), 0.0);
+
+        // Infinity wins over NaN.
+        assert_eq!(super::hypot(f32::INFINITY, f32::INFINITY), f32::INFINITY);
+        assert_eq!(super::hypot(f32::NEG_INFINITY, 1.0), f32::INFINITY);
+        assert_eq!(super::hypot(1.0, f32::NEG_INFINITY), f32::INFINITY);
+        assert_eq!(super::hypot(f32::NAN, f32::INFINITY), f32::INFINITY);
+        assert_eq!(super::hypot(f32::NEG_INFINITY, f32::NAN), f32::INFINITY);
+        assert!(super::hypot(f32::NAN, 1.0).is_nan());
+    }
+
+    #[test]
+    fn hypot_overflow() {
+        // The squares overflow.
+        assert!((3e+19f32 * 3e+19f32).is_infinite());
+        assert_eq!(super::hypot(3e+19, 4e+19), 5e+19);
+
+        UniformSample::with_count(1.9e+19, 1.7e+38, 10000).assert(error_bounds(), |x| {
+            (super::hypot(x, 0.5 * x), hypot_f64(x, 0.5 * x))
+        });
+    }
+
+    #[test]
+    fn hypot_underflow() {
+        // The squares underflow to zero.
+        assert_eq!(3e-25f32 * 3e-25f32, 0.0);
+        let h = super::hypot(3e-25, 4e-25);
+        assert!((h - 5e-25).abs() <= 5e-25 * 1e-6);
+
+        // Subnormal inputs.
+        UniformSample::with_count(1e-45, 1e-38, 10000)
+            .assert(error_bounds(), |x| (super::hypot(x, x), hypot_f64(x, x)));
+    }
+
+    #[test]
+    fn hypot() {
+        UniformSample::with_count(-100.0, 100.0, 1000).fold((), |_, x| {
+            UniformSample::with_count(-100.0, 100.0, 1000)
+                .assert(error_bounds(), |y| (super::hypot(x, y), hypot_f64(x, y)));
+        });
+    }
+}
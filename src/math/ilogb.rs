@@ -0,0 +1,72 @@
+use crate::float::{F, I};
+use crate::utils::{abs, decompose};
+
+/// Computes the exponent of a number, that is, the integral part of log2(|x|).
+///
+/// # Notes
+///
+/// For zero and NaN, the result is `i32::MIN`, and for infinity, the result is
+/// `i32::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::ilogb;
+/// assert_eq!(ilogb(10.0), 3);
+/// assert_eq!(ilogb(-0.25), -2);
+/// ```
+///
+/// # Implementation details
+///
+/// The input x is decomposed into real y and integer n such that
+///
+/// ```plain
+///   x = y * 2^n, where 1 ≤ |y| < 2
+/// ```
+///
+/// Since this is the machine representation of floating point number, n is
+/// simply the exponent stored in x and the result is exact. Subnormal numbers
+/// are multiplied by 2^24 first (which is exact) and then 24 is subtracted from
+/// the exponent.
+#[inline]
+pub fn ilogb(x: F) -> I {
+    if x == 0.0 || x.is_nan() {
+        return I::MIN;
+    } else if x.is_infinite() {
+        return I::MAX;
+    } else if abs(x) < F::MIN_POSITIVE {
+        return decompose(x * 16777216.0).1 - 24;
+    }
+
+    decompose(x).1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::{F, I};
+    use nikisas_test::float::FloatExt;
+    use nikisas_test::prelude::*;
+
+    fn ilogb_f64(x: F) -> I {
+        (x as f64).abs().log2().floor() as I
+    }
+
+    #[test]
+    fn ilogb() {
+        assert_eq!(super::ilogb(1.0), 0);
+        assert_eq!(super::ilogb(2.0f32.nextdown()), 0);
+        assert_eq!(super::ilogb(1e-45), -149);
+        assert_eq!(super::ilogb(0.0), I::MIN);
+        assert_eq!(super::ilogb(F::NAN), I::MIN);
+        assert_eq!(super::ilogb(F::NEG_INFINITY), I::MAX);
+
+        let error = BitUniformSample::with_count(1e-45, 3.4e+38, 100000)
+            .error_exact(|x| (super::ilogb(x), ilogb_f64(x)));
+        error.assert();
+        assert_eq!(error.match_ratio(), 1.0);
+
+        BitUniformSample::with_count(-3.4e+38, -1e-45, 100000)
+            .error_exact(|x| (super::ilogb(x), ilogb_f64(x)))
+            .assert();
+    }
+}
@@ -1,13 +1,24 @@
-use super::data::{E, LN_2, POLY_LN1P, SQRT_2};
-use crate::float::{EPSILON, F};
-use crate::utils::{decompose, f, nearly_equal, poly};
+#[cfg(not(feature = "table-ln"))]
+use super::data::POLY_LN1P;
+use super::data::{E, LN_2, SQRT_2};
+#[cfg(feature = "table-ln")]
+use super::data::{LN_INV_TABLE, LN_TABLE, POLY_LN1P_SHORT};
+use crate::float::F;
+use crate::tolerances::LN_E_TOL;
+#[cfg(not(feature = "table-ln"))]
+use crate::utils::poly;
+#[cfg(feature = "table-ln")]
+use crate::utils::{clamp, poly3, round_small};
+use crate::utils::{decompose, f, nearly_equal};
 
 /// Computes natural logarithm of a number.
 ///
 /// # Notes
 ///
-/// Theoretical input domain is (0, max(f32)] ≈ (0, 3.40282347e+38], but near
-/// zero the values get quite inaccurate.
+/// The input domain is (0, max(f32)] ≈ (0, 3.40282347e+38], including
+/// subnormal numbers.
+///
+/// The function is `const`, so it can be evaluated at compile time.
 ///
 /// # Examples
 ///
@@ -26,6 +37,10 @@ use crate::utils::{decompose, f, nearly_equal, poly};
 ///   x = y * 2^n, where 1 ≤ y < 2
 /// ```
 ///
+/// Subnormal numbers do not have the implicit leading one in their machine
+/// representation, so they are multiplied by 2^24 (which is exact) before the
+/// decomposition and 24 is subtracted from n afterwards.
+///
 /// At this point, we might use
 ///
 /// ```plain
@@ -63,15 +78,39 @@ use crate::utils::{decompose, f, nearly_equal, poly};
 ///   ln(x) = n * ln(2) + ln(y) = n * ln(2) + ln(1 + z)
 /// ```
 ///
+/// With `table-ln` feature, y is further reduced using a table of precomputed
+/// logarithms. Let c = 1 + j/16 be the nearest multiple of 1/16 to y, for j in
+/// -5..=7. Then
+///
+/// ```plain
+///   ln(y) = ln(c) + ln(y / c) = ln(c) + ln(1 + r), where r = (y - c) / c
+/// ```
+///
+/// The difference y - c is exact and the division is replaced by multiplication
+/// by the tabulated 1/c. Since |r| < 0.046, a shorter polynomial
+///
+/// ```plain
+///   ln(1 + r) ≈ r - 1/2 * r^2 + r^3 * Q(r)
+/// ```
+///
+/// with only three coefficients gives more accurate results. For y close to 1,
+/// c = 1 and ln(c) = 0, so there is no cancellation in the sum.
+///
 /// [`Euler's number`]: consts/constant.E.html
-pub fn ln(x: F) -> F {
+#[inline]
+pub const fn ln(x: F) -> F {
     if x == 1.0 {
         return 0.0;
-    } else if nearly_equal(x, f(E), EPSILON) {
+    } else if nearly_equal(x, f(E), LN_E_TOL) {
         return 1.0;
     }
 
-    let (y, n) = decompose(x);
+    let (y, n) = if x < F::MIN_POSITIVE {
+        let (y, n) = decompose(x * 16777216.0);
+        (y, n - 24)
+    } else {
+        decompose(x)
+    };
 
     let (y, n) = if y > f(SQRT_2) {
         (y * 0.5, n + 1)
@@ -79,17 +118,37 @@ pub fn ln(x: F) -> F {
         (y, n)
     };
 
+    let n = n as F;
+    n * f(LN_2) + ln_reduced(y)
+}
+
+/// Computes ln(y) for y in [1/sqrt(2), sqrt(2)].
+#[cfg(not(feature = "table-ln"))]
+#[inline]
+const fn ln_reduced(y: F) -> F {
     let z = y - 1.0;
     let z2 = z * z;
-    let lny = z - 0.5 * z2 + z2 * z * poly(z, POLY_LN1P);
+    z - 0.5 * z2 + z2 * z * poly(z, POLY_LN1P)
+}
 
-    let n = n as F;
-    n * f(LN_2) + lny
+/// Computes ln(y) for y in [1/sqrt(2), sqrt(2)] using the table.
+#[cfg(feature = "table-ln")]
+#[inline]
+const fn ln_reduced(y: F) -> F {
+    // The clamping does not change the index for valid inputs, but avoids
+    // panicking on out-of-bounds access for invalid ones.
+    let j = clamp(round_small((y - 1.0) * 16.0), -5, 7);
+    let i = (j + 5) as usize;
+    let c = 1.0 + j as F * 0.0625;
+
+    let r = (y - c) * f(LN_INV_TABLE[i]);
+    let r2 = r * r;
+    f(LN_TABLE[i]) + (r - 0.5 * r2 + r2 * r * poly3(r, POLY_LN1P_SHORT))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test::error_bounds;
+    use crate::test::{baseline_bounds, error_bounds, LN_BASELINE};
     use crate::utils::f;
     use nikisas_test::prelude::*;
     use nikisas_test::utils::shift_right;
@@ -104,5 +163,116 @@ mod tests {
 
         UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000)
             .assert(error_bounds(), |x| (super::ln(x), x.ln()));
+
+        // The baseline comes from sampling in the logarithmic scale, which
+        // covers the primary range as well.
+        UniformSample::builder(1e-45, 3.4e+38)
+            .count(100000)
+            .log_scale()
+            .build()
+            .assert(baseline_bounds(LN_BASELINE), |x| (super::ln(x), x.ln()));
+    }
+
+    #[test]
+    fn const_eval() {
+        const LN_2_APPROX: f32 = super::ln(2.0);
+
+        assert!((LN_2_APPROX - core::f32::consts::LN_2).abs() <= f32::EPSILON);
+        assert_eq!(LN_2_APPROX.to_bits(), super::ln(2.0).to_bits());
+    }
+
+    #[test]
+    fn subnormal() {
+        assert_eq!(super::ln(1e-45), 1e-45f32.ln());
+
+        SubnormalSample::with_count(1e-45, 1.1754942e-38, 100000)
+            .assert(error_bounds(), |x| (super::ln(x), x.ln()));
+    }
+
+    // The implementation before the subnormal numbers were handled.
+    fn ln_unnormalized(x: f32) -> f32 {
+        let (y, n) = crate::utils::decompose(x);
+        let (y, n) = if y > f(super::SQRT_2) {
+            (y * 0.5, n + 1)
+        } else {
+            (y, n)
+        };
+        n as f32 * f(super::LN_2) + super::ln_reduced(y)
+    }
+
+    #[test]
+    fn decades() {
+        let sample = || {
+            UniformSample::builder(1e-45, 3.4e+38)
+                .count(100000)
+                .log_scale()
+                .build()
+        };
+
+        let worst = |decades: &[(i32, f32)]| {
+            decades.iter().fold(
+                (0, 0.0),
+                |worst, &(decade, rel)| {
+                    if rel > worst.1 {
+                        (decade, rel)
+                    } else {
+                        worst
+                    }
+                },
+            )
+        };
+
+        // Without scaling, the subnormal numbers are the worst by far.
+        let before = sample().error_by_decade(|x| (ln_unnormalized(x), x.ln()));
+        let (decade, rel) = worst(&before);
+        assert!(decade < -37 && rel > 0.1);
+
+        // With scaling, the error is small across the whole range.
+        let after = sample().error_by_decade(|x| (super::ln(x), x.ln()));
+        let (decade, rel) = worst(&after);
+        assert!(decade >= -1 && rel < 1e-5);
+    }
+
+    #[test]
+    #[cfg(feature = "table-ln")]
+    fn table() {
+        // Without the table, the error in the primary range is up to 32 ULP
+        // (relative error 2.32e-6).
+        UniformSample::with_fraction(1.0 / 2.0f32.sqrt(), 2.0f32.sqrt(), 0.5)
+            .assert(ErrorBounds::new().ulp(2), |x: f32| {
+                (super::ln(x), (x as f64).ln() as f32)
+            });
+
+        // The maximum relative error on the whole domain, measured for all
+        // single precision numbers, is attained in the primary range. It is an
+        // order of magnitude less than without the table.
+        let max_rel = 2.35e-7;
+        assert!(max_rel < 0.2 * crate::errors::LN_MAX_REL);
+
+        UniformSample::with_fraction(1.0 / 2.0f32.sqrt(), 2.0f32.sqrt(), 0.5)
+            .assert(ErrorBounds::new().rel(max_rel), |x| (super::ln(x), x.ln()));
+
+        UniformSample::builder(1e-45, 3.4e+38)
+            .count(100000)
+            .log_scale()
+            .build()
+            .assert(ErrorBounds::new().rel(max_rel), |x| (super::ln(x), x.ln()));
+    }
+
+    #[cfg(feature = "cross-check")]
+    #[test]
+    fn micromath() {
+        use micromath::F32Ext;
+
+        // The same inputs are fed to both implementations.
+        let (error, error_micromath) = UniformSample::with_count(0.5, 2.0, 100000)
+            .error_tee(|x| (super::ln(x), x.ln()), |x| (F32Ext::ln(x), x.ln()));
+        assert!(error.max_rel() <= error_micromath.max_rel());
+        assert!(error.rms() <= error_micromath.rms());
+
+        let (error, error_micromath) = UniformSample::with_count(shift_right(0.0), 3.4e+38, 100000)
+            .error_tee(|x| (super::ln(x), x.ln()), |x| (F32Ext::ln(x), x.ln()));
+        assert!(error.max_rel() <= error_micromath.max_rel());
+        assert!(error.rms() <= error_micromath.rms());
     }
 }
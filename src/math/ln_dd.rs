@@ -0,0 +1,134 @@
+use super::data::{LN_2_HI, LN_2_LO, POLY_ATANH, SQRT_2};
+use crate::float::F;
+use crate::utils::{decompose, f, poly, two_prod, two_sum};
+
+/// Computes natural logarithm of a number as an unevaluated sum of two single
+/// precision numbers hi + lo, where hi is the result rounded to single
+/// precision and lo is the correction. See [`exp_dd`] for the motivation.
+///
+/// # Notes
+///
+/// The input domain is the same as for [`ln`], that is, (0, max(f32)] ≈ (0,
+/// 3.40282347e+38], including subnormal numbers.
+///
+/// The relative error of hi + lo is below 5e-9.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::ln_dd;
+/// let (hi, lo) = ln_dd(2.0);
+/// assert_eq!(hi, core::f32::consts::LN_2);
+/// assert!((hi as f64 + lo as f64 - core::f64::consts::LN_2).abs() < 1e-9);
+/// ```
+///
+/// # Implementation details
+///
+/// The input x is decomposed and adjusted in the same way as in [`ln`], so that
+///
+/// ```plain
+///   x = y * 2^n, where 1/sqrt(2) ≤ y ≤ sqrt(2)
+/// ```
+///
+/// The logarithm of y is computed using the identity
+///
+/// ```plain
+///   ln(y) = 2 * atanh(s), where s = (y - 1) / (y + 1)
+/// ```
+///
+/// The subtraction y - 1 is exact. The quotient is represented as a pair of sh
+/// and sl, where the low part is obtained from the exact residual of the
+/// division computed using error-free transformations [`two_sum`] and
+/// [`two_prod`]. Then
+///
+/// ```plain
+///   ln(y) ≈ 2 * sh + (2 * sl * (1 + sh^2) + sh^3 * P(sh^2))
+/// ```
+///
+/// where P consists of the coefficients of the Taylor series of inverse
+/// hyperbolic tangent. Since |s| ≤ 0.172, they converge fast enough.
+///
+/// Finally, n * ln(2) is added to the result, where ln(2) is split to two parts
+/// in the same way as in [`exp_dd`].
+///
+/// [`exp_dd`]: fn.exp_dd.html
+/// [`ln`]: fn.ln.html
+/// [`two_sum`]: https://en.wikipedia.org/wiki/2Sum
+/// [`two_prod`]: https://doi.org/10.1007/BF01397083
+#[inline]
+pub fn ln_dd(x: F) -> (F, F) {
+    let (y, n) = if x < F::MIN_POSITIVE {
+        let (y, n) = decompose(x * 16777216.0);
+        (y, n - 24)
+    } else {
+        decompose(x)
+    };
+
+    let (y, n) = if y > f(SQRT_2) {
+        (y * 0.5, n + 1)
+    } else {
+        (y, n)
+    };
+
+    let num = y - 1.0;
+    let (den, den_err) = two_sum(y, 1.0);
+    let sh = num / den;
+    let (p, p_err) = two_prod(sh, den);
+    let sl = (((num - p) - p_err) - sh * den_err) / den;
+
+    let s2 = sh * sh;
+    let lny_lo = 2.0 * sl * (1.0 + s2) + sh * s2 * poly(s2, POLY_ATANH);
+
+    let n = n as F;
+    let (h, l) = two_sum(n * f(LN_2_HI), 2.0 * sh);
+    let l = l + (n * f(LN_2_LO) + lny_lo);
+    two_sum(h, l)
+}
+
+#[cfg(test)]
+mod tests {
+    use nikisas_test::prelude::*;
+    use nikisas_test::utils::shift_right;
+
+    fn errors<D: Iterator<Item = f32>>(domain: D) -> (Error<f64, f32>, Error<f64, f32>) {
+        domain.fold(
+            (Error::new(), Error::new()),
+            |(mut error_hi, mut error_pair), x| {
+                let (hi, lo) = super::ln_dd(x);
+                let real = (x as f64).ln();
+                error_hi.calculate(x, hi as f64, real);
+                error_pair.calculate(x, hi as f64 + lo as f64, real);
+                (error_hi, error_pair)
+            },
+        )
+    }
+
+    fn assert_improves(error_hi: Error<f64, f32>, error_pair: Error<f64, f32>) {
+        // The high part alone is the result rounded to single precision, the
+        // pair is much closer to the real value.
+        assert!(error_hi.max_rel() > 2e-8, "{:e}", error_hi.max_rel());
+        assert!(error_hi.max_rel() < 1e-7, "{:e}", error_hi.max_rel());
+        assert!(error_pair.max_rel() < 5e-9, "{:e}", error_pair.max_rel());
+    }
+
+    #[test]
+    fn ln_dd() {
+        assert_eq!(super::ln_dd(1.0), (0.0, 0.0));
+        assert_eq!(super::ln_dd(2.0).0, core::f32::consts::LN_2);
+
+        let (error_hi, error_pair) = errors(UniformSample::with_count(
+            1.0 / 2.0f32.sqrt(),
+            2.0f32.sqrt(),
+            100000,
+        ));
+        assert_improves(error_hi, error_pair);
+
+        let (error_hi, error_pair) = errors(
+            UniformSample::builder(shift_right(0.0), 3.4e+38)
+                .count(100000)
+                .log_scale()
+                .build(),
+        );
+        assert_improves(error_hi, error_pair);
+    }
+}
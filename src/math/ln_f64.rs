@@ -0,0 +1,98 @@
+use super::data::{LN_2_HI_F64, LN_2_LO_F64, POLY_LN_F64};
+use crate::utils::{decompose_f64, poly_f64};
+
+use core::f64::consts::SQRT_2;
+
+/// Computes natural logarithm of a double precision number.
+///
+/// # Notes
+///
+/// The input domain is (0, max(f64)] ≈ (0, 1.7976931348623157e+308], including
+/// subnormal numbers.
+///
+/// The relative error is below 1e-15 on the whole domain.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::ln_f64;
+/// assert_eq!(ln_f64(1.0), 0.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The input x is decomposed and adjusted in the same way as in [`ln`] (the
+/// subnormal numbers are multiplied by 2^54), so that
+///
+/// ```plain
+///   x = y * 2^n, where 1/sqrt(2) ≤ y ≤ sqrt(2)
+/// ```
+///
+/// Unlike in [`ln`], the logarithm of y is approximated using the identity
+///
+/// ```plain
+///   ln(y) = 2 * atanh(s), where s = (y - 1) / (y + 1)
+/// ```
+///
+/// Since |s| ≤ 0.172, the polynomial approximation converges much faster than
+/// the one for ln(1 + z) with z = y - 1. Inverse hyperbolic tangent is an odd
+/// function and so a polynomial in the following form is used:
+///
+/// ```plain
+///   ln(y) ≈ 2 * s + s^3 * P(s^2)
+/// ```
+///
+/// where P has degree 6 and its coefficients are found by interpolation at
+/// Chebyshev nodes, which is close to the minimax polynomial. The script
+/// computing them is `chebyshev.py` in the `sollya` directory.
+///
+/// Finally, n * ln(2) is added to the result, where ln(2) is split to two parts
+/// in the same way as in [`exp_f64`].
+///
+/// [`ln`]: fn.ln.html
+/// [`exp_f64`]: fn.exp_f64.html
+#[inline]
+pub fn ln_f64(x: f64) -> f64 {
+    let (y, n) = if x < f64::MIN_POSITIVE {
+        let (y, n) = decompose_f64(x * 18014398509481984.0);
+        (y, n - 54)
+    } else {
+        decompose_f64(x)
+    };
+
+    let (y, n) = if y > SQRT_2 { (y * 0.5, n + 1) } else { (y, n) };
+
+    let s = (y - 1.0) / (y + 1.0);
+    let s2 = s * s;
+    let lny = 2.0 * s + s2 * s * poly_f64(s2, &POLY_LN_F64);
+
+    let n = n as f64;
+    n * f64::from_bits(LN_2_HI_F64) + (n * f64::from_bits(LN_2_LO_F64) + lny)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds_f64;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn ln_f64() {
+        assert_eq!(super::ln_f64(1.0), 0.0);
+
+        UniformSample::with_count(
+            core::f64::consts::FRAC_1_SQRT_2,
+            core::f64::consts::SQRT_2,
+            100000,
+        )
+        .assert(error_bounds_f64(), |x| (super::ln_f64(x), x.ln()));
+
+        UniformSample::builder(f64::MIN_POSITIVE, f64::MAX)
+            .count(100000)
+            .log_scale()
+            .build()
+            .assert(error_bounds_f64(), |x| (super::ln_f64(x), x.ln()));
+
+        SubnormalSample::with_count(5e-324, 2.2e-308, 10000)
+            .assert(error_bounds_f64(), |x| (super::ln_f64(x), x.ln()));
+    }
+}
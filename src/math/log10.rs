@@ -1,7 +1,7 @@
-use super::data::LOG10_E;
+use super::data::{LOG10_E, POW10_TABLE};
 use super::ln::ln;
-use crate::float::{EPSILON, F};
-use crate::utils::{f, nearly_equal, round_small};
+use crate::float::{F, I};
+use crate::utils::{f, round_small};
 
 /// Computes decimal logarithm of a number.
 ///
@@ -29,32 +29,63 @@ use crate::utils::{f, nearly_equal, round_small};
 /// constant.
 ///
 /// We would like to get exact values when the input number is a power of ten.
-/// However, in this case it's not that straightforward as in [`pow2`]. We
-/// fallback to the following faithful determination: If the computed value of
-/// log10(x) is close to an integer, than we assume that the input was indeed a
-/// power of ten. Then we return the rounded value. This is not always true
-/// because the tolerance for "closeness" is a bit bigger than in other cases
-/// throughout this library.
+/// However, in this case it's not that straightforward as in [`pow2`]. The
+/// computed value is rounded to the nearest integer k and if the input is
+/// exactly 10^k rounded to single precision (looked up in a table of all
+/// powers of ten in the normal range), k is returned. The difference between
+/// 10^k and its single precision value is much smaller than the precision of
+/// the result, so k is the correctly rounded value of log10(x). Values that are
+/// only close to an integer are never snapped to it.
 ///
 /// [`ln`]: fn.ln.html
 /// [`pow2`]: fn.pow2.html
 pub fn log10(x: F) -> F {
     let log10x = ln(x) * f(LOG10_E);
-    let rounded = round_small(log10x) as F;
+    let k = round_small(log10x);
 
-    if nearly_equal(log10x, rounded, 16.0 * EPSILON) {
-        rounded
+    if (POW10_TABLE_MIN..=POW10_TABLE_MAX).contains(&k)
+        && x == f(POW10_TABLE[(k - POW10_TABLE_MIN) as usize])
+    {
+        k as F
     } else {
         log10x
     }
 }
 
+/// The range of exponents in the table of powers of ten.
+const POW10_TABLE_MIN: I = -37;
+const POW10_TABLE_MAX: I = 38;
+
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use crate::float::F;
     use crate::test::error_bounds;
+    use nikisas_test::float::FloatExt;
     use nikisas_test::prelude::*;
-    use nikisas_test::utils::shift_right;
+    use nikisas_test::utils::{roundtrip, scaled_identity, shift_right};
+    use std::format;
+
+    #[test]
+    fn ln_consistency() {
+        // Both sides are computed from the same ln(x), so they differ only by
+        // the rounding errors of the constant and multiplications. The powers
+        // of ten, for which the result is an exact integer, are excluded.
+        let identity = || scaled_identity(super::log10, core::f32::consts::LN_10, crate::ln);
+        let not_snapped = |x: &F| super::log10(*x).fract() != 0.0;
+
+        UniformSample::builder(shift_right(0.0), 1e+30)
+            .count(100000)
+            .log_scale()
+            .build()
+            .filter(not_snapped)
+            .assert(ErrorBounds::new().ulp(2), identity());
+
+        UniformSample::with_count(0.5, 2.0, 100000)
+            .filter(not_snapped)
+            .assert(ErrorBounds::new().ulp(2), identity());
+    }
 
     #[test]
     fn log10() {
@@ -70,4 +101,41 @@ mod tests {
         UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000)
             .assert(error_bounds(), |x| (super::log10(x), x.log10()));
     }
+
+    #[test]
+    fn no_output_snapping() {
+        // Inputs whose logarithm is close to, but not, an integer. With the
+        // former snapping of the outputs within 16 * EPSILON of an integer, the
+        // results in these regions were off by up to 8 ulps.
+        for k in -37..=38 {
+            let p = format!("1e{}", k).parse::<F>().unwrap();
+            let real = |x: F| (x as f64).log10() as F;
+
+            Exhaustive::near(p, p * 8e-6)
+                .assert(ErrorBounds::new().ulp(4), |x| (super::log10(x), real(x)));
+
+            // Powers of ten still give exact integers.
+            assert_eq!(super::log10(p), k as F, "{}", p);
+        }
+
+        // The logarithm is 3 + 8 * EPSILON / ln(10), which is more than one ulp
+        // away from 3.
+        let x = 1000.0 * (1.0 + 8.0 * F::EPSILON);
+        assert_ne!(super::log10(x), 3.0);
+        assert!(super::log10(x) > 3.0);
+
+        let x = 10.0f32.nextup().powi(3);
+        assert!((super::log10(x) - (x as f64).log10() as F).abs() <= 3.0f32.nextup() - 3.0);
+    }
+
+    #[test]
+    fn pow10_roundtrip() {
+        // The range is limited to the results of log10 which are in the domain
+        // of pow10.
+        UniformSample::builder(1e-37, 3.4e+38)
+            .count(100000)
+            .log_scale()
+            .build()
+            .assert(error_bounds(), roundtrip(super::log10, crate::pow10));
+    }
 }
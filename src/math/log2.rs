@@ -59,7 +59,22 @@ mod tests {
     use crate::float::F;
     use crate::test::error_bounds;
     use nikisas_test::prelude::*;
-    use nikisas_test::utils::shift_right;
+    use nikisas_test::utils::{roundtrip, scaled_identity, shift_right};
+
+    #[test]
+    fn ln_consistency() {
+        // Both sides are computed from the same ln(x), so they differ only by
+        // the rounding errors of the constant and multiplications.
+        let identity = || scaled_identity(super::log2, core::f32::consts::LN_2, crate::ln);
+
+        UniformSample::builder(shift_right(0.0), 1e+30)
+            .count(100000)
+            .log_scale()
+            .build()
+            .assert(ErrorBounds::new().ulp(2), identity());
+
+        UniformSample::with_count(0.5, 2.0, 100000).assert(ErrorBounds::new().ulp(2), identity());
+    }
 
     #[test]
     fn log2() {
@@ -75,4 +90,15 @@ mod tests {
         UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000)
             .assert(error_bounds(), |x| (super::log2(x), x.log2()));
     }
+
+    #[test]
+    fn pow2_roundtrip() {
+        // The range is limited to the results of log2 which are in the domain
+        // of pow2.
+        UniformSample::builder(f32::MIN_POSITIVE, 3.4e+38)
+            .count(100000)
+            .log_scale()
+            .build()
+            .assert(error_bounds(), roundtrip(super::log2, crate::pow2));
+    }
 }
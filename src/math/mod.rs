@@ -1,24 +1,134 @@
 mod data;
+mod atan;
+mod atan2;
 mod cos;
+mod cospi;
 mod cot;
 mod exp;
+mod exp_dd;
+#[cfg(not(feature = "bare-metal"))]
+mod exp_f64;
+#[cfg(not(feature = "bare-metal"))]
+mod exp_mixed;
+mod expm1;
+mod hypot;
+mod ilogb;
 mod ln;
+mod ln_dd;
+#[cfg(not(feature = "bare-metal"))]
+mod ln_f64;
 mod log10;
 mod log2;
+mod parts;
 mod pow;
 mod pow10;
 mod pow2;
+mod powu;
+mod remainder;
 mod sin;
+mod sincos;
+mod sinpi;
+mod sqrt;
+mod sqrt_signed;
 mod tan;
+mod tanpi;
+mod vec2;
 
+pub use atan::atan;
+pub use atan2::atan2;
 pub use cos::cos;
+pub use cospi::cospi;
 pub use cot::cot;
-pub use exp::exp;
+pub use exp::{exp, exp_neg};
+pub use exp_dd::exp_dd;
+#[cfg(not(feature = "bare-metal"))]
+pub use exp_f64::exp_f64;
+#[cfg(not(feature = "bare-metal"))]
+pub use exp_mixed::exp_mixed;
+pub use expm1::expm1;
+pub use hypot::hypot;
+pub use ilogb::ilogb;
 pub use ln::ln;
+pub use ln_dd::ln_dd;
+#[cfg(not(feature = "bare-metal"))]
+pub use ln_f64::ln_f64;
 pub use log10::log10;
 pub use log2::log2;
+pub use parts::{exponent, from_parts, mantissa};
 pub use pow::pow;
 pub use pow10::pow10;
 pub use pow2::pow2;
+pub use powu::powu;
+pub use remainder::remainder;
 pub use sin::sin;
+pub use sincos::sincos;
+pub use sinpi::sinpi;
+pub use sqrt::{recip_sqrt, recip_sqrt_slice, sqrt, sqrt_slice};
+pub use sqrt_signed::sqrt_signed;
 pub use tan::tan;
+pub use tanpi::tanpi;
+pub use vec2::{angle_between, from_polar, rotate, to_polar};
+
+#[cfg(test)]
+mod tests {
+    use crate::float::F;
+
+    // All functions available with `bare-metal` feature. Running the tests with
+    // `cargo test --features bare-metal` checks that the subset builds without
+    // the double precision functions.
+    #[test]
+    fn bare_metal_subset() {
+        let unary: &[fn(F) -> F] = &[
+            super::atan,
+            super::cos,
+            super::cospi,
+            super::cot,
+            super::exp,
+            super::exp_neg,
+            super::expm1,
+            super::ln,
+            super::log10,
+            super::log2,
+            super::pow10,
+            super::pow2,
+            super::recip_sqrt,
+            super::sin,
+            super::sinpi,
+            super::sqrt,
+            super::sqrt_signed,
+            super::tan,
+            super::tanpi,
+        ];
+
+        for f in unary {
+            assert!(f(0.3).is_finite());
+        }
+
+        let binary: &[fn(F, F) -> F] = &[super::atan2, super::hypot, super::pow, super::remainder];
+
+        for f in binary {
+            assert!(f(0.3, 0.7).is_finite());
+        }
+
+        let finite = |(a, b): (F, F)| a.is_finite() && b.is_finite();
+        assert!(finite(super::exp_dd(0.3)));
+        assert!(finite(super::ln_dd(0.3)));
+        assert!(finite(super::sincos(0.3)));
+        assert!(finite(super::to_polar(0.3, 0.7)));
+        assert!(finite(super::from_polar(0.3, 0.7)));
+        assert!(finite(super::rotate(0.3, 0.7, 0.5)));
+        assert!(super::angle_between((0.3, 0.7), (0.7, 0.3)).is_finite());
+        assert_eq!(super::powu(0.5, 2), 0.25);
+        assert_eq!(super::ilogb(0.3), -2);
+        assert_eq!(
+            super::from_parts(false, super::exponent(0.3), super::mantissa(0.3)),
+            0.3
+        );
+
+        let mut output = [0.0; 2];
+        super::sqrt_slice(&[4.0, 9.0], &mut output);
+        assert!(output.iter().all(|y| y.is_finite()));
+        super::recip_sqrt_slice(&[4.0, 9.0], &mut output);
+        assert!(output.iter().all(|y| y.is_finite()));
+    }
+}
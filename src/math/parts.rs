@@ -0,0 +1,133 @@
+use crate::float::{EXP_BIAS, EXP_MASK, EXP_MAX, F, I, MANTISSA_BITS, MANTISSA_MASK, SIGN_MASK, U};
+use crate::utils::clamp;
+
+/// Returns the mantissa bits stored in the machine representation of a number,
+/// without the implicit leading one of normal numbers.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::mantissa;
+/// assert_eq!(mantissa(1.0), 0);
+/// assert_eq!(mantissa(1.5), 1 << 22);
+/// ```
+///
+/// # Implementation details
+///
+/// The exponent and sign bits are masked out.
+pub fn mantissa(x: F) -> U {
+    x.to_bits() & MANTISSA_MASK
+}
+
+/// Returns the exponent stored in the machine representation of a number, with
+/// the bias subtracted.
+///
+/// # Notes
+///
+/// For zero and subnormal numbers, the result is -127, and for infinity and
+/// NaN, the result is 128. Use [`ilogb`] for the true exponent of subnormal
+/// numbers.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::exponent;
+/// assert_eq!(exponent(1.0), 0);
+/// assert_eq!(exponent(-0.25), -2);
+/// ```
+///
+/// # Implementation details
+///
+/// The exponent bits are shifted to the right and the bias is subtracted.
+///
+/// [`ilogb`]: fn.ilogb.html
+pub fn exponent(x: F) -> I {
+    ((x.to_bits() & EXP_MASK) >> MANTISSA_BITS) as I - EXP_BIAS
+}
+
+/// Constructs a number from its sign, exponent and mantissa as returned by
+/// [`exponent`] and [`mantissa`].
+///
+/// # Notes
+///
+/// The exponent is clamped to [-127, 128] and only the lowest 23 bits of the
+/// mantissa are used. Exponent -127 gives zero or subnormal numbers and
+/// exponent 128 gives infinity or NaN.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::from_parts;
+/// assert_eq!(from_parts(true, 1, 1 << 22), -3.0);
+/// assert_eq!(from_parts(false, 1000, 0), core::f32::INFINITY);
+/// ```
+///
+/// # Implementation details
+///
+/// The biased exponent and the mantissa are shifted and masked to their
+/// positions in the machine representation and combined with the sign bit.
+///
+/// [`exponent`]: fn.exponent.html
+/// [`mantissa`]: fn.mantissa.html
+pub fn from_parts(sign: bool, exponent: I, mantissa: U) -> F {
+    let e = clamp(exponent.saturating_add(EXP_BIAS), 0, EXP_MAX) as U;
+    let sbits = if sign { SIGN_MASK } else { 0 };
+    F::from_bits(sbits | (e << MANTISSA_BITS) | (mantissa & MANTISSA_MASK))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::F;
+    use nikisas_test::prelude::*;
+
+    fn round_trip(x: F) -> F {
+        super::from_parts(x.is_sign_negative(), super::exponent(x), super::mantissa(x))
+    }
+
+    #[test]
+    fn parts() {
+        assert_eq!(super::exponent(0.0), -127);
+        assert_eq!(super::exponent(1e-45), -127);
+        assert_eq!(super::mantissa(1e-45), 1);
+        assert_eq!(super::exponent(F::INFINITY), 128);
+        assert_eq!(super::exponent(F::MAX), 127);
+        assert_eq!(super::exponent(F::MIN_POSITIVE), -126);
+    }
+
+    #[test]
+    fn from_parts() {
+        assert_eq!(super::from_parts(false, 0, 0), 1.0);
+        assert_eq!(super::from_parts(false, -1000, 0), 0.0);
+        assert_eq!(super::from_parts(false, -1000, 1), 1e-45);
+        assert_eq!(super::from_parts(true, 1000, 0), F::NEG_INFINITY);
+        assert!(super::from_parts(false, 128, 1).is_nan());
+        assert_eq!(super::from_parts(false, 0, 0xffffffff), 2.0 - F::EPSILON);
+
+        // The biased exponent does not overflow for the extreme exponents.
+        assert_eq!(super::from_parts(false, i32::MAX, 0), F::INFINITY);
+        assert!(super::from_parts(true, i32::MAX, 1).is_nan());
+        assert_eq!(super::from_parts(false, i32::MIN, 0).to_bits(), 0);
+        assert_eq!(super::from_parts(true, i32::MIN, 1), -1e-45);
+    }
+
+    #[test]
+    fn round_trip_parts() {
+        for &x in &[
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            F::MAX,
+            F::MIN,
+            F::INFINITY,
+            F::NEG_INFINITY,
+        ] {
+            assert_eq!(round_trip(x).to_bits(), x.to_bits());
+        }
+
+        BitUniformSample::with_count(-3.4e+38, 3.4e+38, 100000)
+            .chain(SubnormalSample::with_count(1e-45, 1.1754942e-38, 100000))
+            .chain(SubnormalSample::with_count(-1.1754942e-38, -1e-45, 100000))
+            .for_each(|x| assert_eq!(round_trip(x).to_bits(), x.to_bits(), "{:?}", x));
+    }
+}
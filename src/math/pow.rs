@@ -2,8 +2,9 @@ use super::exp::exp;
 use super::ln::ln;
 use super::pow10::pow10;
 use super::pow2::pow2;
-use crate::float::{EPSILON, F, I};
-use crate::utils::{decompose, is_odd, nearly_equal, reduce1, scale, trunc_fract};
+use crate::float::{F, I, U};
+use crate::tolerances::{POW_BASE_TOL, POW_EXP_TOL, POW_ONE_TOL};
+use crate::utils::{abs, decompose, is_odd, nearly_equal, reduce1, scale, trunc_fract};
 
 /// Computes a number raised to a power.
 ///
@@ -29,6 +30,11 @@ use crate::utils::{decompose, is_odd, nearly_equal, reduce1, scale, trunc_fract}
 /// using a fixed-degree polynomial or rational function, but in most cases
 /// should be fine.
 ///
+/// The intermediate results are kept in the normal range whenever the result
+/// is normal. This matters on targets with flush-to-zero mode, where subnormal
+/// intermediates would be silently replaced by zero. Results in the subnormal
+/// range are not supported.
+///
 /// # Examples
 ///
 /// ```
@@ -43,8 +49,15 @@ use crate::utils::{decompose, is_odd, nearly_equal, reduce1, scale, trunc_fract}
 /// * if x is near 1, then the result is simply 1,
 /// * if p is near 1, then the result is simply x,
 /// * if p is near 0, then the result is simply 1,
-/// * if x is near 2, then specialized [`pow2`] is used, and
-/// * if x is near 10, then specialized [`pow10`] is used.
+/// * if x is near 2, then specialized [`pow2`] is used,
+/// * if x is near 10, then specialized [`pow10`] is used, and
+/// * if |p| ≥ 2^31 (including infinities), then the result is 1 for x = -1
+///   (such p is an even integer), otherwise it is infinity or zero, depending
+///   on whether |x| > 1 and the sign of p.
+///
+/// If x is an exact power of two, that is, x = 2^n, then the result is
+/// computed as 2^(pn) using [`pow2`], which avoids the error of the general
+/// reconstruction.
 ///
 /// If x is non-negative, the procedure goes like this. First, x is decomposed
 /// to real y and integer n, such that
@@ -75,6 +88,16 @@ use crate::utils::{decompose, is_odd, nearly_equal, reduce1, scale, trunc_fract}
 /// 2^qf we use [`pow2`] routine, and multiplying by 2^qi can be implemented
 /// exactly using bit manipulation of floating point number representation.
 ///
+/// The value of y^pi alone may easily overflow or underflow even if the result
+/// is representable, because it is compensated by 2^qi. The square-and-multiply
+/// loop thus keeps the intermediate results decomposed to m * 2^e, where 1 ≤ m
+/// < 2, and the exponent e is added to qi, so that the multiplication by 2^qi
+/// is effectively applied before the intermediates leave the normal range:
+///
+/// ```plain
+///   x^p = (m * y^pf * 2^qf) * 2^(e + qi)
+/// ```
+///
 /// If x is negative, the p must be an integer. This is true when z is zero,
 /// where z is the fractional part of p = k + z. If this is a case, we again
 /// decompose x into x = y * 2^n. Then the same procedure as before is used,
@@ -87,31 +110,52 @@ use crate::utils::{decompose, is_odd, nearly_equal, reduce1, scale, trunc_fract}
 /// [`pow2`]: fn.pow2.html
 /// [`pow10`]: fn.pow10.html
 pub fn pow(x: F, p: F) -> F {
-    if nearly_equal(x, 1.0, EPSILON) {
+    if nearly_equal(x, 1.0, POW_ONE_TOL) {
         return 1.0;
-    } else if nearly_equal(p, 1.0, EPSILON) {
+    } else if nearly_equal(p, 1.0, POW_EXP_TOL) {
         return x;
-    } else if nearly_equal(p, 0.0, EPSILON) {
+    } else if nearly_equal(p, 0.0, POW_EXP_TOL) {
         return 1.0;
-    } else if nearly_equal(x, 2.0, EPSILON) {
+    } else if nearly_equal(x, 2.0, POW_BASE_TOL) {
         return pow2(p);
-    } else if nearly_equal(x, 10.0, EPSILON) {
+    } else if nearly_equal(x, 10.0, POW_BASE_TOL) {
         return pow10(p);
+    } else if abs(p) >= 2147483648.0 && !x.is_nan() {
+        // The integral part of p would not fit in the integer. All such p are
+        // even integers (or infinities), so the result for x = -1 is 1.
+        // Otherwise, the result overflows, respectively underflows, since the
+        // bases near 1 are handled above.
+        return if abs(x) == 1.0 {
+            1.0
+        } else if (abs(x) > 1.0) == (p > 0.0) {
+            F::INFINITY
+        } else {
+            0.0
+        };
     }
 
     if x >= 0.0 {
         let (y, n) = decompose(x);
         let nd = n as F;
 
+        // The comparison is exact on purpose, y is 1 only for powers of two
+        // and any other y must not be ignored.
+        if y == 1.0 {
+            return pow2(p * nd);
+        }
+
         let (pi, pf) = trunc_fract(p);
         let (pni, pnf) = reduce1(p * nd);
 
-        scale(square_mul(y, pi) * exp(pf * ln(y)) * pow2(pnf), pni)
+        let (m, e) = square_mul_decomposed(y, pi);
+        scale(m * exp(pf * ln(y)) * pow2(pnf), e.saturating_add(pni))
     } else {
         let (k, z) = reduce1(p);
+        // Negative base is defined only for integral exponents.
         if z == 0.0 {
             let (y, n) = decompose(x);
-            scale(square_mul(y, k), n * k)
+            let (m, e) = square_mul_decomposed(y, k);
+            scale(m, e.saturating_add(n * k))
         } else {
             F::NAN
         }
@@ -119,13 +163,18 @@ pub fn pow(x: F, p: F) -> F {
 }
 
 pub(crate) fn square_mul(x: F, k: I) -> F {
-    let (mut k, mut base) = if k < 0 { (-k, 1.0 / x) } else { (k, x) };
+    // The magnitude is unsigned, because -k overflows for k = I::MIN.
+    let (mut k, mut base) = if k < 0 {
+        (k.unsigned_abs(), 1.0 / x)
+    } else {
+        (k as U, x)
+    };
     let mut r = 1.0;
 
     // At maximum, there are mem::size_of::<I>() * 8 iterations (32, or 64).
     // Power function is hard to approximate, let's accept this cost for now.
     loop {
-        if is_odd(k) {
+        if is_odd(k as I) {
             r *= base;
         }
 
@@ -141,6 +190,38 @@ pub(crate) fn square_mul(x: F, k: I) -> F {
     r
 }
 
+/// Computes x^k as m * 2^e, where 1 ≤ |m| < 2. The partial results are
+/// decomposed after every multiplication, so they never overflow nor underflow.
+pub(crate) fn square_mul_decomposed(x: F, k: I) -> (F, I) {
+    let (mut k, base) = if k < 0 {
+        (k.unsigned_abs(), 1.0 / x)
+    } else {
+        (k as U, x)
+    };
+    let (mut base, mut base_e) = decompose(base);
+    let (mut r, mut r_e) = (1.0, 0 as I);
+
+    loop {
+        if is_odd(k as I) {
+            let (m, e) = decompose(r * base);
+            r = m;
+            r_e = r_e.saturating_add(base_e).saturating_add(e);
+        }
+
+        k >>= 1;
+
+        if k == 0 {
+            break;
+        }
+
+        let (m, e) = decompose(base * base);
+        base = m;
+        base_e = base_e.saturating_mul(2).saturating_add(e);
+    }
+
+    (r, r_e)
+}
+
 pub(crate) fn pow_reduce(x: F) -> (I, F, bool) {
     let (k, y) = reduce1(x);
     let (y, inv) = if y < 0.0 { (-y, true) } else { (y, false) };
@@ -151,6 +232,7 @@ pub(crate) fn pow_reduce(x: F) -> (I, F, bool) {
 mod tests {
     use crate::float::F;
     use crate::test::error_bounds;
+    use nikisas_test::float::FloatExt;
     use nikisas_test::prelude::*;
     use nikisas_test::utils::{avoid, shift_right};
 
@@ -197,4 +279,151 @@ mod tests {
             })
             .assert();
     }
+
+    #[test]
+    fn delegation() {
+        UniformSample::with_count(-126.0f32, 127.0, 10000).for_each(|p| {
+            assert_eq!(
+                super::pow(2.0, p).to_bits(),
+                super::pow2(p).to_bits(),
+                "{}",
+                p
+            );
+        });
+
+        UniformSample::with_count(-37.9f32, 38.5, 10000).for_each(|p| {
+            assert_eq!(
+                super::pow(10.0, p).to_bits(),
+                super::pow10(p).to_bits(),
+                "{}",
+                p
+            );
+        });
+    }
+
+    #[test]
+    fn delegation_seams() {
+        // A few representable numbers on both sides of the bases, so that the
+        // sweep crosses the boundary of the delegation tolerance.
+        let around = |base: F| {
+            let below = (0..8).scan(base, |x, _| {
+                *x = x.nextdown();
+                Some(*x)
+            });
+            let above = (0..8).scan(base, |x, _| {
+                *x = x.nextup();
+                Some(*x)
+            });
+            below.chain(core::iter::once(base)).chain(above)
+        };
+
+        for &(base, max) in &[(2.0, 120.0), (10.0, 36.0)] {
+            around(base)
+                .fold(Error::with_bounds(error_bounds()), |error, x| {
+                    UniformSample::with_count(-max, max, 1000)
+                        .filter(avoid(0.0))
+                        .fold(error, |mut error, p| {
+                            let real = (x as f64).powf(p as f64) as F;
+                            error.calculate((x, p), super::pow(x, p), real);
+                            error
+                        })
+                })
+                .assert();
+        }
+    }
+
+    #[test]
+    fn power_of_two() {
+        for &k in &[-10, -3, -1, 3, 5, 10] {
+            let x = 2f32.powi(k);
+
+            UniformSample::with_count(-12.0, 12.0, 10000)
+                .filter(|p| {
+                    let real = 2f32.powf(k as F * p);
+                    real.is_finite() && real >= F::MIN_POSITIVE
+                })
+                .assert(ErrorBounds::new().rel(F::EPSILON), |p| {
+                    (super::pow(x, p), 2f32.powf(k as F * p))
+                });
+        }
+    }
+
+    #[test]
+    fn square_mul_decomposed() {
+        // When no intermediate result leaves the normal range, the
+        // decomposition does not change the rounding.
+        UniformSample::with_count(1.0f32, 2.0, 1000).for_each(|x| {
+            for k in -60..60 {
+                let (m, e) = super::square_mul_decomposed(x, k);
+                assert_eq!(
+                    super::scale(m, e).to_bits(),
+                    super::square_mul(x, k).to_bits(),
+                    "{}^{}",
+                    x,
+                    k
+                );
+            }
+        });
+
+        let (m, e) = super::square_mul_decomposed(1.5, 1000);
+        assert!((1.0..2.0).contains(&m));
+        assert_eq!(e, 584);
+    }
+
+    #[test]
+    fn out_of_domain() {
+        for &p in &[1e+10, F::MAX, F::INFINITY] {
+            for &x in &[2.5, 3.0, -3.0, 10.0] {
+                assert_eq!(super::pow(x, p), F::INFINITY, "{}^{}", x, p);
+                assert_eq!(super::pow(x, -p), 0.0, "{}^{}", x, -p);
+            }
+
+            for &x in &[0.3, 0.0, -0.3] {
+                assert_eq!(super::pow(x, p), 0.0, "{}^{}", x, p);
+                assert_eq!(super::pow(x, -p), F::INFINITY, "{}^{}", x, -p);
+            }
+
+            assert!(super::pow(F::NAN, p).is_nan());
+        }
+
+        // Such exponents are even integers.
+        assert_eq!(super::pow(-1.0, 1e+10), 1.0);
+        assert_eq!(super::pow(-1.0, -1e+10), 1.0);
+        assert_eq!(super::pow(-1.0, 3e+9), 1.0);
+    }
+
+    #[test]
+    fn square_mul_min() {
+        // Negating the exponent would overflow.
+        assert_eq!(super::square_mul(10.0, i32::MIN), 0.0);
+        assert_eq!(super::square_mul(0.1, i32::MIN), F::INFINITY);
+
+        let (m, e) = super::square_mul_decomposed(1.5, i32::MIN);
+        assert!((1.0..2.0).contains(&m));
+        let expected = -2147483648.0 * 1.5f64.log2();
+        assert!((e as f64 - expected).abs() <= 1e-6 * expected.abs());
+    }
+
+    #[test]
+    fn normal_intermediates() {
+        // The result is normal, but y^pi alone would be subnormal, infinite or
+        // zero.
+        let data = [
+            (0.995, -127.5),
+            (0.995, -140.25),
+            (0.99, -128.5),
+            (0.999, 10000.0),
+            (0.7, -245.5),
+            (-0.999, 10000.0),
+            (-0.99, -8191.0),
+        ];
+
+        data.iter()
+            .fold(Error::with_bounds(error_bounds()), |mut error, &(x, p)| {
+                let real = (x as f64).powf(p as f64) as F;
+                error.calculate((x, p), super::pow(x, p), real);
+                error
+            })
+            .assert();
+    }
 }
@@ -1,7 +1,8 @@
-use super::data::POLY_POW10;
+use super::data::{POLY_POW10, SQRT_10};
 use super::pow::{pow_reduce, square_mul};
-use crate::float::{EPSILON, F};
-use crate::utils::{nearly_equal, poly};
+use crate::float::F;
+use crate::tolerances::POW10_ZERO_TOL;
+use crate::utils::{f, nearly_equal, poly, trunc_fract};
 
 /// Computes 10 raised to a power.
 ///
@@ -19,9 +20,12 @@ use crate::utils::{nearly_equal, poly};
 ///
 /// # Implementation details
 ///
-/// First, the special case when x is near zero is handled such that the result
-/// is simply 1. Otherwise, the input x is reduced to an integer k and real y
-/// such that
+/// First, special cases are handled. If x is near zero, then the result is
+/// simply 1. If x is a half-integer, that is, x = k + 1/2 for an integer k, then
+/// the result is 10^k * sqrt(10), where sqrt(10) is precomputed constant and
+/// 10^k is computed as described below (for negative k, we divide by 10^-k to
+/// avoid the error of inexact 1/10). Otherwise, the input x is reduced to an
+/// integer k and real y such that
 ///
 /// ```plain
 ///   x = k + y and |y| ≤ 1/2
@@ -43,6 +47,10 @@ use crate::utils::{nearly_equal, poly};
 ///   10^y = if y ≥ 0 then 10^z else 1 / 10^z
 /// ```
 ///
+/// Unlike in [`pow2`], a separate polynomial for negative y does not help,
+/// because the error is dominated by the polynomial approximation and not by
+/// the rounding of the reciprocal.
+///
 /// The reconstruction of original value is then
 ///
 /// ```plain
@@ -52,18 +60,40 @@ use crate::utils::{nearly_equal, poly};
 /// Computation of 10^y is (transitively) done using aforementioned polynomial
 /// approximation and multiply-and-square loop algorithm is used for computation
 /// of 10^k. Note that in this case, the maximum number of iterations is limited
-/// by log2(max(|input range of x|)) < 6.
+/// by log2(max(|input range of x|)) < 6. For x near the upper limit of the
+/// domain, k = 39 and 10^k overflows, so 10^(k - 1) * (10 * 10^y) is computed
+/// instead.
+///
+/// Alternatively, 10^k could be computed as 5^k * 2^k, where multiplying by 2^k
+/// is done using bit manipulation. This does not bring any improvement in
+/// accuracy, because 5^k needs to be rounded the same way as 10^k.
+///
+/// [`pow2`]: fn.pow2.html
 pub fn pow10(p: F) -> F {
-    if nearly_equal(p, 0.0, EPSILON) {
+    if nearly_equal(p, 0.0, POW10_ZERO_TOL) {
         return 1.0;
     }
 
+    let (k, y) = trunc_fract(p);
+    if y == 0.5 {
+        return if k < 0 {
+            f(SQRT_10) / square_mul(10.0, -k)
+        } else {
+            square_mul(10.0, k) * f(SQRT_10)
+        };
+    }
+
     let (k, z, inv) = pow_reduce(p);
 
     let pow10z = 1.0 + z * poly(z, POLY_POW10);
     let pow10z = if inv { 1.0 / pow10z } else { pow10z };
 
-    square_mul(10.0, k) * pow10z
+    if k > 38 {
+        // 10^39 overflows, even though the result is representable.
+        square_mul(10.0, k - 1) * (10.0 * pow10z)
+    } else {
+        square_mul(10.0, k) * pow10z
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +101,61 @@ mod tests {
     use crate::float::F;
     use crate::test::error_bounds;
     use nikisas_test::prelude::*;
+    use nikisas_test::utils::roundtrip;
+
+    #[test]
+    fn pow10_half_integer() {
+        for k in -37..38 {
+            let x = k as F + 0.5;
+            let real = 10.0f32.powf(x);
+            assert!((super::pow10(x) - real).abs() <= 2.0 * F::EPSILON * real);
+        }
+    }
+
+    // Variant of pow10 that computes 10^k = 5^k * 2^k and multiplies by 2^k
+    // using the manipulation of the exponent bits.
+    fn pow10_scale(p: f32) -> f32 {
+        let (k, z, inv) = super::pow_reduce(p);
+
+        let pow10z = 1.0 + z * super::poly(z, super::POLY_POW10);
+        let pow10z = if inv { 1.0 / pow10z } else { pow10z };
+
+        crate::utils::scale(super::square_mul(5.0, k) * pow10z, k)
+    }
+
+    #[test]
+    fn reconstruction() {
+        let exact = |x: f32| 10.0f64.powf(x as f64) as f32;
+
+        let mul =
+            UniformSample::with_count(-37.9, 38.5, 100000).error(|x| (super::pow10(x), exact(x)));
+        let scale =
+            UniformSample::with_count(-37.9, 38.5, 100000).error(|x| (pow10_scale(x), exact(x)));
+
+        // Both approaches are equally accurate, the error is dominated by the
+        // polynomial approximation and rounding of 10^k, respectively 5^k.
+        assert!(mul.max_ulp().max(scale.max_ulp()) - mul.max_ulp().min(scale.max_ulp()) <= 1);
+        assert!((mul.mean_ulp() - scale.mean_ulp()).abs() < 0.01 * mul.mean_ulp());
+    }
+
+    // Coefficients of P for y in [-0.5, 0], found by interpolation at Chebyshev
+    // nodes.
+    const POLY_POW10_NEG: [u32; 5] = [0x40135d6a, 0x40299b39, 0x4001554d, 0x3f8b982d, 0x3ead2c94];
+
+    #[test]
+    fn negative_residual() {
+        let exact = |x: f32| 10.0f64.powf(x as f64) as f32;
+
+        let reciprocal =
+            UniformSample::with_count(-0.5, 0.0, 100000).error(|x| (super::pow10(x), exact(x)));
+        let direct = UniformSample::with_count(-0.5, 0.0, 100000)
+            .error(|x| (1.0 + x * super::poly(x, POLY_POW10_NEG), exact(x)));
+
+        // The error of the polynomial approximation on the negative side is
+        // worse relative to the smaller values of 10^y.
+        assert!(reciprocal.max_ulp() < direct.max_ulp());
+        assert!(reciprocal.mean_ulp() < direct.mean_ulp());
+    }
 
     #[test]
     fn pow10() {
@@ -88,4 +173,25 @@ mod tests {
         UniformSample::with_count(-37.9, 38.5, 10000)
             .assert(error_bounds(), |x| (super::pow10(x), 10.0f32.powf(x)));
     }
+
+    #[test]
+    fn out_of_domain() {
+        // The integer k of the reduction does not fit in 32 bits for most of
+        // these inputs.
+        for &x in &[50.0, 1e+10, f32::MAX, f32::INFINITY] {
+            assert_eq!(super::pow10(x), f32::INFINITY, "{}", x);
+            assert_eq!(super::pow10(-x), 0.0, "{}", -x);
+        }
+
+        assert!(super::pow10(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn log10_roundtrip() {
+        // Near the upper limit of the domain, 10^k alone overflows.
+        assert!((super::pow10(38.53) / 3.388442e+38 - 1.0).abs() < 1e-5);
+
+        UniformSample::with_count(-37.9, 38.5, 100000)
+            .assert(error_bounds(), roundtrip(super::pow10, crate::log10));
+    }
 }
@@ -1,7 +1,7 @@
-use super::data::POLY_POW2;
-use super::pow::pow_reduce;
-use crate::float::{EPSILON, F};
-use crate::utils::{nearly_equal, poly, scale};
+use super::data::{POLY_POW2, POLY_POW2_NEG, SQRT_2};
+use crate::float::F;
+use crate::tolerances::POW2_ZERO_TOL;
+use crate::utils::{f, nearly_equal, poly, reduce1, scale, trunc_fract};
 
 /// Computes 2 raised to a power.
 ///
@@ -9,6 +9,7 @@ use crate::utils::{nearly_equal, poly, scale};
 ///
 /// The input domain is limited to approximately [log2(min(positive f32)),
 /// log2(max(f32))] ≈ [-126.0, 127.9] due to limits of machine representation.
+/// Above the domain, the result is infinity, below it, the result is zero.
 ///
 /// # Examples
 ///
@@ -19,29 +20,30 @@ use crate::utils::{nearly_equal, poly, scale};
 ///
 /// # Implementation details
 ///
-/// First, the special case when x is near zero is handled such that the result
-/// is simply 1. Otherwise, the input x is reduced to an integer k and real y
-/// such that
+/// First, special cases are handled. If x is near zero, then the result is
+/// simply 1. If x is outside of the domain (including infinities), the result
+/// overflows, respectively underflows. If x is a half-integer, that is, x = k +
+/// 1/2 for an integer k, then the result is 2^k * sqrt(2), where sqrt(2) is
+/// precomputed constant and multiplying by 2^k is exact. Otherwise, the input x
+/// is reduced to an integer k and real y such that
 ///
 /// ```plain
 ///   x = k + y and |y| ≤ 1/2
 /// ```
 ///
-/// Let us denote z = |y|. Approximation of 2^z is done using polynomial in the
-/// form:
+/// Approximation of 2^y is done using polynomial in the form:
 ///
 /// ```plain
-///   2^z ≈ 1 + z * P(z)
+///   2^y ≈ 1 + y * P(y)
 /// ```
 ///
 /// The "prefix" corresponds to coefficients of low-degree Taylor polynomial of
-/// 2^z for z = 0 and P is found using special minimax algorithm in Sollya.
-///
-/// Now we have
-///
-/// ```plain
-///   2^y = if y ≥ 0 then 2^z else 1 / 2^z
-/// ```
+/// 2^y for y = 0. There are two sets of coefficients of P, one for y ≥ 0 found
+/// using special minimax algorithm in Sollya and one for y < 0 found by
+/// interpolation at Chebyshev nodes (using `chebyshev.py` script in the
+/// `sollya` directory). Evaluating the polynomial directly for negative y
+/// avoids the rounding error of computing 1 / 2^|y|, which is the approach
+/// used in [`pow10`].
 ///
 /// The reconstruction of original value is then
 ///
@@ -52,17 +54,31 @@ use crate::utils::{nearly_equal, poly, scale};
 /// Computation of 2^y is (transitively) done using aforementioned polynomial
 /// approximation and multiplying by 2^k can be implemented exactly using bit
 /// manipulation of floating point number representation.
+///
+/// [`pow10`]: fn.pow10.html
 pub fn pow2(p: F) -> F {
-    if nearly_equal(p, 0.0, EPSILON) {
+    if nearly_equal(p, 0.0, POW2_ZERO_TOL) {
         return 1.0;
+    } else if p >= 128.0 {
+        return F::INFINITY;
+    } else if p < -126.0 {
+        return 0.0;
     }
 
-    let (k, z, inv) = pow_reduce(p);
+    let (k, y) = trunc_fract(p);
+    if y == 0.5 {
+        return scale(f(SQRT_2), k);
+    }
 
-    let pow2z = 1.0 + z * poly(z, POLY_POW2);
-    let pow2z = if inv { 1.0 / pow2z } else { pow2z };
+    let (k, y) = reduce1(p);
 
-    scale(pow2z, k)
+    let pow2y = if y < 0.0 {
+        1.0 + y * poly(y, POLY_POW2_NEG)
+    } else {
+        1.0 + y * poly(y, POLY_POW2)
+    };
+
+    scale(pow2y, k)
 }
 
 #[cfg(test)]
@@ -70,6 +86,16 @@ mod tests {
     use crate::float::F;
     use crate::test::error_bounds;
     use nikisas_test::prelude::*;
+    use nikisas_test::utils::roundtrip;
+
+    #[test]
+    fn pow2_half_integer() {
+        for k in -126..127 {
+            let x = k as F + 0.5;
+            let real = x.exp2();
+            assert!((super::pow2(x) - real).abs() <= F::EPSILON * real);
+        }
+    }
 
     #[test]
     fn pow2() {
@@ -87,4 +113,41 @@ mod tests {
         UniformSample::with_count(-87.3, 88.7, 10000)
             .assert(error_bounds(), |x| (super::pow2(x), x.exp2()));
     }
+
+    #[test]
+    fn out_of_domain() {
+        // The integer k of the reduction does not fit in 32 bits for most of
+        // these inputs.
+        for &x in &[128.5, 1e+10, f32::MAX, f32::INFINITY] {
+            assert_eq!(super::pow2(x), f32::INFINITY, "{}", x);
+            assert_eq!(super::pow2(-x), 0.0, "{}", -x);
+        }
+
+        assert!(super::pow2(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn negative_residual() {
+        let exact = |x: f32| (x as f64).exp2() as f32;
+
+        // The previous approach which reciprocates 2^|y|.
+        let reciprocal = UniformSample::with_count(-0.5, 0.0, 100000).error(|x| {
+            let z = -x;
+            let pow2z = 1.0 + z * super::poly(z, super::POLY_POW2);
+            (1.0 / pow2z, exact(x))
+        });
+        let direct =
+            UniformSample::with_count(-0.5, 0.0, 100000).error(|x| (super::pow2(x), exact(x)));
+
+        // Both approaches are within one ULP, but the direct evaluation is
+        // correctly rounded much more often.
+        assert!(direct.max_ulp() <= reciprocal.max_ulp());
+        assert!(direct.mean_ulp() < 0.5 * reciprocal.mean_ulp());
+    }
+
+    #[test]
+    fn log2_roundtrip() {
+        UniformSample::with_count(-126.0, 127.9, 100000)
+            .assert(error_bounds(), roundtrip(super::pow2, crate::log2));
+    }
 }
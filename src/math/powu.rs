@@ -0,0 +1,78 @@
+use crate::float::F;
+
+/// Computes a number raised to a non-negative integer power.
+///
+/// # Notes
+///
+/// Compared to [`pow`], there is no need for handling negative exponents (the
+/// reciprocal of the base), so this is a bit faster and also clearer at call
+/// sites where the exponent is conceptually unsigned (e.g., a degree of a
+/// polynomial). The result is exact whenever all intermediate products are
+/// representable, which is the case for example for powers of two.
+///
+/// By convention, x^0 = 1 for every x, including 0 and NaN.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::powu;
+/// assert_eq!(powu(3.0, 4), 81.0);
+/// assert_eq!(powu(0.0, 0), 1.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The power is computed using square-and-multiply algorithm. The exponent n is
+/// processed bit by bit from the least significant one, the base is squared in
+/// every iteration and multiplied into the result if the corresponding bit is
+/// set:
+///
+/// ```plain
+///   x^n = x^(b0 * 2^0) * x^(b1 * 2^1) * ... * x^(b31 * 2^31)
+/// ```
+///
+/// There are at most 32 iterations.
+///
+/// [`pow`]: fn.pow.html
+pub fn powu(x: F, n: u32) -> F {
+    let mut n = n;
+    let mut base = x;
+    let mut r = 1.0;
+
+    while n != 0 {
+        if n & 0x1 == 0x1 {
+            r *= base;
+        }
+
+        n >>= 1;
+        base *= base;
+    }
+
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn powu() {
+        assert_eq!(super::powu(0.0, 0), 1.0);
+        assert_eq!(super::powu(f32::NAN, 0), 1.0);
+        assert_eq!(super::powu(0.0, 3), 0.0);
+        assert_eq!(super::powu(-2.0, 3), -8.0);
+        assert_eq!(super::powu(2.0, 31), 2147483648.0);
+        assert_eq!(super::powu(2.0, 128), f32::INFINITY);
+
+        for n in 0..64 {
+            UniformSample::with_count(-4.0, 4.0, 1000)
+                .filter(|x: &f32| x.powi(n as i32).is_finite())
+                .fold(Error::with_bounds(error_bounds()), |mut error, x| {
+                    error.calculate(x, super::powu(x, n), x.powi(n as i32));
+                    error
+                })
+                .assert();
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::float::{EXP_MASK, EXP_MAX, F, I, MANTISSA_BITS, MANTISSA_MASK, SIGN_MASK, U};
+
+/// Computes the IEEE remainder of a division of two numbers.
+///
+/// # Notes
+///
+/// The result r is in the interval [-|y|/2, |y|/2]. Unlike the `%` operator,
+/// which truncates the quotient toward zero, the quotient is rounded to nearest
+/// integer (ties to even). The result is exact for all inputs.
+///
+/// If y is zero, x is infinite or any of the arguments is NaN, NaN is
+/// returned. If y is infinite and x is finite, the result is x.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::remainder;
+/// assert_eq!(remainder(5.0, 2.0), 1.0);
+/// assert_eq!(remainder(5.0, 3.0), -1.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The remainder of two floating point numbers is always representable, so it
+/// can be computed exactly. Both |x| and |y| are decomposed into integer
+/// mantissas mx, my in [2^23, 2^24) and exponents ex, ey (subnormal numbers
+/// are normalized). Then the schoolbook binary long division is performed on
+/// the mantissas:
+///
+/// ```plain
+///   repeat ex - ey times:
+///     if mx ≥ my then mx = mx - my
+///     mx = 2 * mx
+///   if mx ≥ my then mx = mx - my
+/// ```
+///
+/// The lowest bit of the quotient is tracked along the way. At the end, mx *
+/// 2^ey is the remainder r of |x| / |y| with the quotient truncated toward
+/// zero, such that 0 ≤ r < |y|. If r > |y| / 2, or r = |y| / 2 and the
+/// quotient is odd, the quotient must be rounded up instead and the result is
+/// r - |y|. Finally, the sign of x is applied.
+///
+/// The loop takes up to 254 iterations for the most distant exponents. That is
+/// more cycles than a reduction using the inverse of y, but such a reduction
+/// is not exact for large quotients.
+pub fn remainder(x: F, y: F) -> F {
+    let xbits = x.to_bits();
+    let ybits = y.to_bits();
+    let ex = ((xbits & EXP_MASK) >> MANTISSA_BITS) as I;
+    let ey = ((ybits & EXP_MASK) >> MANTISSA_BITS) as I;
+
+    if ybits & !SIGN_MASK == 0 || y.is_nan() || ex == EXP_MAX {
+        return F::NAN;
+    } else if ey == EXP_MAX || xbits & !SIGN_MASK == 0 {
+        // Infinite y (NaN was handled above) or zero x.
+        return x;
+    }
+
+    let (mut mx, mut ex) = normalize(xbits, ex);
+    let (my, ey) = normalize(ybits, ey);
+    let mut odd = false;
+
+    if ex < ey - 1 {
+        // |x| < |y| / 2.
+        return x;
+    } else if ex >= ey {
+        while ex > ey {
+            if mx >= my {
+                mx -= my;
+            }
+
+            mx <<= 1;
+            ex -= 1;
+        }
+
+        odd = mx >= my;
+        if odd {
+            mx -= my;
+        }
+
+        if mx == 0 {
+            return F::from_bits(xbits & SIGN_MASK);
+        }
+
+        // Normalize the remainder back to [2^23, 2^24).
+        let shift = mx.leading_zeros() as I - 8;
+        mx <<= shift;
+        ex -= shift;
+    }
+
+    let r = denormalize(mx, ex);
+    let y = F::from_bits(ybits & !SIGN_MASK);
+
+    // Now 0 < r < |y| and the quotient is rounded to nearest, ties to even.
+    // The doubling may overflow only if r > |y| / 2 anyway.
+    let r = if 2.0 * r > y || (2.0 * r == y && odd) {
+        r - y
+    } else {
+        r
+    };
+
+    F::from_bits(r.to_bits() ^ (xbits & SIGN_MASK))
+}
+
+/// Returns the mantissa with the implicit leading one, that is, in [2^23,
+/// 2^24), and the biased exponent of a nonzero finite number. Subnormal numbers
+/// are normalized, their exponent is then less than 1.
+fn normalize(bits: U, e: I) -> (U, I) {
+    let m = bits & MANTISSA_MASK;
+
+    if e == 0 {
+        let shift = m.leading_zeros() as I - 8;
+        (m << shift, 1 - shift)
+    } else {
+        (m | (1 << MANTISSA_BITS), e)
+    }
+}
+
+/// Inverse of [`normalize`](fn.normalize.html) for positive numbers.
+fn denormalize(m: U, e: I) -> F {
+    if e > 0 {
+        F::from_bits((m & MANTISSA_MASK) | ((e as U) << MANTISSA_BITS))
+    } else {
+        F::from_bits(m >> (1 - e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    // The standard library does not provide IEEE remainder.
+    fn ieee_remainder(x: f32, y: f32) -> f32 {
+        let (x, y) = (x as f64, y as f64);
+        (x - (x / y).round_ties_even() * y) as f32
+    }
+
+    #[test]
+    fn remainder_special() {
+        assert_eq!(super::remainder(5.0, 2.0), 1.0);
+        assert_eq!(super::remainder(5.0, 3.0), -1.0);
+        assert_eq!(super::remainder(7.0, 2.0), -1.0);
+        assert_eq!(super::remainder(-5.0, 3.0), 1.0);
+        assert_eq!(super::remainder(6.0, 3.0), 0.0);
+        assert!(super::remainder(1.0, 0.0).is_nan());
+
+        // The sign of zero result is the sign of x.
+        assert_eq!(super::remainder(-6.0, 3.0).to_bits(), (-0.0f32).to_bits());
+        assert_eq!(super::remainder(0.0, 3.0).to_bits(), 0.0f32.to_bits());
+
+        // Infinities and NaN.
+        assert_eq!(super::remainder(5.0, f32::INFINITY), 5.0);
+        assert_eq!(super::remainder(-5.0, f32::NEG_INFINITY), -5.0);
+        assert_eq!(super::remainder(f32::MAX, f32::INFINITY), f32::MAX);
+        assert!(super::remainder(f32::INFINITY, 2.0).is_nan());
+        assert!(super::remainder(f32::INFINITY, f32::INFINITY).is_nan());
+        assert!(super::remainder(f32::NAN, 2.0).is_nan());
+        assert!(super::remainder(2.0, f32::NAN).is_nan());
+
+        // Ties are rounded to even quotient.
+        assert_eq!(super::remainder(2.5, 1.0), 0.5);
+        assert_eq!(super::remainder(3.5, 1.0), -0.5);
+        assert_eq!(super::remainder(-2.5, 1.0), -0.5);
+
+        // Huge quotients, the real values are computed exactly using
+        // arbitrary precision arithmetic.
+        assert_eq!(super::remainder(1e+30, 7.0), 1.0);
+        assert_eq!(super::remainder(f32::MAX, 3e-45), 0.0);
+        assert_eq!(super::remainder(3.4e+38, 0.1), -0.049792543);
+        assert_eq!(super::remainder(1e+20, 7.7), 2.0845861);
+
+        // Subnormal numbers.
+        assert_eq!(super::remainder(4e-45, 3e-45), -1e-45);
+        assert_eq!(super::remainder(1.0, 4e-45), -1e-45);
+        assert_eq!(super::remainder(1.1754942e-38, 1e-40), -4.4994e-41);
+    }
+
+    #[test]
+    fn exact() {
+        // The real value is computed exactly in double precision for
+        // quotients less than 2^28.
+        for &y in [0.3f32, 1.0, 2.5, -3.0, core::f32::consts::PI, 7.7e-40].iter() {
+            UniformSample::with_count(-1e+8 * y.abs(), 1e+8 * y.abs(), 100000).for_each(|x| {
+                // The reference does not respect the sign of zero results.
+                assert_eq!(super::remainder(x, y), ieee_remainder(x, y), "{} {}", x, y);
+            });
+        }
+    }
+
+    #[test]
+    fn remainder() {
+        for &y in [0.3f32, 1.0, 2.5, -3.0, core::f32::consts::PI].iter() {
+            UniformSample::with_count(-100.0, 100.0, 10000)
+                .inspect(|&x| assert!(super::remainder(x, y).abs() <= y.abs() / 2.0))
+                .assert(error_bounds(), |x| {
+                    (super::remainder(x, y), ieee_remainder(x, y))
+                });
+        }
+    }
+}
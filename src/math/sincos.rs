@@ -0,0 +1,104 @@
+use super::data::{POLY_COS, POLY_SIN};
+use super::sin::reduce_pi_half;
+use crate::float::F;
+use crate::tolerances::SIN_ZERO_TOL;
+use crate::utils::{abs_sgn, modulo_mask, nearly_equal, poly};
+
+/// Computes the sine and the cosine of a number in radians at once.
+///
+/// # Notes
+///
+/// The input domain is limited to approximately [-2.1e+9, 2.1e+9] due to
+/// implementation details (see [`sin`]). Outside of it, both results are NaN.
+///
+/// It is faster than calling [`sin`] and [`cos`] separately, because the
+/// argument reduction is done only once. The results are the same as computed
+/// by [`sin`] and [`cos`].
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{sincos, consts::PI};
+/// let (sin, cos) = sincos(PI);
+/// assert!(sin.abs() < 1e-7);
+/// assert_eq!(cos, -1.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The input is reduced in the same way as in [`sin`] to an integer k and real
+/// z such that
+///
+/// ```plain
+///   |x| = k * π / 2 + z and |z| ≤ π / 4
+/// ```
+///
+/// and both sin(z) and cos(z) are approximated using the same polynomials. For
+/// i = k mod 4, the results are then obtained as follows:
+///
+/// * for i = 0, sin(x) = sin(z) and cos(x) = cos(z),
+/// * for i = 1, sin(x) = cos(z) and cos(x) = -sin(z),
+/// * for i = 2, sin(x) = -sin(z) and cos(x) = -cos(z), and
+/// * for i = 3, sin(x) = -cos(z) and cos(x) = sin(z).
+///
+/// Finally, the sign of x is applied to the sine, since the sine is an odd
+/// function and the cosine is an even function.
+///
+/// [`sin`]: fn.sin.html
+/// [`cos`]: fn.cos.html
+pub fn sincos(x: F) -> (F, F) {
+    let (x, sgn) = abs_sgn(x);
+    let (k, z) = match reduce_pi_half(x) {
+        Some(reduced) => reduced,
+        None => return (F::NAN, F::NAN),
+    };
+    let i = modulo_mask(k, 0x3);
+
+    let (sinz, cosz) = if nearly_equal(z, 0.0, SIN_ZERO_TOL) {
+        (z, 1.0)
+    } else {
+        let z2 = z * z;
+        (
+            z + z2 * z * poly(z2, POLY_SIN),
+            1.0 + z2 * poly(z2, POLY_COS),
+        )
+    };
+
+    // The value of i is always in 0..=3, the last arm is thus i = 3.
+    let (sin, cos) = match i {
+        0 => (sinz, cosz),
+        1 => (cosz, -sinz),
+        2 => (-sinz, -cosz),
+        _ => (-cosz, sinz),
+    };
+
+    (sgn * sin, cos)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn sincos() {
+        assert_eq!(super::sincos(0.0), (0.0, 1.0));
+        assert_eq!(super::sincos(core::f32::consts::PI * 0.5).0, 1.0);
+        assert_eq!(super::sincos(-core::f32::consts::PI * 0.5).0, -1.0);
+        assert!(super::sincos(core::f32::consts::PI * 0.5).1.abs() < 1e-7);
+
+        UniformSample::with_count(-core::f32::consts::PI, core::f32::consts::PI, 100000)
+            .assert(error_bounds(), |x| (super::sincos(x).0, x.sin()));
+
+        UniformSample::with_count(-core::f32::consts::PI, core::f32::consts::PI, 100000)
+            .assert(error_bounds(), |x| (super::sincos(x).1, x.cos()));
+
+        UniformSample::with_count(-1e+3, 1e+3, 10000).for_each(|x| {
+            assert_eq!(super::sincos(x).0, crate::math::sin(x));
+        });
+
+        UniformSample::with_count(-2.1e+9, 2.1e+9, 10000).for_each(|x| {
+            assert_eq!(super::sincos(x), (crate::math::sin(x), crate::math::cos(x)));
+        });
+    }
+}
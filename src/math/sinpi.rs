@@ -0,0 +1,129 @@
+use super::data::{PI_HALF, PI_HALF_LO, POLY_COS, POLY_SIN};
+use crate::float::{F, I};
+use crate::utils::{abs_sgn, f, modulo_mask, poly, reduce1};
+
+/// Computes the sine of a number multiplied by π, that is, sin(π * x).
+///
+/// # Notes
+///
+/// The input domain is all finite numbers, for infinities and NaN, NaN is
+/// returned. Unlike in `sin(PI * x)`, there is no error caused by the rounding
+/// of π nor of the product. In particular, the results for integers and
+/// half-integers are exact.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::sinpi;
+/// assert_eq!(sinpi(1.0), 0.0);
+/// assert_eq!(sinpi(0.5), 1.0);
+/// ```
+///
+/// # Implementation details
+///
+/// Since the sine is an odd function, we compute it for |x| and apply the sign
+/// of x to the result. The input |x| is reduced to an integer k and real y such
+/// that
+///
+/// ```plain
+///   2 * x = k + y and |y| ≤ 1/2
+/// ```
+///
+/// Multiplying by 2 is exact and so is the subtraction of the rounded k,
+/// therefore there is no error in the reduction at all. For finite |x| ≥ 2^24,
+/// x is an even integer and the result is zero. Then
+///
+/// ```plain
+///   π * x = k * π / 2 + z, where z = π / 2 * y
+/// ```
+///
+/// and the approximation continues in the same way as in [`sin`]. The product
+/// z is computed using π/2 split to two parts (see [`tan`]), so that the error
+/// of single precision π/2 does not contribute to the result. If y = 0, the
+/// exact values (0, 1, 0, -1) are returned.
+///
+/// [`sin`]: fn.sin.html
+/// [`tan`]: fn.tan.html
+#[inline]
+pub fn sinpi(x: F) -> F {
+    let (x, sgn) = abs_sgn(x);
+    sgn * sinpi_shifted(x, 0)
+}
+
+/// Computes sin(π * x + shift * π / 2) for non-negative x.
+#[inline]
+pub(crate) fn sinpi_shifted(x: F, shift: I) -> F {
+    if x == F::INFINITY {
+        return F::NAN;
+    }
+
+    // Every finite number greater than 2^24 is an even integer.
+    let (k, y) = if x >= 16777216.0 {
+        (0, 0.0)
+    } else {
+        reduce1(2.0 * x)
+    };
+
+    let i = modulo_mask(k + shift, 0x3);
+
+    // The value of i is always in 0..=3, the last arm is thus i = 3. Matching
+    // all values avoids unreachable (but panicking) arm.
+    if y == 0.0 {
+        return match i {
+            0 => 0.0,
+            1 => 1.0,
+            2 => 0.0,
+            _ => -1.0,
+        };
+    }
+
+    let z = y * f(PI_HALF) + y * f(PI_HALF_LO);
+    let z2 = z * z;
+
+    match i {
+        0 => z + z2 * z * poly(z2, POLY_SIN),
+        1 => 1.0 + z2 * poly(z2, POLY_COS),
+        2 => -(z + z2 * z * poly(z2, POLY_SIN)),
+        _ => -(1.0 + z2 * poly(z2, POLY_COS)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    fn sinpi_f64(x: f32) -> f32 {
+        (core::f64::consts::PI * x as f64).sin() as f32
+    }
+
+    #[test]
+    fn sinpi() {
+        for k in -1000..=1000 {
+            let x = k as f32;
+            assert_eq!(super::sinpi(x), 0.0, "{}", x);
+
+            let expected = if k % 2 == 0 { 1.0 } else { -1.0 };
+            assert_eq!(super::sinpi(x + 0.5), expected, "{}", x + 0.5);
+        }
+
+        assert_eq!(super::sinpi(16777216.0), 0.0);
+        assert_eq!(super::sinpi(-4194304.5), -1.0);
+        assert!(super::sinpi(f32::INFINITY).is_nan());
+        assert!(super::sinpi(f32::NEG_INFINITY).is_nan());
+        assert!(super::sinpi(f32::NAN).is_nan());
+
+        UniformSample::with_count(-0.25, 0.25, 100000).assert(ErrorBounds::new().ulp(2), |x| {
+            (super::sinpi(x), sinpi_f64(x))
+        });
+
+        UniformSample::with_count(-1e+6, 1e+6, 100000)
+            .assert(error_bounds(), |x| (super::sinpi(x), sinpi_f64(x)));
+    }
+
+    #[test]
+    fn odd() {
+        UniformSample::with_count(0.0, 1e+6, 10000)
+            .for_each(|x| assert_eq!(super::sinpi(-x), -super::sinpi(x)));
+    }
+}
@@ -0,0 +1,581 @@
+use crate::float::{F, I, U};
+use crate::utils::{abs, decompose, scale};
+
+/// The number of Newton iterations. It is the trade-off between speed and
+/// accuracy: one iteration does not meet the 0.1% error bound, two iterations
+/// do, and three iterations give the best accuracy achievable in single
+/// precision. More iterations do not improve the result anymore.
+const ITERATIONS: usize = 3;
+
+/// The magic constant of the fast inverse square root. Its upper bits
+/// correspond to 1.5 * 2^23 * 127, that is, negating and halving the exponent,
+/// and the lower bits minimize the error of the initial guess.
+const RECIP_SQRT_MAGIC: U = 0x5f3759df;
+
+/// The number of Newton iterations for the inverse square root. One iteration
+/// gives the relative error of 1.8e-3, two iterations 4.7e-6.
+const RECIP_SQRT_ITERATIONS: usize = 2;
+
+/// Computes the square root of a number.
+///
+/// # Notes
+///
+/// For negative inputs, NaN is returned.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::sqrt;
+/// assert_eq!(sqrt(4.0), 2.0);
+/// ```
+///
+/// # Implementation details
+///
+/// First, special cases are handled. If x is zero, infinity or NaN, the result
+/// is x itself, and if x is negative, the result is NaN. Subnormal numbers are
+/// multiplied by 2^24 (which is exact) and the result is multiplied by 2^-12.
+/// Otherwise, the input x is decomposed into real y and integer n such that
+///
+/// ```plain
+///   x = y * 2^n, where 1 ≤ y < 2
+/// ```
+///
+/// If n is odd, we adjust the decomposition such that n is even:
+///
+/// ```plain
+///   y <- 2 * y
+///   n <- n - 1
+/// ```
+///
+/// Now 1 ≤ y < 4 and the square root can be reconstructed as
+///
+/// ```plain
+///   sqrt(x) = sqrt(y * 2^n) = sqrt(y) * 2^(n/2)
+/// ```
+///
+/// where multiplying by 2^(n/2) can be implemented exactly using bit
+/// manipulation of floating point number representation. The square root of y
+/// is computed using Newton's method
+///
+/// ```plain
+///   g <- (g + y / g) / 2
+/// ```
+///
+/// starting with the initial guess g = (y + 2) / 3, which is exact for y = 1
+/// and y = 4 and its relative error is less than 6% on the whole interval
+/// (the worst case is y = 2). Because the guess is derived from the reduced y
+/// and not from x itself, it is equally good for every magnitude of x and the
+/// iteration always starts in the basin of convergence.
+///
+/// If e is the relative error of g, the error after one iteration is
+/// e^2 / (2 * (1 + e)). Starting with |e| < 0.06, the errors after individual
+/// iterations are bounded by
+///
+/// ```plain
+///   1.9e-3, 1.9e-6, 1.7e-12
+/// ```
+///
+/// and so three iterations are enough for every representable input. The
+/// error of the result is then determined only by the rounding in floating
+/// point operations and it is less than the machine epsilon (about 1.2e-7),
+/// which is verified by testing all numbers in [1, 4). The measured maximum
+/// relative errors after individual iterations are
+///
+/// ```plain
+///   1.7e-3, 1.6e-6, 8.9e-8
+/// ```
+pub fn sqrt(x: F) -> F {
+    if x == 0.0 || x == F::INFINITY || x.is_nan() {
+        return x;
+    } else if x < 0.0 {
+        return F::NAN;
+    } else if x < F::MIN_POSITIVE {
+        // Subnormal numbers are normalized by 2^24 first and the result is
+        // then scaled back by 2^-12.
+        return scale(sqrt(x * 16777216.0), -12);
+    }
+
+    sqrt_normal(x)
+}
+
+/// Decomposes positive normal x into y in [1, 4) and even n such that x = y *
+/// 2^n. It does not contain any branches, so that it can be vectorized.
+#[inline]
+fn decompose_even(x: F) -> (F, I) {
+    let (y, n) = decompose(x);
+    let odd = n & 0x1;
+    (y * (1 + odd) as F, n - odd)
+}
+
+/// Computes the square root of positive normal x without handling the special
+/// cases.
+#[inline]
+fn sqrt_normal(x: F) -> F {
+    let (y, n) = decompose_even(x);
+    scale(sqrt_reduced(y, ITERATIONS), n / 2)
+}
+
+/// Computes sqrt(y) for y in [1, 4) using given number of Newton iterations.
+#[inline]
+fn sqrt_reduced(y: F, iterations: usize) -> F {
+    let mut g = (y + 2.0) / 3.0;
+
+    for _ in 0..iterations {
+        g = 0.5 * (g + y / g);
+    }
+
+    g
+}
+
+/// Computes the inverse square root of a number, that is, 1 / sqrt(x).
+///
+/// # Notes
+///
+/// For negative inputs, NaN is returned. For zero, the result is infinity.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::recip_sqrt;
+/// assert!((recip_sqrt(4.0) - 0.5).abs() < 1e-5);
+/// ```
+///
+/// # Implementation details
+///
+/// First, special cases are handled. If x is zero, the result is infinity, if
+/// x is infinity, the result is zero, and if x is negative or NaN, the result
+/// is NaN. Subnormal numbers are multiplied by 2^24 (which is exact) and the
+/// result is multiplied by 2^12. Otherwise, the input x is reduced in the same
+/// way as in [`sqrt`] to y in [1, 4) and even n and the result is reconstructed
+/// as
+///
+/// ```plain
+///   1 / sqrt(x) = 1 / sqrt(y * 2^n) = 1 / sqrt(y) * 2^(-n/2)
+/// ```
+///
+/// The initial guess is computed using the famous bit trick from Quake III
+/// Arena:
+///
+/// ```plain
+///   g = bits(0x5f3759df - (bits(y) >> 1))
+/// ```
+///
+/// Interpreting the bits of a floating point number as an integer
+/// approximates its scaled and shifted logarithm, so halving and negating it
+/// approximates the logarithm of the inverse square root. The relative error
+/// of the guess is less than 3.5%. It is then refined using Newton's method
+/// for the function 1 / g^2 - y, which does not need any division:
+///
+/// ```plain
+///   g <- g * (1.5 - 0.5 * y * g^2)
+/// ```
+///
+/// [`sqrt`]: fn.sqrt.html
+pub fn recip_sqrt(x: F) -> F {
+    if x == 0.0 {
+        return F::INFINITY;
+    } else if x == F::INFINITY {
+        return 0.0;
+    } else if x < 0.0 || x.is_nan() {
+        return F::NAN;
+    } else if x < F::MIN_POSITIVE {
+        // Subnormal numbers are normalized by 2^24 first and the result is
+        // then scaled back by 2^12.
+        return scale(recip_sqrt(x * 16777216.0), 12);
+    }
+
+    recip_sqrt_normal(x)
+}
+
+/// Computes the inverse square root of positive normal x without handling the
+/// special cases.
+#[inline]
+fn recip_sqrt_normal(x: F) -> F {
+    let (y, n) = decompose_even(x);
+    scale(recip_sqrt_reduced(y, RECIP_SQRT_ITERATIONS), -n / 2)
+}
+
+/// Computes 1 / sqrt(y) for y in [1, 4) using given number of Newton
+/// iterations.
+#[inline]
+fn recip_sqrt_reduced(y: F, iterations: usize) -> F {
+    // The special inputs in the first pass of recip_sqrt_slice, whose results
+    // are discarded anyway, also give |y| in [1, 4) after the decomposition:
+    // zeros and subnormal numbers have y in [2, 4), infinities and NaNs in
+    // [1, 2). Only the sign of negative inputs is kept in y, so it is cleared
+    // to keep the subtraction from overflowing.
+    let mut g = F::from_bits(RECIP_SQRT_MAGIC - (abs(y).to_bits() >> 1));
+
+    for _ in 0..iterations {
+        g = g * (1.5 - 0.5 * y * g * g);
+    }
+
+    g
+}
+
+/// Computes the square root of every number in `input` and stores the results
+/// to `output`.
+///
+/// # Notes
+///
+/// The results are bit for bit equal to calling [`sqrt`] on each element.
+///
+/// # Panics
+///
+/// Panics if the lengths of `input` and `output` differ.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::sqrt_slice;
+/// let mut output = [0.0; 3];
+/// sqrt_slice(&[1.0, 4.0, 9.0], &mut output);
+/// assert_eq!(output, [1.0, 2.0, 3.0]);
+/// ```
+///
+/// # Implementation details
+///
+/// The computation is done in two passes. The first pass computes the result
+/// as if every input was positive normal number. This path contains no
+/// branches (the number of Newton iterations is fixed and the adjustment of odd
+/// exponents is done arithmetically), so the compiler is able to vectorize the
+/// loop. The second pass then replaces the results for the special inputs (zero,
+/// subnormal, negative, infinity and NaN) by the result of [`sqrt`]. These are
+/// expected to be rare and so the second pass costs just the comparisons.
+///
+/// [`sqrt`]: fn.sqrt.html
+pub fn sqrt_slice(input: &[F], output: &mut [F]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "lengths of input and output differ"
+    );
+
+    for (y, &x) in output.iter_mut().zip(input) {
+        *y = sqrt_normal(x);
+    }
+
+    for (y, &x) in output.iter_mut().zip(input) {
+        if !is_positive_normal(x) {
+            *y = sqrt(x);
+        }
+    }
+}
+
+/// Computes the inverse square root of every number in `input` and stores the
+/// results to `output`.
+///
+/// # Notes
+///
+/// The results are bit for bit equal to calling [`recip_sqrt`] on each
+/// element.
+///
+/// # Panics
+///
+/// Panics if the lengths of `input` and `output` differ.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::recip_sqrt_slice;
+/// let mut output = [0.0; 2];
+/// recip_sqrt_slice(&[1.0, 4.0], &mut output);
+/// assert!((output[1] - 0.5).abs() < 1e-5);
+/// ```
+///
+/// # Implementation details
+///
+/// The same two passes are done as in [`sqrt_slice`]. The bit trick for the
+/// initial guess and the Newton iterations are branch-free, so the first pass
+/// vectorizes well.
+///
+/// [`recip_sqrt`]: fn.recip_sqrt.html
+/// [`sqrt_slice`]: fn.sqrt_slice.html
+pub fn recip_sqrt_slice(input: &[F], output: &mut [F]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "lengths of input and output differ"
+    );
+
+    for (y, &x) in output.iter_mut().zip(input) {
+        *y = recip_sqrt_normal(x);
+    }
+
+    for (y, &x) in output.iter_mut().zip(input) {
+        if !is_positive_normal(x) {
+            *y = recip_sqrt(x);
+        }
+    }
+}
+
+#[inline]
+fn is_positive_normal(x: F) -> bool {
+    x.is_normal() && x > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::test::error_bounds;
+    use nikisas_test::float::FloatExt;
+    use nikisas_test::prelude::*;
+    use nikisas_test::utils::shift_right;
+
+    #[test]
+    fn sqrt() {
+        assert_eq!(super::sqrt(0.0), 0.0);
+        assert_eq!(super::sqrt(1.0), 1.0);
+        assert_eq!(super::sqrt(4.0), 2.0);
+        assert_eq!(super::sqrt(f32::INFINITY), f32::INFINITY);
+        assert!(super::sqrt(-1.0).is_nan());
+
+        UniformSample::with_count(1.0, 4.0, 100000)
+            .assert(error_bounds(), |x| (super::sqrt(x), x.sqrt()));
+
+        UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000)
+            .assert(error_bounds(), |x| (super::sqrt(x), x.sqrt()));
+
+        UniformSample::with_count(1e-45, 1e-38, 10000)
+            .assert(error_bounds(), |x| (super::sqrt(x), x.sqrt()));
+    }
+
+    #[test]
+    fn convergence() {
+        // Every positive normal input is reduced into [1, 4), so testing all
+        // the numbers in this interval covers the whole range.
+        let error = Exhaustive::bounded(1.0, 4.0f32.nextdown()).fold(
+            Error::<f64, f32>::new(),
+            |mut error, x| {
+                error.calculate(x, super::sqrt(x) as f64, (x as f64).sqrt());
+                error
+            },
+        );
+
+        assert!(
+            error.max_rel() <= f32::EPSILON as f64,
+            "{:e}",
+            error.max_rel()
+        );
+    }
+
+    fn iterations_error(iterations: usize) -> Error<f64, f32> {
+        UniformSample::with_count(1.0, 4.0, 100000).fold(Error::new(), |mut error, x| {
+            let computed = super::sqrt_reduced(x, iterations);
+            error.calculate(x, computed as f64, (x as f64).sqrt());
+            error
+        })
+    }
+
+    #[test]
+    fn iterations() {
+        let errors = [
+            iterations_error(1),
+            iterations_error(2),
+            iterations_error(3),
+            iterations_error(4),
+        ];
+
+        for (i, error) in errors.iter().enumerate() {
+            std::println!(
+                "sqrt with {} iteration(s): relative = {:e}, root-mean-square = {:e}",
+                i + 1,
+                error.max_rel(),
+                error.rms()
+            );
+        }
+
+        // Every iteration improves the accuracy until the rounding errors
+        // dominate.
+        assert!(errors[0].max_rel() > errors[1].max_rel());
+        assert!(errors[1].max_rel() > errors[2].max_rel());
+        assert!(errors[2].max_rel() <= f32::EPSILON as f64);
+        assert!(errors[3].max_rel() <= f32::EPSILON as f64);
+
+        // Two iterations is the minimum that meets the error bounds.
+        let sqrt = |iterations| move |x: f32| (super::sqrt_reduced(x, iterations), x.sqrt());
+        UniformSample::with_count(1.0, 4.0, 100000).assert_expected_fail(error_bounds(), sqrt(1));
+        UniformSample::with_count(1.0, 4.0, 100000).assert(error_bounds(), sqrt(2));
+        assert!(errors[super::ITERATIONS - 1].max_rel() <= errors[1].max_rel());
+    }
+
+    #[test]
+    fn exponent_boundaries() {
+        // Inputs near powers of two, where the exponent of the decomposition
+        // changes and the reduced argument jumps between both ends of [1, 4).
+        let mut p = f32::MIN_POSITIVE;
+        while p.is_finite() {
+            let error =
+                Exhaustive::near(p, p * 1e-4).fold(Error::<f64, f32>::new(), |mut error, x| {
+                    error.calculate(x, super::sqrt(x) as f64, (x as f64).sqrt());
+                    error
+                });
+
+            assert!(error.max_rel() <= f32::EPSILON as f64, "{}", p);
+            p *= 2.0;
+        }
+    }
+
+    #[test]
+    fn all_magnitudes() {
+        // Each binade has the same number of machine numbers, so sampling the
+        // bits uniformly samples the exponent uniformly, from the smallest
+        // subnormal to the largest finite number.
+        let sample = || BitUniformSample::with_count(0.0f32.nextup(), f32::MAX, 1000000);
+
+        sample().assert(error_bounds(), |x| (super::sqrt(x), x.sqrt()));
+
+        // The maximum relative error for each exponent, including the
+        // subnormal numbers with exponent -127.
+        let mut bands = [0.0f64; 255];
+        for x in sample() {
+            let real = (x as f64).sqrt();
+            let rel = ((super::sqrt(x) as f64 - real) / real).abs();
+            let band = &mut bands[(crate::exponent(x) + 127) as usize];
+            *band = band.max(rel);
+        }
+
+        // Halving the exponent is exact and odd exponents are folded into the
+        // reduced argument, so no band is worse than the rounding error.
+        for (i, rel) in bands.iter().enumerate() {
+            assert!(*rel > 0.0, "exponent {} not sampled", i as i32 - 127);
+            assert!(*rel <= f32::EPSILON as f64, "exponent {}", i as i32 - 127);
+        }
+    }
+
+    #[test]
+    fn recip_sqrt() {
+        assert_eq!(super::recip_sqrt(0.0), f32::INFINITY);
+        assert_eq!(super::recip_sqrt(f32::INFINITY), 0.0);
+        assert!(super::recip_sqrt(-1.0).is_nan());
+        assert!(super::recip_sqrt(f32::NAN).is_nan());
+
+        UniformSample::with_count(1.0, 4.0, 100000)
+            .assert(error_bounds(), |x| (super::recip_sqrt(x), 1.0 / x.sqrt()));
+
+        UniformSample::with_count(shift_right(0.0), 3.4e+38, 10000)
+            .assert(error_bounds(), |x| (super::recip_sqrt(x), 1.0 / x.sqrt()));
+
+        SubnormalSample::with_count(1e-45, 1.1754942e-38, 10000)
+            .assert(error_bounds(), |x| (super::recip_sqrt(x), 1.0 / x.sqrt()));
+    }
+
+    #[test]
+    fn recip_sqrt_all_magnitudes() {
+        // The initial guess is always computed for the reduced argument in [1,
+        // 4), so the convergence of the Newton iterations does not depend on
+        // the exponent, even for subnormal numbers and near the top of the
+        // range.
+        let sample = || BitUniformSample::with_count(0.0f32.nextup(), f32::MAX, 1000000);
+
+        sample().assert(error_bounds(), |x| (super::recip_sqrt(x), 1.0 / x.sqrt()));
+
+        let mut bands = [0.0f64; 255];
+        for x in sample() {
+            let real = 1.0 / (x as f64).sqrt();
+            let rel = ((super::recip_sqrt(x) as f64 - real) / real).abs();
+            let band = &mut bands[(crate::exponent(x) + 127) as usize];
+            *band = band.max(rel);
+        }
+
+        for (i, rel) in bands.iter().enumerate() {
+            assert!(*rel > 0.0, "exponent {} not sampled", i as i32 - 127);
+            assert!(*rel <= 5e-6, "exponent {}", i as i32 - 127);
+        }
+
+        // The extremes of the range and the boundary of the subnormal numbers.
+        let min_positive = f32::MIN_POSITIVE.to_bits();
+        let max = f32::MAX.to_bits();
+
+        Exhaustive::bounded(0.0f32.nextup(), f32::from_bits(20000))
+            .chain(Exhaustive::bounded(
+                f32::from_bits(min_positive - 10000),
+                f32::from_bits(min_positive + 10000),
+            ))
+            .chain(Exhaustive::bounded(f32::from_bits(max - 20000), f32::MAX))
+            .assert(ErrorBounds::new().rel(5e-6), |x| {
+                (super::recip_sqrt(x), 1.0 / x.sqrt())
+            });
+    }
+
+    #[test]
+    fn recip_sqrt_iterations() {
+        let recip_sqrt = |iterations| {
+            move |x: f32| {
+                let computed = super::recip_sqrt_reduced(x, iterations) as f64;
+                (computed, 1.0 / (x as f64).sqrt())
+            }
+        };
+
+        let error = |iterations| {
+            UniformSample::with_count(1.0, 4.0, 100000).fold(
+                Error::<f64, f32>::new(),
+                |mut error, x| {
+                    let (computed, real) = recip_sqrt(iterations)(x);
+                    error.calculate(x, computed, real);
+                    error
+                },
+            )
+        };
+
+        // The errors of the initial guess and after individual iterations.
+        let errors = [error(0), error(1), error(2)];
+        assert!(errors[0].max_rel() < 0.035);
+        assert!(errors[1].max_rel() > 1e-3 && errors[1].max_rel() < 2e-3);
+        assert!(errors[2].max_rel() < 5e-6);
+    }
+
+    fn slice_inputs() -> std::vec::Vec<f32> {
+        let mut input = BitUniformSample::with_count(0.0f32.nextup(), f32::MAX, 1000000)
+            .collect::<std::vec::Vec<_>>();
+        input.extend_from_slice(&[
+            0.0,
+            -0.0,
+            1.0,
+            4.0,
+            -1.0,
+            f32::MIN_POSITIVE,
+            f32::MIN_POSITIVE.nextdown(),
+            f32::MAX,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+        ]);
+        input
+    }
+
+    #[test]
+    fn sqrt_slice() {
+        let input = slice_inputs();
+        let mut output = std::vec![0.0; input.len()];
+        super::sqrt_slice(&input, &mut output);
+
+        for (&x, &y) in input.iter().zip(output.iter()) {
+            assert_eq!(y.to_bits(), super::sqrt(x).to_bits(), "{}", x);
+        }
+    }
+
+    #[test]
+    fn recip_sqrt_slice() {
+        // A large batch as in normalizing many vectors at once.
+        let input = slice_inputs();
+        let expected = input
+            .iter()
+            .map(|&x| super::recip_sqrt(x))
+            .collect::<std::vec::Vec<_>>();
+
+        let mut output = std::vec![0.0; input.len()];
+        super::recip_sqrt_slice(&input, &mut output);
+
+        for ((&x, &y), &e) in input.iter().zip(output.iter()).zip(expected.iter()) {
+            assert_eq!(y.to_bits(), e.to_bits(), "{}", x);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "lengths of input and output differ")]
+    fn slice_lengths() {
+        super::sqrt_slice(&[1.0, 2.0], &mut [0.0]);
+    }
+}
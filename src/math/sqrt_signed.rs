@@ -0,0 +1,50 @@
+use super::sqrt::sqrt;
+use crate::float::F;
+use crate::utils::abs_sgn;
+
+/// Computes the signed square root of a number, that is, sgn(x) * sqrt(|x|).
+///
+/// # Notes
+///
+/// Unlike [`sqrt`], negative inputs give negative results instead of NaN. This
+/// is useful for example for signed distance fields.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::sqrt_signed;
+/// assert_eq!(sqrt_signed(-4.0), -2.0);
+/// ```
+///
+/// # Implementation details
+///
+/// The sign is split from the input, the square root is computed for the
+/// magnitude and the sign is applied back to the result. The function is thus
+/// exactly odd and its error is the same as that of [`sqrt`].
+///
+/// [`sqrt`]: fn.sqrt.html
+#[inline]
+pub fn sqrt_signed(x: F) -> F {
+    let (x, sgn) = abs_sgn(x);
+    sgn * sqrt(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+
+    #[test]
+    fn sqrt_signed() {
+        assert_eq!(super::sqrt_signed(4.0), 2.0);
+        assert_eq!(super::sqrt_signed(-4.0), -2.0);
+        assert_eq!(super::sqrt_signed(0.0).to_bits(), 0.0f32.to_bits());
+        assert_eq!(super::sqrt_signed(-0.0).to_bits(), (-0.0f32).to_bits());
+        assert_eq!(super::sqrt_signed(f32::NEG_INFINITY), f32::NEG_INFINITY);
+        assert!(super::sqrt_signed(f32::NAN).is_nan());
+
+        UniformSample::with_count(-100.0, 100.0, 100000).assert(error_bounds(), |x| {
+            (super::sqrt_signed(x), x.signum() * x.abs().sqrt())
+        });
+    }
+}
@@ -1,14 +1,16 @@
-use super::data::{PI_HALF, PI_HALF_INV, PI_QUARTER, POLY_TAN};
-use crate::float::{EPSILON, F};
-use crate::utils::{abs_sgn, f, is_even, nearly_equal, poly, reduce};
+use super::data::{PI_HALF, PI_HALF_INV, PI_HALF_LO, PI_QUARTER, POLY_COS, POLY_SIN, POLY_TAN};
+use super::sin::reduce_pi_half;
+use crate::float::{F, I};
+use crate::tolerances::TAN_QUARTER_TOL;
+use crate::utils::{abs_sgn, f, is_even, nearly_equal, poly, try_reduce};
 
 /// Computes tangent of a number.
 ///
 /// # Notes
 ///
 /// The input domain is limited to approximately [-2.1e+9, 2.1e+9] due to
-/// implementation details. Near asymptotes (-π/2, π/2) the values get quite
-/// inaccurate.
+/// implementation details. Outside of it, including infinities, the result
+/// is NaN.
 ///
 /// # Examples
 ///
@@ -26,13 +28,21 @@ use crate::utils::{abs_sgn, f, is_even, nearly_equal, poly, reduce};
 /// ```
 ///
 /// This is the reason why the input domain is limited to smaller range, because
-/// the integral part must fit into 32-bit integer.
+/// the integral part must fit into 32-bit integer. The reduction is the same
+/// double precision one as in [`sin`], so the reduced argument is accurate on
+/// the whole domain. With `f32-only` feature, it is done in single precision
+/// and, since π/2 is not exactly representable, the reduced argument is
+/// corrected by k times the difference between the real π/2 and its single
+/// precision value. Near the asymptotes, the real value of z is tiny and
+/// without the correction its relative error would be large. The single
+/// precision reduction is still meaningless for |x| larger than approximately
+/// 1e+7.
 ///
 /// Then, the approximation is split into 2 pieces. Let's consider one period of
 /// the tangent from -π/2 to π/2:
 ///
 /// * for x in [-π/4, π/4], tan(x) = tan(z),
-/// * for x in [-π/2, -π/4) ∪ (π/4, π/2], tan(x) = -1 / tan(z).
+/// * for x in [-π/2, -π/4) ∪ (π/4, π/2], tan(x) = -cos(z) / sin(z).
 ///
 /// To determine in which part of the period number x falls, i suffices to check
 /// if is even (first case) or odd (second case).
@@ -48,27 +58,51 @@ use crate::utils::{abs_sgn, f, is_even, nearly_equal, poly, reduce};
 /// The use of z^2 instead of simply z is due to the fact that the tangent is an
 /// odd function (z^3 multiplier before P(z^2) is important).
 ///
-/// There is also a special case when |z| is near π/4. Depending on the sign of
-/// z, the exact values of tan(z) are 1, respectively -1. We return them without
-/// employing any approximation.
+/// In the second case, sine and cosine of z are approximated using the same
+/// polynomials as in [`sin`] and their ratio is returned. Near the asymptotes,
+/// the large value then comes from the small denominator sin(z), which is
+/// accurate, instead of amplifying the error of tan(z) by the reciprocal.
+///
+/// There is also a special case when |z| is near π/4, that is, x is near an odd
+/// multiple of π/4. In the first case, the exact values of tan(z) are 1,
+/// respectively -1, depending on the sign of z. In the second case, the
+/// reciprocal flips the sign and so the exact values of -1 / tan(z) are -1,
+/// respectively 1. We return them without employing any approximation.
+///
+/// [`sin`]: fn.sin.html
 pub fn tan(x: F) -> F {
-    let (k, z) = reduce(x, f(PI_HALF), f(PI_HALF_INV));
+    let reduced = if cfg!(feature = "f32-only") {
+        try_reduce(x, f(PI_HALF), f(PI_HALF_INV)).map(|(k, z)| (k, z - k as F * f(PI_HALF_LO)))
+    } else {
+        reduce_pi_half(x)
+    };
+
+    match reduced {
+        Some((k, z)) => tan_reduced(k, z),
+        None => F::NAN,
+    }
+}
+
+/// Computes tan(k * π/2 + z) for the reduced argument z.
+#[inline]
+fn tan_reduced(k: I, z: F) -> F {
     let (z_abs, z_sgn) = abs_sgn(z);
 
-    if nearly_equal(z_abs, f(PI_QUARTER), EPSILON) {
-        if z_sgn == 1.0 {
-            1.0
+    if nearly_equal(z_abs, f(PI_QUARTER), TAN_QUARTER_TOL) {
+        if is_even(k) {
+            z_sgn
         } else {
-            -1.0
+            -z_sgn
         }
     } else {
         let z2 = z * z;
-        let tanz = z + z2 * z * poly(z2, POLY_TAN);
 
         if is_even(k) {
-            tanz
+            z + z2 * z * poly(z2, POLY_TAN)
         } else {
-            -1.0 / tanz
+            let sinz = z + z2 * z * poly(z2, POLY_SIN);
+            let cosz = 1.0 + z2 * poly(z2, POLY_COS);
+            -cosz / sinz
         }
     }
 }
@@ -79,6 +113,38 @@ mod tests {
     use nikisas_test::prelude::*;
     use nikisas_test::utils::{avoid_odd_mults, shift_left, shift_right};
 
+    fn tan_sincos(x: f32) -> f32 {
+        let (sin, cos) = crate::sincos(x);
+        sin / cos
+    }
+
+    #[test]
+    fn sincos_ratio() {
+        // Away from the poles, both implementations are fine.
+        UniformSample::with_count(-1.5, 1.5, 100000)
+            .assert(error_bounds(), |x| (super::tan(x), x.tan()));
+        UniformSample::with_count(-1.5, 1.5, 100000)
+            .assert(error_bounds(), |x| (tan_sincos(x), x.tan()));
+
+        // Near the poles, the ratio is accurate only if the reduced argument is
+        // corrected by the low part of π/2. The reduction in sincos does that
+        // unless it is done in single precision.
+        let near_poles = || {
+            UniformSample::with_count(
+                shift_right(-core::f32::consts::PI / 2.0),
+                shift_left(core::f32::consts::PI / 2.0),
+                100000,
+            )
+        };
+
+        near_poles().assert(error_bounds(), |x| (super::tan(x), x.tan()));
+        if cfg!(feature = "f32-only") {
+            near_poles().assert_expected_fail(error_bounds(), |x| (tan_sincos(x), x.tan()));
+        } else {
+            near_poles().assert(error_bounds(), |x| (tan_sincos(x), x.tan()));
+        }
+    }
+
     #[test]
     fn tan() {
         assert_eq!(super::tan(0.0), 0.0);
@@ -92,8 +158,41 @@ mod tests {
         )
         .assert(error_bounds(), |x| (super::tan(x), x.tan()));
 
-        UniformSample::with_count(-2.1e+9, 2.1e+9, 10000)
-            .filter(avoid_odd_mults(core::f32::consts::PI / 2.0))
-            .assert(error_bounds(), |x| (super::tan(x), x.tan()));
+        // The argument reduction is imprecise for large inputs unless it is
+        // done in double precision.
+        let full_range = || {
+            UniformSample::with_count(-2.1e+9, 2.1e+9, 10000)
+                .filter(avoid_odd_mults(core::f32::consts::PI / 2.0))
+        };
+
+        if cfg!(feature = "f32-only") {
+            full_range().assert_expected_fail(error_bounds(), |x| (super::tan(x), x.tan()));
+        } else {
+            full_range().assert(error_bounds(), |x| (super::tan(x), x.tan()));
+        }
+    }
+
+    #[test]
+    fn odd_quarter_multiples() {
+        for &(m, expected) in &[(1.0, 1.0), (3.0, -1.0), (5.0, 1.0), (7.0, -1.0)] {
+            for &sgn in &[1.0, -1.0] {
+                let x = sgn * m * core::f32::consts::FRAC_PI_4;
+                let y = super::tan(x);
+
+                // The multiples of π/4 in single precision are not exact, so the
+                // real value may slightly differ from ±1.
+                assert!((y - sgn * expected).abs() < 1e-5, "{}", x);
+                assert!((y as f64 - (x as f64).tan()).abs() < 1e-6, "{}", x);
+            }
+        }
+
+        // The special case in both branches, including the odd k, for which
+        // the reciprocal flips the sign.
+        let quarter = crate::utils::f(super::PI_QUARTER);
+        for k in -4..4 {
+            let expected = if crate::utils::is_even(k) { 1.0 } else { -1.0 };
+            assert_eq!(super::tan_reduced(k, quarter), expected, "{}", k);
+            assert_eq!(super::tan_reduced(k, -quarter), -expected, "{}", k);
+        }
     }
 }
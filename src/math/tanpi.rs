@@ -0,0 +1,67 @@
+use super::sinpi::sinpi_shifted;
+use crate::float::F;
+use crate::utils::abs_sgn;
+
+/// Computes the tangent of a number multiplied by π, that is, tan(π * x).
+///
+/// # Notes
+///
+/// The input domain is all finite numbers, for infinities and NaN, NaN is
+/// returned. The results for integers are exact zeros and the results for
+/// half-integers, which are the poles of the tangent, are infinities.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::tanpi;
+/// assert_eq!(tanpi(1.0), 0.0);
+/// assert_eq!(tanpi(0.5), f32::INFINITY);
+/// ```
+///
+/// # Implementation details
+///
+/// The tangent is an odd function, so it is computed for |x| and the sign of x
+/// is applied to the result. The sine and the cosine of π * |x| are computed as
+/// in [`sinpi`] and [`cospi`] and the result is their ratio. Since both are
+/// exact for integers and half-integers, so is the ratio.
+///
+/// [`sinpi`]: fn.sinpi.html
+/// [`cospi`]: fn.cospi.html
+#[inline]
+pub fn tanpi(x: F) -> F {
+    let (x, sgn) = abs_sgn(x);
+    sgn * (sinpi_shifted(x, 0) / sinpi_shifted(x, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::error_bounds;
+    use nikisas_test::prelude::*;
+    use nikisas_test::utils::avoid_odd_mults;
+
+    fn tanpi_f64(x: f32) -> f32 {
+        (core::f64::consts::PI * x as f64).tan() as f32
+    }
+
+    #[test]
+    fn tanpi() {
+        for k in -1000..=1000 {
+            let x = k as f32;
+            assert_eq!(super::tanpi(x), 0.0, "{}", x);
+            assert!(super::tanpi(x + 0.5).is_infinite(), "{}", x + 0.5);
+        }
+
+        assert_eq!(super::tanpi(0.5), f32::INFINITY);
+        assert_eq!(super::tanpi(-0.5), f32::NEG_INFINITY);
+        assert!(super::tanpi(f32::INFINITY).is_nan());
+        assert!(super::tanpi(f32::NEG_INFINITY).is_nan());
+
+        UniformSample::with_count(-0.25, 0.25, 100000).assert(ErrorBounds::new().ulp(4), |x| {
+            (super::tanpi(x), tanpi_f64(x))
+        });
+
+        UniformSample::with_count(-1e+3, 1e+3, 100000)
+            .filter(avoid_odd_mults(0.5))
+            .assert(error_bounds(), |x| (super::tanpi(x), tanpi_f64(x)));
+    }
+}
@@ -0,0 +1,196 @@
+use super::atan2::atan2;
+use super::hypot::hypot;
+use super::sincos::sincos;
+use crate::float::F;
+
+/// Rotates a 2D vector (x, y) by an angle in radians counterclockwise.
+///
+/// # Notes
+///
+/// The input domain of the angle is limited in the same way as in [`sincos`].
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{rotate, consts::PI};
+/// let (x, y) = rotate(1.0, 0.0, PI);
+/// assert_eq!(x, -1.0);
+/// assert!(y.abs() < 1e-7);
+/// ```
+///
+/// # Implementation details
+///
+/// The sine and the cosine of the angle are computed at once using [`sincos`]
+/// routine and the vector is multiplied by the rotation matrix:
+///
+/// ```plain
+///   x' = x * cos(a) - y * sin(a)
+///   y' = x * sin(a) + y * cos(a)
+/// ```
+///
+/// [`sincos`]: fn.sincos.html
+pub fn rotate(x: F, y: F, angle: F) -> (F, F) {
+    let (sin, cos) = sincos(angle);
+    (x * cos - y * sin, x * sin + y * cos)
+}
+
+/// Computes the signed angle in radians from the first 2D vector to the second
+/// one. The result is in the interval [-π, π] and it is positive if the
+/// rotation from the first vector to the second is counterclockwise.
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{angle_between, consts::PI};
+/// assert_eq!(angle_between((1.0, 0.0), (0.0, 2.0)), 0.5 * PI);
+/// ```
+///
+/// # Implementation details
+///
+/// The angle is computed as
+///
+/// ```plain
+///   angle = atan2(u × v, u · v)
+/// ```
+///
+/// using [`atan2`] routine, where u × v = |u| |v| sin(angle) is the cross
+/// product and u · v = |u| |v| cos(angle) is the dot product. This is more
+/// robust than computing acos of the dot product of normalized vectors, which
+/// loses precision for nearly parallel vectors and does not need the
+/// normalization.
+///
+/// [`atan2`]: fn.atan2.html
+pub fn angle_between(u: (F, F), v: (F, F)) -> F {
+    let cross = u.0 * v.1 - u.1 * v.0;
+    let dot = u.0 * v.0 + u.1 * v.1;
+    atan2(cross, dot)
+}
+
+/// Converts a 2D vector (x, y) from Cartesian to polar coordinates (r, θ). The
+/// angle is in radians in the interval [-π, π].
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{to_polar, consts::PI};
+/// assert_eq!(to_polar(0.0, 2.0), (2.0, 0.5 * PI));
+/// ```
+///
+/// # Implementation details
+///
+/// The radius is computed using [`hypot`] routine, so it does not overflow nor
+/// underflow, and the angle is computed using [`atan2`] routine:
+///
+/// ```plain
+///   r = sqrt(x^2 + y^2)
+///   θ = atan2(y, x)
+/// ```
+///
+/// [`hypot`]: fn.hypot.html
+/// [`atan2`]: fn.atan2.html
+pub fn to_polar(x: F, y: F) -> (F, F) {
+    (hypot(x, y), atan2(y, x))
+}
+
+/// Converts a 2D vector (r, θ) from polar to Cartesian coordinates (x, y). The
+/// angle is in radians.
+///
+/// # Notes
+///
+/// The input domain of the angle is limited in the same way as in [`sincos`].
+///
+/// # Examples
+///
+/// ```
+/// use nikisas::{from_polar, consts::PI};
+/// let (x, y) = from_polar(2.0, PI);
+/// assert_eq!(x, -2.0);
+/// assert!(y.abs() < 1e-6);
+/// ```
+///
+/// # Implementation details
+///
+/// The sine and the cosine of the angle are computed at once using [`sincos`]
+/// routine:
+///
+/// ```plain
+///   x = r * cos(θ)
+///   y = r * sin(θ)
+/// ```
+///
+/// [`sincos`]: fn.sincos.html
+pub fn from_polar(r: F, theta: F) -> (F, F) {
+    let (sin, cos) = sincos(theta);
+    (r * cos, r * sin)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{ABS_ERROR, REL_ERROR};
+    use crate::utils::nearly_equal;
+
+    #[test]
+    fn rotate() {
+        let pi = core::f32::consts::PI;
+
+        let (x, y) = super::rotate(1.0, 0.0, pi / 2.0);
+        assert!(nearly_equal(x, 0.0, ABS_ERROR));
+        assert!(nearly_equal(y, 1.0, ABS_ERROR));
+
+        let (x, y) = super::rotate(3.0, 4.0, -pi);
+        assert!(nearly_equal(x, -3.0, ABS_ERROR));
+        assert!(nearly_equal(y, -4.0, ABS_ERROR));
+
+        // Rotation preserves the length of the vector.
+        for i in 0..100 {
+            let (x, y) = super::rotate(3.0, 4.0, i as f32 * 0.1);
+            assert!(nearly_equal(x * x + y * y, 25.0, 25.0 * REL_ERROR));
+        }
+    }
+
+    #[test]
+    fn angle_between() {
+        let pi = core::f32::consts::PI;
+
+        assert_eq!(super::angle_between((1.0, 0.0), (0.0, 1.0)), pi / 2.0);
+        assert_eq!(super::angle_between((0.0, 1.0), (1.0, 0.0)), -pi / 2.0);
+        assert_eq!(super::angle_between((2.0, 2.0), (-3.0, 3.0)), pi / 2.0);
+        assert_eq!(super::angle_between((1.0, 0.0), (-1.0, 0.0)), pi);
+        assert_eq!(super::angle_between((1.0, 1.0), (3.0, 3.0)), 0.0);
+
+        for i in -31..32 {
+            let angle = i as f32 * 0.1;
+            let v = super::rotate(2.0, 1.0, angle);
+            assert!(nearly_equal(
+                super::angle_between((2.0, 1.0), v),
+                angle,
+                ABS_ERROR
+            ));
+        }
+    }
+
+    #[test]
+    fn polar() {
+        let pi = core::f32::consts::PI;
+
+        assert_eq!(super::to_polar(0.0, 0.0), (0.0, 0.0));
+        assert_eq!(super::to_polar(3.0, 4.0).0, 5.0);
+        assert_eq!(super::to_polar(-1.0, 0.0), (1.0, pi));
+        assert_eq!(super::to_polar(-1.0, -0.0), (1.0, -pi));
+        assert_eq!(super::to_polar(0.0, -1.0), (1.0, -pi / 2.0));
+
+        // Points in all four quadrants, including the axes and the branch cut
+        // of atan2 at the negative x-axis.
+        for i in -20..=20 {
+            for j in -20..=20 {
+                let (x, y) = (i as f32 * 0.37, j as f32 * 0.53);
+                let (r, theta) = super::to_polar(x, y);
+                assert!(theta.abs() <= pi);
+
+                let (xr, yr) = super::from_polar(r, theta);
+                assert!(nearly_equal(xr, x, r * REL_ERROR), "({}, {})", x, y);
+                assert!(nearly_equal(yr, y, r * REL_ERROR), "({}, {})", x, y);
+            }
+        }
+    }
+}
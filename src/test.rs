@@ -7,6 +7,50 @@ pub(crate) const REL_ERROR: F = 0.001;
 /// Corresponds to precision up to 4 decimal points.
 pub(crate) const ABS_ERROR: F = 0.00005;
 
-pub(crate) fn error_bounds() -> ErrorBounds<f32> {
+/// Corresponds to 1e-13 % error, appropriate for double precision.
+#[cfg(not(feature = "bare-metal"))]
+pub(crate) const REL_ERROR_F64: f64 = 1e-15;
+
+pub(crate) fn error_bounds<In>() -> ErrorBounds<f32, In> {
     ErrorBounds::new().rel(REL_ERROR).abs(ABS_ERROR)
 }
+
+#[cfg(not(feature = "bare-metal"))]
+pub(crate) fn error_bounds_f64<In>() -> ErrorBounds<f64, In> {
+    ErrorBounds::new().rel(REL_ERROR_F64)
+}
+
+/// Allowed growth of the errors over the baselines, relative to them. It
+/// accommodates small differences between platforms.
+pub(crate) const BASELINE_TOL: F = 0.05;
+
+/// Measured errors of a function on its entire domain. They match the table in
+/// the crate documentation and guard against silent accuracy regressions,
+/// which the coarse [`error_bounds`] would not catch.
+///
+/// [`error_bounds`]: fn.error_bounds.html
+pub(crate) struct Baseline {
+    pub max_rel: F,
+    pub rms: F,
+}
+
+pub(crate) const EXP_BASELINE: Baseline = Baseline {
+    max_rel: 4.19e-6,
+    rms: 1.39e-6,
+};
+
+pub(crate) const LN_BASELINE: Baseline = Baseline {
+    max_rel: 2.32e-6,
+    rms: 7.98e-8,
+};
+
+pub(crate) const SIN_BASELINE: Baseline = Baseline {
+    max_rel: 1.25e-7,
+    rms: 3.55e-8,
+};
+
+pub(crate) fn baseline_bounds<In>(baseline: Baseline) -> ErrorBounds<f32, In> {
+    ErrorBounds::new()
+        .rel(baseline.max_rel * (1.0 + BASELINE_TOL))
+        .rms(baseline.rms * (1.0 + BASELINE_TOL))
+}
@@ -0,0 +1,33 @@
+//! Tolerances for detecting the special cases.
+//!
+//! For some inputs, the exact value of the function is known and it is returned
+//! without employing any approximation. The argument is snapped to the special
+//! value if it is within the tolerance of it. The tolerances are public so that
+//! tests can deliberately target both the special-case and the general
+//! branches.
+
+use crate::float::{EPSILON, F};
+
+/// Tolerance of x around 0 in exp(x) = 1.
+pub const EXP_ZERO_TOL: F = EPSILON;
+/// Tolerance of x around 1 in exp(x) = e. It is only half of the machine
+/// epsilon, because the approximation at 1 - EPSILON already differs from e by
+/// two units in the last place.
+pub const EXP_ONE_TOL: F = 0.5 * EPSILON;
+/// Tolerance of x around e in ln(x) = 1.
+pub const LN_E_TOL: F = EPSILON;
+/// Tolerance of p around 0 in 2^p = 1.
+pub const POW2_ZERO_TOL: F = EPSILON;
+/// Tolerance of p around 0 in 10^p = 1.
+pub const POW10_ZERO_TOL: F = EPSILON;
+/// Tolerance of x around 1 in x^p = 1.
+pub const POW_ONE_TOL: F = EPSILON;
+/// Tolerance of p around 1 in x^p = x and around 0 in x^p = 1.
+pub const POW_EXP_TOL: F = EPSILON;
+/// Tolerance of x around 2 and 10 for which x^p is delegated to pow2 and
+/// pow10, respectively.
+pub const POW_BASE_TOL: F = EPSILON;
+/// Tolerance of the reduced argument z around 0 in sin(z) = z and cos(z) = 1.
+pub const SIN_ZERO_TOL: F = EPSILON;
+/// Tolerance of the reduced argument |z| around π/4 in tan(z) = ±1.
+pub const TAN_QUARTER_TOL: F = EPSILON;
@@ -12,14 +12,14 @@ pub fn extract_bits(x: F, mask: U, shift: U) -> U {
 }
 
 /// Returns absolute value of x.
-pub fn abs(x: F) -> F {
+pub const fn abs(x: F) -> F {
     let xbits = x.to_bits();
     let ybits = xbits & !SIGN_MASK;
     F::from_bits(ybits)
 }
 
 /// Returns absolute value and sign of x.
-pub fn abs_sgn(x: F) -> (F, F) {
+pub const fn abs_sgn(x: F) -> (F, F) {
     let xbits = x.to_bits();
 
     let ybits = xbits & !SIGN_MASK;
@@ -32,12 +32,64 @@ pub fn abs_sgn(x: F) -> (F, F) {
 
 /// Rounds x to nearest 32-bit integer. Hence, it only works for the doubles
 /// whose nearest integer fits in a 32-bit machine signed integer.
-pub fn round_small(x: F) -> I {
-    let t = (x as f64) + ROUND_ADD;
+///
+/// If `f32-only` feature is enabled, the rounding is done without any double
+/// precision arithmetic.
+pub const fn round_small(x: F) -> I {
+    if cfg!(feature = "f32-only") {
+        round_small_f32(x)
+    } else {
+        round_small_f64(x)
+    }
+}
+
+/// Rounds x to nearest 32-bit integer like [`round_small`](fn.round_small.html),
+/// but the inputs whose nearest integer does not fit in a 32-bit machine signed
+/// integer are saturated to `I::MIN`, respectively `I::MAX`, instead of
+/// producing wrapped garbage. NaN is rounded to zero, consistently with `as`
+/// cast.
+pub const fn saturating_round(x: F) -> I {
+    // -2^31 is exactly representable and rounded correctly.
+    if x < -2147483648.0 {
+        I::MIN
+    } else if x >= 2147483648.0 {
+        I::MAX
+    } else if x.is_nan() {
+        0
+    } else {
+        round_small(x)
+    }
+}
+
+/// Rounds x to nearest 32-bit integer using double precision. Adding 2^52 +
+/// 2^51 makes the double precision number to round x to an integer which can be
+/// then read in the lower bits of its mantissa.
+pub const fn round_small_f64(x: F) -> I {
+    round_f64(x as f64)
+}
+
+/// Rounds double precision x to nearest 32-bit integer. See
+/// [`round_small_f64`](fn.round_small_f64.html).
+pub const fn round_f64(x: f64) -> I {
+    let t = x + ROUND_ADD;
     let tbits = t.to_bits();
     (tbits & ROUND_MASK) as I
 }
 
+/// Rounds x to nearest 32-bit integer using only single precision. Adding and
+/// subtracting 2^23 to |x| rounds it to an integer, because there is no room
+/// for fractional digits in single precision numbers of this magnitude. Numbers
+/// greater than 2^23 are already integers.
+pub const fn round_small_f32(x: F) -> I {
+    let (y, s) = abs_sgn(x);
+
+    if y >= ROUND_ADD_F32 {
+        x as I
+    } else {
+        (s * ((y + ROUND_ADD_F32) - ROUND_ADD_F32)) as I
+    }
+}
+
 /// Decomposes x into real f and integer n such that
 ///
 /// ```plain
@@ -46,7 +98,7 @@ pub fn round_small(x: F) -> I {
 ///
 /// Since this is the machine representation of floating point number, this
 /// decomposition is exact.
-pub fn decompose(x: F) -> (F, I) {
+pub const fn decompose(x: F) -> (F, I) {
     let xbits = x.to_bits();
 
     let fbits = xbits & !EXP_MASK;
@@ -58,8 +110,22 @@ pub fn decompose(x: F) -> (F, I) {
     (F::from_bits(fbits), nbits)
 }
 
+/// Double precision version of [`decompose`](fn.decompose.html).
+#[cfg(not(feature = "bare-metal"))]
+pub fn decompose_f64(x: f64) -> (f64, I) {
+    let xbits = x.to_bits();
+
+    let fbits = xbits & !EXP_MASK_F64;
+    let fbits = fbits | (EXP_BIAS_F64 as u64) << MANTISSA_BITS_F64;
+
+    let nbits = xbits & EXP_MASK_F64;
+    let nbits = (nbits >> MANTISSA_BITS_F64) as I - EXP_BIAS_F64;
+
+    (f64::from_bits(fbits), nbits)
+}
+
 /// Restricts a value to a certain interval.
-pub fn clamp(x: I, min: I, max: I) -> I {
+pub const fn clamp(x: I, min: I, max: I) -> I {
     if x < min {
         min
     } else if x > max {
@@ -70,17 +136,30 @@ pub fn clamp(x: I, min: I, max: I) -> I {
 }
 
 /// Multiplies x by 2^n.
-pub fn scale(x: F, n: I) -> F {
+pub const fn scale(x: F, n: I) -> F {
     let xbits = x.to_bits();
     let ebits = xbits & EXP_MASK;
     let e = (ebits >> MANTISSA_BITS) as I;
-    let e = clamp(e + n, 0, EXP_MAX);
+    let e = clamp(e.saturating_add(n), 0, EXP_MAX);
     let ebits = (e << MANTISSA_BITS) as U;
     let xbits = xbits & !EXP_MASK;
     let xbits = xbits | ebits;
     F::from_bits(xbits)
 }
 
+/// Double precision version of [`scale`](fn.scale.html).
+#[cfg(not(feature = "bare-metal"))]
+pub fn scale_f64(x: f64, n: I) -> f64 {
+    let xbits = x.to_bits();
+    let ebits = xbits & EXP_MASK_F64;
+    let e = (ebits >> MANTISSA_BITS_F64) as I;
+    let e = clamp(e.saturating_add(n), 0, EXP_MAX_F64);
+    let ebits = (e as u64) << MANTISSA_BITS_F64;
+    let xbits = xbits & !EXP_MASK_F64;
+    let xbits = xbits | ebits;
+    f64::from_bits(xbits)
+}
+
 /// Decomposes x into integer k and real y such that
 ///
 /// ```plain
@@ -89,14 +168,35 @@ pub fn scale(x: F, n: I) -> F {
 ///
 /// It must hold that cst_inv = 1 / cst (explicit inverse is required because it
 /// is more precise to compute the inverse of a number that cannot be stored in
-/// finite precision and then round it to nearest).
-pub fn reduce(x: F, cst: F, cst_inv: F) -> (I, F) {
-    let k = round_small(x * cst_inv);
+/// finite precision and then round it to nearest). In debug builds, it is
+/// asserted that their product is within one ulp of 1, unless the inverse is
+/// not a normal number and so cannot be precise anyway.
+pub const fn reduce(x: F, cst: F, cst_inv: F) -> (I, F) {
+    debug_assert!(
+        !cst_inv.is_normal() || nearly_equal(cst * cst_inv, 1.0, EPSILON),
+        "cst_inv is not the inverse of cst"
+    );
+
+    let k = saturating_round(x * cst_inv);
     let kd = k as F;
     let y = x - kd * cst;
     (k, y)
 }
 
+/// Like [`reduce`](fn.reduce.html), but returns `None` if the integer k does
+/// not fit in a 32-bit machine signed integer (or x is not finite) instead of
+/// saturating it, in which case the reduction would be meaningless.
+pub const fn try_reduce(x: F, cst: F, cst_inv: F) -> Option<(I, F)> {
+    let q = x * cst_inv;
+    // -2^31 is exactly representable and rounded correctly. The comparisons
+    // are false for NaN.
+    if q >= -2147483648.0 && q < 2147483648.0 {
+        Some(reduce(x, cst, cst_inv))
+    } else {
+        None
+    }
+}
+
 /// Optimized version of reduce(x, 1, 1), that is, it decomposes x into integer
 /// k and real y such that
 ///
@@ -107,7 +207,7 @@ pub fn reduce(x: F, cst: F, cst_inv: F) -> (I, F) {
 /// For decomposing the number into its integral and fractional parts, use
 /// `trunc_fract`.
 pub fn reduce1(x: F) -> (I, F) {
-    let k = round_small(x);
+    let k = saturating_round(x);
     let kd = k as F;
 
     (k, x - kd)
@@ -122,14 +222,14 @@ pub fn reduce1(x: F) -> (I, F) {
 pub fn trunc_fract(x: F) -> (I, F) {
     let (k, y) = reduce1(x);
     if y < 0.0 {
-        (k - 1, y + 1.0)
+        (k.saturating_sub(1), y + 1.0)
     } else {
         (k, y)
     }
 }
 
 /// Compares x with a with given tolerance.
-pub fn nearly_equal(x: F, a: F, tol: F) -> bool {
+pub const fn nearly_equal(x: F, a: F, tol: F) -> bool {
     abs(x - a) <= tol
 }
 
@@ -155,23 +255,54 @@ fn is_modulo_mask(mut m: U) -> bool {
     true
 }
 
-/// Calculates n modulo m, where m is always positive.
+/// Calculates n modulo m, where m is always positive. For a modulus which is
+/// not a power of two, `I::rem_euclid` gives the same non-negative result also
+/// for negative n.
 pub fn modulo_mask(n: I, m: U) -> U {
     debug_assert!(is_modulo_mask(m));
     (n & (m as I)) as U
 }
 
 /// A shortcut for `F::from_bits`.
-pub fn f(x: U) -> F {
+pub const fn f(x: U) -> F {
     F::from_bits(x)
 }
 
 // Fused-multiply add operation (x * m + a).
-pub fn fma(x: F, m: F, a: F) -> F {
+pub const fn fma(x: F, m: F, a: F) -> F {
     x * m + a
 }
 
-pub fn poly(x: F, coeffs: [U; 5]) -> F {
+/// Computes s + e = a + b exactly, where s is the rounded sum and e is its
+/// rounding error (Knuth's TwoSum).
+pub fn two_sum(a: F, b: F) -> (F, F) {
+    let s = a + b;
+    let bb = s - a;
+    let e = (a - (s - bb)) + (b - bb);
+    (s, e)
+}
+
+/// Computes p + e = a * b exactly, where p is the rounded product and e is its
+/// rounding error (Dekker's TwoProduct). It does not rely on fused
+/// multiply-add, which may not be available.
+pub fn two_prod(a: F, b: F) -> (F, F) {
+    let p = a * b;
+    let (ah, al) = split(a);
+    let (bh, bl) = split(b);
+    let e = ((ah * bh - p) + ah * bl + al * bh) + al * bl;
+    (p, e)
+}
+
+/// Splits x into two halves with 12 significant bits each (Veltkamp's
+/// splitting), so that their products are exact.
+fn split(x: F) -> (F, F) {
+    // 2^12 + 1
+    let t = 4097.0 * x;
+    let h = t - (t - x);
+    (h, x - h)
+}
+
+pub const fn poly(x: F, coeffs: [U; 5]) -> F {
     let p = f(coeffs[4]);
     let p = fma(x, p, f(coeffs[3]));
     let p = fma(x, p, f(coeffs[2]));
@@ -181,6 +312,23 @@ pub fn poly(x: F, coeffs: [U; 5]) -> F {
     p
 }
 
+/// Evaluates a double precision polynomial with coefficients of arbitrary
+/// degree using Horner's scheme.
+#[cfg(not(feature = "bare-metal"))]
+pub fn poly_f64(x: f64, coeffs: &[u64]) -> f64 {
+    coeffs
+        .iter()
+        .rev()
+        .fold(0.0, |p, &c| p * x + f64::from_bits(c))
+}
+
+#[cfg(feature = "table-ln")]
+pub const fn poly3(x: F, coeffs: [U; 3]) -> F {
+    let p = f(coeffs[2]);
+    let p = fma(x, p, f(coeffs[1]));
+    fma(x, p, f(coeffs[0]))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::float::EPSILON;
@@ -229,6 +377,62 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn round_small_f32(x in -2.1e+9f32..2.1e+9) {
+            assert_eq!(super::round_small_f32(x), super::round_small_f64(x));
+        }
+    }
+
+    #[test]
+    fn round_small_f32_special() {
+        for &x in &[
+            0.0, -0.0, 0.5, -0.5, 1.5, -1.5, 2.5, -2.5, 0.49999997, 4194304.5, -4194304.5,
+            8388607.5, -8388607.5, 8388608.0, 16777216.0, -2.1e+9, 2.1e+9,
+        ] {
+            assert_eq!(
+                super::round_small_f32(x),
+                super::round_small_f64(x),
+                "{}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn saturating_round() {
+        for &x in &[2147483648.0, 1e+10, f32::MAX, f32::INFINITY] {
+            assert_eq!(super::saturating_round(x), i32::MAX, "{}", x);
+            assert_eq!(super::saturating_round(-x), i32::MIN, "{}", -x);
+        }
+
+        assert_eq!(super::saturating_round(-2147483648.0), i32::MIN);
+        assert_eq!(super::saturating_round(2147483520.0), 2147483520);
+        assert_eq!(super::saturating_round(-2.5e+9), i32::MIN);
+        assert_eq!(super::saturating_round(f32::NAN), 0);
+
+        // Without saturation, the result is wrapped around.
+        // 1e+10 mod 2^32 = 1410065408.
+        assert_eq!(super::round_small_f64(1e+10), 1410065408);
+
+        assert_eq!(super::reduce1(1e+10).0, i32::MAX);
+        assert_eq!(super::reduce(-1e+10, 2.0, 0.5).0, i32::MIN);
+        assert_eq!(super::trunc_fract(-1e+10).0, i32::MIN);
+    }
+
+    proptest! {
+        #[test]
+        fn reduce_f32(x in -1e+5f32..1e+5) {
+            // Both rounding variants give the same reduction.
+            let cst = core::f32::consts::FRAC_PI_2;
+            let cst_inv = core::f32::consts::FRAC_2_PI;
+            let k = super::round_small_f32(x * cst_inv);
+            let (k_f64, y_f64) = super::reduce(x, cst, cst_inv);
+            assert_eq!(k, k_f64);
+            assert_eq!(x - k as f32 * cst, y_f64);
+        }
+    }
+
     proptest! {
         #[test]
         fn decompose(x: f32) {
@@ -240,6 +444,16 @@ mod tests {
         }
     }
 
+    #[cfg(not(feature = "bare-metal"))]
+    proptest! {
+        #[test]
+        fn decompose_scale_f64(x in 1e-300f64..1e+300) {
+            let (y, n) = super::decompose_f64(x);
+            assert!((1.0..2.0).contains(&y));
+            assert_eq!(super::scale_f64(y, n), x);
+        }
+    }
+
     proptest! {
         #[test]
         fn clamp(x: i32, middle: i32) {
@@ -263,6 +477,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scale_exact() {
+        // As long as the result is normal, scaling must be equal to the exact
+        // multiplication by a power of two. The multiplication is split into
+        // two steps, because 2^128 is not representable.
+        for &x in &[1.0f32, 1.5, 1.999_999_9, 0.75, 3.1, 1234.5678, -0.3] {
+            let (_, e) = super::decompose(x);
+            for k in (-126 - e)..(128 - e) {
+                assert_eq!(
+                    super::scale(x, k).to_bits(),
+                    (x * 2.0f32.powi(k / 2) * 2.0f32.powi(k - k / 2)).to_bits(),
+                    "{} * 2^{}",
+                    x,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scale_saturating() {
+        assert_eq!(super::scale(1.0, i32::MAX), f32::INFINITY);
+        assert_eq!(super::scale(1.0, i32::MIN), 0.0);
+    }
+
+    #[cfg(not(feature = "bare-metal"))]
+    #[test]
+    fn scale_f64_saturating() {
+        assert_eq!(super::scale_f64(1.0, i32::MAX), f64::INFINITY);
+        assert_eq!(super::scale_f64(1.0, i32::MIN), 0.0);
+    }
+
     proptest! {
         #[test]
         fn reduce(x in -100.0f32..100.0, cst in 1.0f32..16.0) {
@@ -275,6 +521,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reduce_inverse() {
+        // The pairs of the constants used in the crate are exercised by the
+        // tests of the corresponding functions.
+        assert_eq!(super::reduce(3.0, 2.0, 0.5), (2, -1.0));
+
+        // The inverse of a huge or a tiny number is not normal and the check
+        // does not apply.
+        assert_eq!(super::reduce(1.0, 3e+38, 1.0 / 3e+38).0, 0);
+        assert_eq!(super::reduce(1.0, f32::INFINITY, 0.0).0, 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "cst_inv is not the inverse of cst")]
+    fn reduce_mismatched() {
+        // The inverse of π/2 paired with ln(2).
+        super::reduce(1.0, core::f32::consts::LN_2, core::f32::consts::FRAC_2_PI);
+    }
+
     #[test]
     fn reduce_special() {
         let data = [
@@ -297,6 +563,36 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn try_reduce(x in -1e+10f32..1e+10, cst in 1.0f32..16.0) {
+            let cst_inv = 1.0 / cst;
+            let q = x * cst_inv;
+            if q.abs() < 2147483648.0 {
+                assert_eq!(super::try_reduce(x, cst, cst_inv), Some(super::reduce(x, cst, cst_inv)));
+            } else {
+                assert_eq!(super::try_reduce(x, cst, cst_inv), None);
+            }
+        }
+    }
+
+    #[test]
+    fn try_reduce_boundary() {
+        // The largest quotient in range and the first one out of it.
+        let max = 2147483648.0f32.nextdown();
+        assert_eq!(super::try_reduce(max, 1.0, 1.0), Some((2147483520, 0.0)));
+        assert_eq!(
+            super::try_reduce(-2147483648.0, 1.0, 1.0),
+            Some((i32::MIN, 0.0))
+        );
+        assert_eq!(super::try_reduce(2147483648.0, 1.0, 1.0), None);
+        assert_eq!(super::try_reduce(-max.nextup().nextup(), 1.0, 1.0), None);
+
+        for &x in &[f32::INFINITY, f32::NEG_INFINITY, f32::NAN, f32::MAX] {
+            assert_eq!(super::try_reduce(x, 2.0, 0.5), None, "{}", x);
+        }
+    }
+
     proptest! {
         #[test]
         fn reduce1(x in -1000.0f32..1000.0) {
@@ -320,6 +616,26 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn two_sum(a in -1e6f32..1e6, b in -1e6f32..1e6) {
+            let (s, e) = super::two_sum(a, b);
+            assert_eq!(s, a + b);
+            // If the transformation is exact, both sides round the same real
+            // number.
+            assert_eq!(s as f64 + e as f64, a as f64 + b as f64);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn two_prod(a in -1e6f32..1e6, b in -1e6f32..1e6) {
+            let (p, e) = super::two_prod(a, b);
+            assert_eq!(p, a * b);
+            assert_eq!(p as f64 + e as f64, a as f64 * b as f64);
+        }
+    }
+
     #[test]
     fn nearly_equal() {
         let data = [0.0, 1.0, -1.0];